@@ -52,4 +52,178 @@ pub enum PredictionMarketError {
     
     #[msg("Market is already resolved")]
     MarketAlreadyResolved,
+    
+    #[msg("Market is not cancelled")]
+    MarketNotCancelled,
+    
+    #[msg("No stake on the winning outcome, cancel the market instead")]
+    NoWinningStake,
+    
+    #[msg("Escrow accounts do not match the market's outcomes")]
+    InvalidSettlementAccounts,
+    
+    #[msg("Collateral mint is not accepted by the platform")]
+    CollateralNotAllowed,
+    
+    #[msg("Mint has a Token-2022 extension that is unsafe for escrows")]
+    UnsupportedMintExtension,
+    
+    #[msg("Accounts do not match the market's collateral mode")]
+    InvalidCollateralMode,
+    
+    #[msg("Bet is below the market's minimum bet")]
+    BetBelowMinimum,
+    
+    #[msg("Bet is above the market's maximum bet per transaction")]
+    BetAboveMaximum,
+    
+    #[msg("Bet would exceed the maximum position per user on this market")]
+    PositionLimitExceeded,
+    
+    #[msg("Bet would exceed the market's maximum pool")]
+    PoolLimitExceeded,
+    
+    #[msg("A maximum bet limit is below the minimum bet")]
+    InvalidBetLimits,
+    
+    #[msg("User is self-excluded from betting")]
+    SelfExcluded,
+    
+    #[msg("Bet would exceed the user's daily deposit limit")]
+    DailyDepositLimitExceeded,
+    
+    #[msg("Bet would exceed the user's weekly deposit limit")]
+    WeeklyDepositLimitExceeded,
+    
+    #[msg("Limits can only be loosened once the cooldown has passed")]
+    ProfileCooldownActive,
+    
+    #[msg("User is not on the allowlist for this market")]
+    NotAllowlisted,
+    
+    #[msg("User has no valid credential from the market's attestation authority")]
+    InvalidCredential,
+    
+    #[msg("Referrer accounts are missing or refer the bettor")]
+    InvalidReferrer,
+    
+    #[msg("Referral share cannot exceed 10000 basis points")]
+    InvalidReferralShare,
+    
+    #[msg("Creator fee is above the platform maximum")]
+    CreatorFeeTooHigh,
+    
+    #[msg("Market already has bets")]
+    MarketHasBets,
+    
+    #[msg("Fee is above the platform maximum")]
+    FeeTooHigh,
+    
+    #[msg("A fee tier discount exceeds 10000 basis points")]
+    InvalidFeeTiers,
+    
+    #[msg("Account is not in a layout this migration converts")]
+    InvalidMigration,
+    
+    #[msg("Treasury accounts are missing or do not match the market's collateral")]
+    InvalidTreasury,
+    
+    #[msg("Market already has the maximum number of outcomes")]
+    TooManyOutcomes,
+    
+    #[msg("Market already has an outcome with this ID")]
+    DuplicateOutcome,
+    
+    #[msg("Name is too long")]
+    NameTooLong,
+    
+    #[msg("Description is too long")]
+    DescriptionTooLong,
+    
+    #[msg("Market book is missing or belongs to another market")]
+    InvalidMarketBook,
+    
+    #[msg("Market already belongs to a tournament")]
+    MarketAlreadyInTournament,
+    
+    #[msg("Tournament already has the maximum number of matches")]
+    TooManyTournamentMatches,
+    
+    #[msg("Tournament already has a grand final")]
+    GrandFinalAlreadySet,
+    
+    #[msg("Tournament is missing or does not match the market")]
+    InvalidTournament,
+    
+    #[msg("Outcome has been eliminated")]
+    OutcomeEliminated,
+    
+    #[msg("The tournament's grand final has not been resolved")]
+    GrandFinalNotResolved,
+    
+    #[msg("Parlay leg is not an open, ungated outcome of a market in the house's collateral, or repeats a market")]
+    InvalidParlayLeg,
+    
+    #[msg("Parlay has too few or too many legs")]
+    InvalidParlayLegCount,
+    
+    #[msg("Parlay is above the house's stake, payout or liability limit")]
+    ParlayLimitExceeded,
+    
+    #[msg("Parlay still has legs waiting on their markets")]
+    ParlayNotSettled,
+    
+    #[msg("Parlay has already been settled")]
+    ParlayAlreadySettled,
+    
+    #[msg("Condition is missing or its parent market is invalid")]
+    InvalidCondition,
+    
+    #[msg("The parent market of this conditional market has not finished")]
+    ConditionNotSettled,
+    
+    #[msg("Prop is missing, or the market is or is not a prop market")]
+    InvalidProp,
+    
+    #[msg("URI is too long")]
+    UriTooLong,
+    
+    #[msg("Team is missing or does not match the outcome")]
+    InvalidTeam,
+    
+    #[msg("Liquidity pool is missing or does not match the market")]
+    InvalidLiquidityPool,
+    
+    #[msg("Liquidity amount must be above zero and within the LP supply")]
+    InvalidLiquidityAmount,
+    
+    #[msg("Liquidity can only be removed before the market closes or once it is settled")]
+    LiquidityLocked,
+    
+    #[msg("Fee share cannot exceed 100%")]
+    InvalidFeeShare,
+    
+    #[msg("Not available in this market's betting mode")]
+    InvalidMarketMode,
+    
+    #[msg("Fixed odds must be above even")]
+    InvalidOdds,
+    
+    #[msg("Bet would take the outcome's liability above the market's maximum")]
+    LiabilityLimitExceeded,
+    
+    #[msg("House is missing or does not match the market's collateral")]
+    InvalidHouse,
+    
+    #[msg("Losing escrows are still being swept into the winning escrow")]
+    SettlementPending,
+    
+    #[msg("Parlay limits must cap the payout, and leg odds above even")]
+    InvalidParlayLimits,
+    
+    #[msg("Outright winner must back the team that won the grand final")]
+    NotGrandFinalWinner,
+    
+    #[msg("Referrals and liquidity earn nothing on markets charging their fee at bet time")]
+    FeeChargedAtBet,
 }
\ No newline at end of file
@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use anchor_spl::token_2022::{self, spl_token_2022};
+use anchor_spl::associated_token::AssociatedToken;
+use spl_token_2022::extension::{BaseStateWithExtensions, ExtensionType, StateWithExtensions};
+use crate::state::{CollateralConfig, PlatformConfig};
+use crate::errors::PredictionMarketError;
+
+/// Token-2022 extensions that let tokens leave an escrow without the
+/// program's signature, or that the program's transfers can't satisfy
+const UNSUPPORTED_MINT_EXTENSIONS: [ExtensionType; 3] = [
+    ExtensionType::PermanentDelegate,
+    ExtensionType::NonTransferable,
+    ExtensionType::TransferHook,
+];
+
+/// Adds a mint to the collateral allowlist and opens its treasury, so fees
+/// in each collateral are kept apart.
+pub fn add_collateral_mint(ctx: Context<AddCollateralMint>) -> Result<()> {
+    // Validations
+    let mint_info = ctx.accounts.mint.to_account_info();
+    if *mint_info.owner == token_2022::ID {
+        let data = mint_info.try_borrow_data()?;
+        let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+        for extension in mint.get_extension_types()? {
+            require!(
+                !UNSUPPORTED_MINT_EXTENSIONS.contains(&extension),
+                PredictionMarketError::UnsupportedMintExtension
+            );
+        }
+    }
+    
+    let collateral_config = &mut ctx.accounts.collateral_config;
+    
+    collateral_config.version = CollateralConfig::VERSION;
+    collateral_config.mint = ctx.accounts.mint.key();
+    collateral_config.treasury = ctx.accounts.treasury_token_account.key();
+    collateral_config.enabled = true;
+    collateral_config.bump = ctx.bumps.collateral_config;
+    
+    msg!("Collateral mint added: {}", collateral_config.mint);
+    msg!("Treasury: {}", collateral_config.treasury);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AddCollateralMint<'info> {
+    #[account(
+        seeds = [PlatformConfig::SEED_PREFIX.as_bytes()],
+        bump = platform_config.bump,
+        constraint = platform_config.admin == admin.key() @ PredictionMarketError::UnauthorizedAdmin
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    /// The collateral mint being accepted
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        init,
+        payer = admin,
+        space = CollateralConfig::SIZE,
+        seeds = [CollateralConfig::SEED_PREFIX.as_bytes(), mint.key().as_ref()],
+        bump
+    )]
+    pub collateral_config: Account<'info, CollateralConfig>,
+    
+    /// CHECK: This is the PDA that has authority over the treasury
+    #[account(
+        seeds = [PlatformConfig::SEED_PREFIX.as_bytes(), b"treasury"],
+        bump
+    )]
+    pub treasury_authority: UncheckedAccount<'info>,
+    
+    // Anyone can create an associated token account, so don't fail if it exists
+    #[account(
+        init_if_needed,
+        payer = admin,
+        associated_token::mint = mint,
+        associated_token::authority = treasury_authority,
+        associated_token::token_program = token_program,
+    )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
\ No newline at end of file
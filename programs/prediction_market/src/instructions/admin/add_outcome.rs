@@ -1,33 +1,121 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, TokenAccount, Mint};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use anchor_spl::associated_token::AssociatedToken;
-use crate::state::{Market, Outcome, MarketStatus};
+use crate::state::{Market, MarketBook, Outcome, MarketStatus, PlatformConfig, Team, TeamOutcome, MAX_OUTCOME_NAME_LEN};
 use crate::errors::PredictionMarketError;
+use super::seed_odds::seed;
 
+/// Adds an outcome to a market, as the admin or the market's creator, before
+/// anything is staked on it. Passing a `team`, along with its `team_outcome`
+/// index, records it as the team whose win the outcome stands for.
+///
+/// A team outcome that makes the market a head-to-head also takes the other
+/// outcome as `opponent_outcome`, and its team as `opponent_team` if it
+/// backs one. Two teams then open at the odds of their ratings, as
+/// `seed_odds` would price them, rather than even.
 pub fn add_outcome(ctx: Context<AddOutcome>, name: String, outcome_id: u8) -> Result<()> {
     let market = &mut ctx.accounts.market;
     let outcome = &mut ctx.accounts.outcome;
     
+    // Validations
     require!(
         market.status == MarketStatus::Active,
         PredictionMarketError::MarketNotActive
     );
     
+    // An outcome added later would dilute the odds bets were placed at, and
+    // hold none of the liquidity pool's stake
+    require!(
+        market.total_pool == 0 && !market.has_liquidity,
+        PredictionMarketError::MarketHasBets
+    );
+    
+    // Prop markets only ever have their generated outcomes
+    require!(
+        !market.has_prop,
+        PredictionMarketError::InvalidProp
+    );
+    
+    require!(
+        name.len() <= MAX_OUTCOME_NAME_LEN,
+        PredictionMarketError::NameTooLong
+    );
+    
+    require!(
+        ctx.accounts.team.is_some() == ctx.accounts.team_outcome.is_some(),
+        PredictionMarketError::InvalidTeam
+    );
+    
     // Initialize outcome
+    outcome.version = Outcome::VERSION;
     outcome.id = outcome_id;
     outcome.market = market.key();
     outcome.name = name;
     outcome.escrow_pubkey = ctx.accounts.escrow_token_account.key();
     outcome.total_staked = 0;
+    outcome.total_claimed = 0;
+    outcome.claimants_remaining = 0;
+    outcome.discounted_stake = 0;
     outcome.odds = 10000; // Default 1:1 odds (represented as 1.0000)
     outcome.bump = ctx.bumps.outcome;
+    outcome.team = ctx.accounts.team.as_ref().map(|team| team.id);
+    outcome.liability = 0;
+    outcome.player = None;
+    
+    if let (Some(team), Some(team_outcome)) = (&ctx.accounts.team, ctx.accounts.team_outcome.as_mut()) {
+        team_outcome.version = TeamOutcome::VERSION;
+        team_outcome.team = team.id;
+        team_outcome.market = market.key();
+        team_outcome.outcome = outcome.key();
+        team_outcome.bump = ctx.bumps.team_outcome.unwrap_or_default();
+    }
     
     // Add outcome ID to market's outcomes list
     market.outcomes.push(outcome.id);
     
+    // Markets with a book list every outcome in it
+    if market.has_book {
+        let market_book = ctx.accounts.market_book
+            .as_ref()
+            .ok_or(PredictionMarketError::InvalidMarketBook)?;
+        market_book.load_mut()?.add_outcome(outcome.id)?;
+    }
+    
+    // Fixed-odds markets open at whatever the oracle posts
+    let completes_head_to_head = market.outcomes.len() == 2 && !market.has_fixed_odds;
+    if let (Some(team), true) = (&ctx.accounts.team, completes_head_to_head) {
+        let opponent_outcome = ctx.accounts.opponent_outcome
+            .as_mut()
+            .ok_or(PredictionMarketError::InvalidTeam)?;
+        require!(
+            opponent_outcome.id != outcome.id,
+            PredictionMarketError::OutcomeNotFound
+        );
+        
+        match (opponent_outcome.team, &ctx.accounts.opponent_team) {
+            (Some(opponent_id), Some(opponent_team)) => {
+                require!(
+                    opponent_team.id == opponent_id,
+                    PredictionMarketError::InvalidTeam
+                );
+                seed(
+                    market,
+                    ctx.accounts.market_book.as_ref(),
+                    (outcome, team),
+                    (opponent_outcome, opponent_team),
+                )?;
+            }
+            (None, None) => {}
+            _ => return err!(PredictionMarketError::InvalidTeam),
+        }
+    }
+    
     msg!("Outcome added: {}", outcome.name);
     msg!("Outcome ID: {}", outcome.id);
     msg!("Escrow token account created: {}", ctx.accounts.escrow_token_account.key());
+    if let Some(team) = &ctx.accounts.team {
+        msg!("Team: {}", team.name);
+    }
     
     Ok(())
 }
@@ -35,26 +123,39 @@ pub fn add_outcome(ctx: Context<AddOutcome>, name: String, outcome_id: u8) -> Re
 #[derive(Accounts)]
 #[instruction(name: String, outcome_id: u8)]
 pub struct AddOutcome<'info> {
+    /// Grown to the current size if it was created with room for fewer
+    /// outcomes, and past it for each outcome a book market adds beyond
+    /// `MAX_OUTCOMES`
     #[account(
         mut,
-        constraint = market.status == MarketStatus::Active @ PredictionMarketError::MarketNotActive
+        constraint = market.status == MarketStatus::Active @ PredictionMarketError::MarketNotActive,
+        constraint = market.outcomes.len() < market.max_outcomes() @ PredictionMarketError::TooManyOutcomes,
+        constraint = !market.outcomes.contains(&outcome_id) @ PredictionMarketError::DuplicateOutcome,
+        realloc = Market::space_for(market.outcomes.len() + 1),
+        realloc::payer = authority,
+        realloc::zero = false
     )]
     pub market: Account<'info, Market>,
     
-    #[account(mut)]
+    #[account(
+        seeds = [PlatformConfig::SEED_PREFIX.as_bytes()],
+        bump = platform_config.bump
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    
+    #[account(
+        mut,
+        constraint = authority.key() == platform_config.admin
+            || authority.key() == market.creator @ PredictionMarketError::UnauthorizedAdmin
+    )]
     pub authority: Signer<'info>,
     
+    /// An existing outcome is loaded rather than failing to be created, so
+    /// that `market` rejects it as a duplicate
     #[account(
-        init,
+        init_if_needed,
         payer = authority,
-        space = 8 +   // Discriminator
-               1 +    // id
-               32 +   // market
-               4 + name.len() + // name (String)
-               32 +   // escrow_pubkey
-               8 +    // total_staked
-               8 +    // odds (u64)
-               1,     // bump
+        space = Outcome::SIZE,
         seeds = [b"outcome", market.key().as_ref(), &outcome_id.to_le_bytes()],
         bump
     )]
@@ -68,17 +169,60 @@ pub struct AddOutcome<'info> {
     pub escrow_authority: UncheckedAccount<'info>,
     
     /// The token mint for the escrow account
-    pub mint: Account<'info, Mint>,
+    #[account(address = market.collateral_mint @ PredictionMarketError::InvalidMint)]
+    pub mint: InterfaceAccount<'info, Mint>,
     
     #[account(
-        init,
+        init_if_needed,
         payer = authority,
         associated_token::mint = mint,
         associated_token::authority = escrow_authority,
+        associated_token::token_program = token_program,
+    )]
+    pub escrow_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// Required on markets with a book
+    #[account(
+        mut,
+        seeds = [MarketBook::SEED_PREFIX.as_bytes(), market.key().as_ref()],
+        bump = market_book.load()?.bump
+    )]
+    pub market_book: Option<AccountLoader<'info, MarketBook>>,
+    
+    /// The team this outcome backs, if any
+    #[account(
+        seeds = [Team::SEED_PREFIX.as_bytes(), team.id.to_le_bytes().as_ref()],
+        bump = team.bump
+    )]
+    pub team: Option<Account<'info, Team>>,
+    
+    /// Indexes the outcome under `team`, required with it
+    #[account(
+        init,
+        payer = authority,
+        space = TeamOutcome::SIZE,
+        seeds = [TeamOutcome::SEED_PREFIX.as_bytes(), outcome.key().as_ref()],
+        bump
+    )]
+    pub team_outcome: Option<Account<'info, TeamOutcome>>,
+    
+    /// The market's other outcome, required when a `team` makes the market
+    /// a head-to-head
+    #[account(
+        mut,
+        seeds = [b"outcome", market.key().as_ref(), &opponent_outcome.id.to_le_bytes()],
+        bump = opponent_outcome.bump
+    )]
+    pub opponent_outcome: Option<Account<'info, Outcome>>,
+    
+    /// The team `opponent_outcome` backs, if any
+    #[account(
+        seeds = [Team::SEED_PREFIX.as_bytes(), opponent_team.id.to_le_bytes().as_ref()],
+        bump = opponent_team.bump
     )]
-    pub escrow_token_account: Account<'info, TokenAccount>,
+    pub opponent_team: Option<Account<'info, Team>>,
     
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
\ No newline at end of file
@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use crate::state::{AllowlistEntry, PlatformConfig};
+use crate::errors::PredictionMarketError;
+
+/// Marks `user` as verified for every allowlist-gated market
+pub fn add_to_allowlist(ctx: Context<AddToAllowlist>, user: Pubkey) -> Result<()> {
+    let allowlist_entry = &mut ctx.accounts.allowlist_entry;
+    let clock = Clock::get()?;
+    
+    allowlist_entry.version = AllowlistEntry::VERSION;
+    allowlist_entry.user = user;
+    allowlist_entry.added_at = clock.unix_timestamp;
+    allowlist_entry.bump = ctx.bumps.allowlist_entry;
+    
+    msg!("User {} added to the allowlist", user);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct AddToAllowlist<'info> {
+    #[account(
+        seeds = [PlatformConfig::SEED_PREFIX.as_bytes()],
+        bump = platform_config.bump,
+        constraint = platform_config.admin == admin.key() @ PredictionMarketError::UnauthorizedAdmin
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        init,
+        payer = admin,
+        space = AllowlistEntry::SIZE,
+        seeds = [AllowlistEntry::SEED_PREFIX.as_bytes(), user.as_ref()],
+        bump
+    )]
+    pub allowlist_entry: Account<'info, AllowlistEntry>,
+    
+    pub system_program: Program<'info, System>,
+}
\ No newline at end of file
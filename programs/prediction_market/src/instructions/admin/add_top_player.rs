@@ -0,0 +1,128 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use anchor_spl::associated_token::AssociatedToken;
+use crate::state::{Market, MarketBook, MarketStatus, Outcome, Player, PlatformConfig, Prop, PropKind};
+use crate::errors::PredictionMarketError;
+use super::create_prop_market::init_outcome;
+
+/// Adds `player` to a top player prop as outcome `outcome_id`, named after
+/// the player's handle, before anything is staked on the market. Each
+/// player should be added once.
+pub fn add_top_player(ctx: Context<AddTopPlayer>, outcome_id: u8) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let player = &ctx.accounts.player;
+    
+    // Validations
+    require!(
+        market.total_pool == 0 && !market.has_liquidity,
+        PredictionMarketError::MarketHasBets
+    );
+    
+    require!(
+        market.has_prop && ctx.accounts.prop.kind == PropKind::TopPlayer,
+        PredictionMarketError::InvalidProp
+    );
+    
+    let outcome = &mut ctx.accounts.outcome;
+    init_outcome(
+        outcome,
+        outcome_id,
+        market.key(),
+        ctx.accounts.prop.player_outcome_name(&player.handle),
+        ctx.accounts.escrow_token_account.key(),
+        ctx.bumps.outcome,
+    );
+    outcome.player = Some(player.id);
+    market.outcomes.push(outcome_id);
+    
+    // Markets with a book list every outcome in it
+    if market.has_book {
+        let market_book = ctx.accounts.market_book
+            .as_ref()
+            .ok_or(PredictionMarketError::InvalidMarketBook)?;
+        market_book.load_mut()?.add_outcome(outcome_id)?;
+    }
+    
+    msg!("Outcome added: {}", outcome.name);
+    msg!("Outcome ID: {}", outcome.id);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(outcome_id: u8)]
+pub struct AddTopPlayer<'info> {
+    #[account(
+        mut,
+        constraint = market.status == MarketStatus::Active @ PredictionMarketError::MarketNotActive,
+        constraint = market.outcomes.len() < market.max_outcomes() @ PredictionMarketError::TooManyOutcomes,
+        constraint = !market.outcomes.contains(&outcome_id) @ PredictionMarketError::DuplicateOutcome,
+        realloc = Market::space_for(market.outcomes.len() + 1),
+        realloc::payer = admin,
+        realloc::zero = false
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        seeds = [Prop::SEED_PREFIX.as_bytes(), market.key().as_ref()],
+        bump = prop.bump
+    )]
+    pub prop: Account<'info, Prop>,
+    
+    #[account(
+        seeds = [Player::SEED_PREFIX.as_bytes(), player.id.to_le_bytes().as_ref()],
+        bump = player.bump
+    )]
+    pub player: Account<'info, Player>,
+    
+    #[account(
+        init,
+        payer = admin,
+        space = Outcome::SIZE,
+        seeds = [b"outcome", market.key().as_ref(), &outcome_id.to_le_bytes()],
+        bump
+    )]
+    pub outcome: Account<'info, Outcome>,
+    
+    /// CHECK: This is the PDA that will have authority over the escrow
+    #[account(
+        seeds = [b"escrow", market.key().as_ref(), &outcome_id.to_le_bytes()],
+        bump,
+    )]
+    pub escrow_authority: UncheckedAccount<'info>,
+    
+    #[account(
+        init_if_needed,
+        payer = admin,
+        associated_token::mint = mint,
+        associated_token::authority = escrow_authority,
+        associated_token::token_program = token_program,
+    )]
+    pub escrow_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// Required on markets with a book
+    #[account(
+        mut,
+        seeds = [MarketBook::SEED_PREFIX.as_bytes(), market.key().as_ref()],
+        bump = market_book.load()?.bump
+    )]
+    pub market_book: Option<AccountLoader<'info, MarketBook>>,
+    
+    /// The token mint for the escrow account
+    #[account(address = market.collateral_mint @ PredictionMarketError::InvalidMint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        seeds = [PlatformConfig::SEED_PREFIX.as_bytes()],
+        bump = platform_config.bump,
+        constraint = platform_config.admin == admin.key() @ PredictionMarketError::UnauthorizedAdmin
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
\ No newline at end of file
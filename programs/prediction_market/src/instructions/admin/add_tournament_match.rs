@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+use crate::state::{Market, PlatformConfig, Tournament, TournamentMarket};
+use crate::errors::PredictionMarketError;
+
+/// Links a match market to a tournament. The outright can only be resolved
+/// once the match linked as the grand final has been.
+///
+/// A match already linked when it was created can be passed again to mark
+/// it as the grand final.
+pub fn add_tournament_match(ctx: Context<AddTournamentMatch>, is_grand_final: bool) -> Result<()> {
+    let tournament = &mut ctx.accounts.tournament;
+    let match_market = &mut ctx.accounts.match_market;
+    let is_linked = tournament.matches.contains(&match_market.key());
+    
+    // Validations
+    require!(
+        match_market.tournament.is_none() || (is_linked && is_grand_final),
+        PredictionMarketError::MarketAlreadyInTournament
+    );
+    
+    require!(
+        !is_grand_final || tournament.grand_final.is_none(),
+        PredictionMarketError::GrandFinalAlreadySet
+    );
+    
+    if !is_linked {
+        tournament.link_match(match_market.key())?;
+    }
+    if is_grand_final {
+        tournament.grand_final = Some(match_market.key());
+    }
+    match_market.tournament = Some(tournament.id);
+    
+    let tournament_market = &mut ctx.accounts.tournament_market;
+    tournament_market.version = TournamentMarket::VERSION;
+    tournament_market.tournament = tournament.id;
+    tournament_market.market = match_market.key();
+    tournament_market.bump = ctx.bumps.tournament_market;
+    
+    msg!("Match added to tournament {}: {}", tournament.id, match_market.name);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AddTournamentMatch<'info> {
+    #[account(
+        mut,
+        seeds = [Tournament::SEED_PREFIX.as_bytes(), tournament.id.to_le_bytes().as_ref()],
+        bump = tournament.bump
+    )]
+    pub tournament: Account<'info, Tournament>,
+    
+    #[account(mut)]
+    pub match_market: Account<'info, Market>,
+    
+    /// Indexes the match under the tournament, already there if it was
+    /// linked at creation
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = TournamentMarket::SIZE,
+        seeds = [TournamentMarket::SEED_PREFIX.as_bytes(), match_market.key().as_ref()],
+        bump
+    )]
+    pub tournament_market: Account<'info, TournamentMarket>,
+    
+    #[account(
+        seeds = [PlatformConfig::SEED_PREFIX.as_bytes()],
+        bump = platform_config.bump,
+        constraint = platform_config.admin == admin.key() @ PredictionMarketError::UnauthorizedAdmin
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
\ No newline at end of file
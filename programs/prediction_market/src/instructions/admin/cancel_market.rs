@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
-use crate::state::{Market, MarketStatus, PlatformConfig};
+use crate::state::{House, Market, MarketBook, MarketStatus, PlatformConfig};
 use crate::errors::PredictionMarketError;
+use super::resolve_market::refund;
 
 pub fn cancel_market(ctx: Context<CancelMarket>) -> Result<()> {
     let market = &mut ctx.accounts.market;
@@ -12,8 +13,9 @@ pub fn cancel_market(ctx: Context<CancelMarket>) -> Result<()> {
         PredictionMarketError::MarketAlreadyResolved
     );
     
-    // Update market status
-    market.status = MarketStatus::Cancelled;
+    // Update market status, pricing the refunds in any book and reserving
+    // them in any house
+    refund(market, ctx.accounts.market_book.as_ref(), ctx.accounts.house.as_mut())?;
     
     msg!("Market cancelled: {}", market.name);
     msg!("Cancelled by admin: {}", admin.key());
@@ -38,4 +40,20 @@ pub struct CancelMarket<'info> {
     
     #[account(mut)]
     pub admin: Signer<'info>,
+    
+    /// Required on markets with a book
+    #[account(
+        mut,
+        seeds = [MarketBook::SEED_PREFIX.as_bytes(), market.key().as_ref()],
+        bump = market_book.load()?.bump
+    )]
+    pub market_book: Option<AccountLoader<'info, MarketBook>>,
+    
+    /// The house in the market's collateral, required on fixed-odds markets
+    #[account(
+        mut,
+        seeds = [House::SEED_PREFIX.as_bytes(), market.collateral_mint.as_ref()],
+        bump = house.bump
+    )]
+    pub house: Option<Account<'info, House>>,
 }
\ No newline at end of file
@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::{CollateralConfig, House, LegLimits, ParlayLimits, PlatformConfig};
+use crate::errors::PredictionMarketError;
+
+/// Opens the house for parlays in an accepted collateral. The house pays
+/// parlays out of its vault, which is funded by transferring tokens to it.
+pub fn create_house(ctx: Context<CreateHouse>, limits: ParlayLimits, leg_limits: LegLimits) -> Result<()> {
+    // Validations
+    require!(
+        limits.is_valid(),
+        PredictionMarketError::InvalidParlayLegCount
+    );
+    
+    require!(
+        limits.caps_payout() && leg_limits.is_valid(),
+        PredictionMarketError::InvalidParlayLimits
+    );
+    
+    let house = &mut ctx.accounts.house;
+    house.version = House::VERSION;
+    house.mint = ctx.accounts.mint.key();
+    house.vault = ctx.accounts.vault.key();
+    house.liability = 0;
+    house.limits = limits;
+    house.leg_limits = leg_limits;
+    house.bump = ctx.bumps.house;
+    
+    msg!("House opened for mint: {}", house.mint);
+    msg!("Vault: {}", house.vault);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CreateHouse<'info> {
+    #[account(
+        seeds = [PlatformConfig::SEED_PREFIX.as_bytes()],
+        bump = platform_config.bump,
+        constraint = platform_config.admin == admin.key() @ PredictionMarketError::UnauthorizedAdmin
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    /// The collateral parlays are staked and paid in
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        seeds = [CollateralConfig::SEED_PREFIX.as_bytes(), mint.key().as_ref()],
+        bump = collateral_config.bump
+    )]
+    pub collateral_config: Account<'info, CollateralConfig>,
+    
+    #[account(
+        init,
+        payer = admin,
+        space = House::SIZE,
+        seeds = [House::SEED_PREFIX.as_bytes(), mint.key().as_ref()],
+        bump
+    )]
+    pub house: Account<'info, House>,
+    
+    // Anyone can create an associated token account, so don't fail if it exists
+    #[account(
+        init_if_needed,
+        payer = admin,
+        associated_token::mint = mint,
+        associated_token::authority = house,
+        associated_token::token_program = token_program,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
\ No newline at end of file
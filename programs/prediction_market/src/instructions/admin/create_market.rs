@@ -1,14 +1,26 @@
 use anchor_lang::prelude::*;
-use crate::state::{PlatformConfig, Market, MarketStatus};
+use anchor_spl::token::spl_token;
+use anchor_spl::token_2022::spl_token_2022;
+use anchor_spl::token_interface::Mint;
+use crate::state::{
+    AccessGate, CollateralConfig, CollateralMode, FeeModel, PlatformConfig, Market, MarketStatus, Tournament,
+    TournamentMarket, MAX_MARKET_DESCRIPTION_LEN, MAX_MARKET_NAME_LEN,
+};
 use crate::errors::PredictionMarketError;
 
+/// Creates a market. Passing a `tournament`, along with its
+/// `tournament_market` index, links it as one of the tournament's matches,
+/// as `add_tournament_match` would.
+#[allow(clippy::too_many_arguments)]
 pub fn create_market(
     ctx: Context<CreateMarket>,
     name: String,
     description: String,
     start_time: i64,
     end_time: i64,
-    custom_fee_percentage: Option<u8>,
+    custom_fee_bps: Option<u16>,
+    custom_oracle: Option<Pubkey>,
+    collateral_mode: CollateralMode,
 ) -> Result<()> {
     let platform_config = &mut ctx.accounts.platform_config;
     let market = &mut ctx.accounts.market;
@@ -31,7 +43,43 @@ pub fn create_market(
         PredictionMarketError::InvalidStartTime
     );
     
+    require!(
+        name.len() <= MAX_MARKET_NAME_LEN,
+        PredictionMarketError::NameTooLong
+    );
+    
+    require!(
+        description.len() <= MAX_MARKET_DESCRIPTION_LEN,
+        PredictionMarketError::DescriptionTooLong
+    );
+    
+    require!(
+        ctx.accounts.collateral_config.enabled,
+        PredictionMarketError::CollateralNotAllowed
+    );
+    
+    let fee_bps = custom_fee_bps.unwrap_or(platform_config.default_fee_bps);
+    require!(
+        fee_bps <= PlatformConfig::MAX_FEE_BPS,
+        PredictionMarketError::FeeTooHigh
+    );
+    
+    require!(
+        ctx.accounts.tournament.is_some() == ctx.accounts.tournament_market.is_some(),
+        PredictionMarketError::InvalidTournament
+    );
+    
+    // Native SOL markets wrap lamports, so their escrows must hold wSOL
+    let mint_key = ctx.accounts.mint.key();
+    require!(
+        collateral_mode == CollateralMode::Token
+            || mint_key == spl_token::native_mint::ID
+            || mint_key == spl_token_2022::native_mint::ID,
+        PredictionMarketError::InvalidCollateralMode
+    );
+    
     // Initialize market data
+    market.version = Market::VERSION;
     market.id = platform_config.markets_count;
     market.name = name;
     market.description = description;
@@ -42,17 +90,47 @@ pub fn create_market(
     market.winner = None;
     market.start_time = start_time;
     market.end_time = end_time;
-    market.fee_percentage = custom_fee_percentage.unwrap_or(platform_config.default_fee_percentage);
-    market.oracle = platform_config.oracle_authority;
+    market.fee_bps = fee_bps;
+    market.fee_model = FeeModel::TotalPool;
+    market.oracle = custom_oracle.unwrap_or(platform_config.oracle_authority);
+    market.collateral_mint = mint_key;
+    market.collateral_mode = collateral_mode;
+    market.bet_limits = platform_config.default_bet_limits;
+    market.access_gate = AccessGate::Open;
+    market.referral_share_bps = platform_config.referral_share_bps;
+    market.creator_fee_bps = 0;
+    market.fee_rebates = 0;
     market.status = MarketStatus::Active;
     market.bump = ctx.bumps.market;
+    market.has_book = false;
+    market.tournament = None;
+    market.has_condition = false;
+    market.has_prop = false;
+    market.has_liquidity = false;
+    market.has_fixed_odds = false;
+    market.max_liability = 0;
+    market.house_reserve = 0;
+    
+    if let (Some(tournament), Some(tournament_market)) =
+        (ctx.accounts.tournament.as_mut(), ctx.accounts.tournament_market.as_mut())
+    {
+        tournament.link_match(market.key())?;
+        market.tournament = Some(tournament.id);
+        
+        tournament_market.version = TournamentMarket::VERSION;
+        tournament_market.tournament = tournament.id;
+        tournament_market.market = market.key();
+        tournament_market.bump = ctx.bumps.tournament_market.unwrap_or_default();
+        msg!("Tournament: {}", tournament.name);
+    }
     
     platform_config.markets_count = platform_config.markets_count.checked_add(1).unwrap();
     
     msg!("Market created by admin: {}", market.name);
     msg!("Market ID: {}", market.id);
     msg!("Admin: {}", creator.key());
-
+    msg!("Collateral mint: {}", market.collateral_mint);
+    
     Ok(())
 }
 
@@ -62,7 +140,9 @@ pub fn create_market(
     description: String,
     start_time: i64,
     end_time: i64,
-    custom_fee_percentage: Option<u8>
+    custom_fee_bps: Option<u16>,
+    custom_oracle: Option<Pubkey>,
+    collateral_mode: CollateralMode
 )]
 pub struct CreateMarket<'info> {
     #[account(
@@ -81,25 +161,38 @@ pub struct CreateMarket<'info> {
     #[account(
         init,
         payer = creator,
-        space = 8 +  // Discriminator
-               8 +   // id
-               4 + name.len() + // name (String)
-               4 + description.len() + // description (String)
-               32 +  // creator
-               4 + 32 * 10 + // outcomes (Vec<u8> with estimated capacity)
-               8 +   // total_pool
-               1 +   // resolved
-               1 + 1 + // winner (Option<u8>)
-               8 +   // start_time
-               8 +   // end_time
-               1 +   // fee_percentage
-               32 +  // oracle
-               1 +   // status
-               1,    // bump
+        space = Market::SIZE,
         seeds = [b"market", platform_config.markets_count.to_le_bytes().as_ref()],
         bump
     )]
     pub market: Account<'info, Market>,
     
+    /// The collateral staked on this market
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        seeds = [CollateralConfig::SEED_PREFIX.as_bytes(), mint.key().as_ref()],
+        bump = collateral_config.bump
+    )]
+    pub collateral_config: Account<'info, CollateralConfig>,
+    
+    /// The tournament this market is a match of, if any
+    #[account(
+        mut,
+        seeds = [Tournament::SEED_PREFIX.as_bytes(), tournament.id.to_le_bytes().as_ref()],
+        bump = tournament.bump
+    )]
+    pub tournament: Option<Account<'info, Tournament>>,
+    
+    /// Indexes the market under `tournament`, required with it
+    #[account(
+        init,
+        payer = creator,
+        space = TournamentMarket::SIZE,
+        seeds = [TournamentMarket::SEED_PREFIX.as_bytes(), market.key().as_ref()],
+        bump
+    )]
+    pub tournament_market: Option<Account<'info, TournamentMarket>>,
+    
     pub system_program: Program<'info, System>,
 }
\ No newline at end of file
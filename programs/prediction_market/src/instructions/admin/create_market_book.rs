@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use crate::state::{Market, MarketBook, MarketStatus, PlatformConfig};
+use crate::errors::PredictionMarketError;
+
+/// Gives a market a `MarketBook`, which every bet then reprices. Only
+/// possible before the first bet, so the book's stakes start from zero.
+pub fn create_market_book(ctx: Context<CreateMarketBook>) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    
+    // Validations
+    require!(
+        market.status == MarketStatus::Active,
+        PredictionMarketError::MarketNotActive
+    );
+    
+    require!(
+        market.total_pool == 0,
+        PredictionMarketError::MarketHasBets
+    );
+    
+    // Fixed-odds markets have no pool to price
+    require!(
+        !market.has_fixed_odds,
+        PredictionMarketError::InvalidMarketMode
+    );
+    
+    let mut market_book = ctx.accounts.market_book.load_init()?;
+    market_book.version = MarketBook::VERSION;
+    market_book.bump = ctx.bumps.market_book;
+    market_book.market = market.key();
+    for outcome_id in market.outcomes.iter() {
+        market_book.add_outcome(*outcome_id)?;
+    }
+    
+    market.has_book = true;
+    
+    msg!("Market {} book created with {} outcomes", market.id, market_book.outcome_count);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CreateMarketBook<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        init,
+        payer = admin,
+        space = MarketBook::SIZE,
+        seeds = [MarketBook::SEED_PREFIX.as_bytes(), market.key().as_ref()],
+        bump
+    )]
+    pub market_book: AccountLoader<'info, MarketBook>,
+    
+    #[account(
+        seeds = [PlatformConfig::SEED_PREFIX.as_bytes()],
+        bump = platform_config.bump,
+        constraint = platform_config.admin == admin.key() @ PredictionMarketError::UnauthorizedAdmin
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
\ No newline at end of file
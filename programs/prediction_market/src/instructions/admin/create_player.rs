@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+use crate::state::{Player, PlatformConfig, Team, MAX_PLAYER_HANDLE_LEN};
+use crate::errors::PredictionMarketError;
+
+/// Registers a player on `team`.
+pub fn create_player(ctx: Context<CreatePlayer>, handle: String) -> Result<()> {
+    let platform_config = &mut ctx.accounts.platform_config;
+    let player = &mut ctx.accounts.player;
+    
+    // Validations
+    require!(
+        handle.len() <= MAX_PLAYER_HANDLE_LEN,
+        PredictionMarketError::NameTooLong
+    );
+    
+    player.version = Player::VERSION;
+    player.id = platform_config.players_count;
+    player.team = ctx.accounts.team.key();
+    player.handle = handle;
+    player.bump = ctx.bumps.player;
+    
+    platform_config.players_count = platform_config.players_count.checked_add(1)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    
+    msg!("Player registered: {}", player.handle);
+    msg!("Player ID: {}", player.id);
+    msg!("Team: {}", ctx.accounts.team.name);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CreatePlayer<'info> {
+    #[account(
+        mut,
+        seeds = [PlatformConfig::SEED_PREFIX.as_bytes()],
+        bump = platform_config.bump,
+        constraint = platform_config.admin == admin.key() @ PredictionMarketError::UnauthorizedAdmin
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    
+    #[account(
+        seeds = [Team::SEED_PREFIX.as_bytes(), team.id.to_le_bytes().as_ref()],
+        bump = team.bump
+    )]
+    pub team: Account<'info, Team>,
+    
+    #[account(
+        init,
+        payer = admin,
+        space = Player::SIZE,
+        seeds = [Player::SEED_PREFIX.as_bytes(), platform_config.players_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub player: Account<'info, Player>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
\ No newline at end of file
@@ -0,0 +1,176 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use anchor_spl::associated_token::AssociatedToken;
+use crate::state::{Market, MarketBook, MarketStatus, Outcome, Player, PlatformConfig, Prop, PropKind, StatKind};
+use crate::errors::PredictionMarketError;
+
+/// Turns a new market without outcomes into a prop on `player`'s `stat`,
+/// generating its over and under outcomes from `line`, in tenths. The
+/// market is then resolved with `resolve_prop_market`.
+pub fn create_prop_market(ctx: Context<CreatePropMarket>, stat: StatKind, line: u64) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    
+    // Validations
+    require!(
+        market.status == MarketStatus::Active,
+        PredictionMarketError::MarketNotActive
+    );
+    
+    require!(
+        market.outcomes.is_empty() && !market.has_prop,
+        PredictionMarketError::InvalidProp
+    );
+    
+    let prop = &mut ctx.accounts.prop;
+    prop.version = Prop::VERSION;
+    prop.market = market.key();
+    prop.player = ctx.accounts.player.key();
+    prop.stat = stat;
+    prop.line = line;
+    prop.stat_value = None;
+    prop.bump = ctx.bumps.prop;
+    prop.kind = PropKind::Line;
+    
+    let [over_name, under_name] = prop.outcome_names();
+    init_outcome(
+        &mut ctx.accounts.over_outcome,
+        Prop::OVER,
+        market.key(),
+        over_name,
+        ctx.accounts.over_escrow_token_account.key(),
+        ctx.bumps.over_outcome,
+    );
+    init_outcome(
+        &mut ctx.accounts.under_outcome,
+        Prop::UNDER,
+        market.key(),
+        under_name,
+        ctx.accounts.under_escrow_token_account.key(),
+        ctx.bumps.under_outcome,
+    );
+    market.outcomes = vec![Prop::OVER, Prop::UNDER];
+    market.has_prop = true;
+    
+    // Markets with a book list every outcome in it
+    if market.has_book {
+        let market_book = ctx.accounts.market_book
+            .as_ref()
+            .ok_or(PredictionMarketError::InvalidMarketBook)?;
+        let mut market_book = market_book.load_mut()?;
+        market_book.add_outcome(Prop::OVER)?;
+        market_book.add_outcome(Prop::UNDER)?;
+    }
+    
+    msg!("Prop market {} on player: {}", market.id, ctx.accounts.player.handle);
+    msg!("Outcomes: {} / {}", ctx.accounts.over_outcome.name, ctx.accounts.under_outcome.name);
+    
+    Ok(())
+}
+
+/// Initializes a prop's generated outcome, shared with `add_top_player`
+pub(crate) fn init_outcome(outcome: &mut Outcome, id: u8, market: Pubkey, name: String, escrow_pubkey: Pubkey, bump: u8) {
+    outcome.version = Outcome::VERSION;
+    outcome.id = id;
+    outcome.market = market;
+    outcome.name = name;
+    outcome.escrow_pubkey = escrow_pubkey;
+    outcome.odds = 10000; // Default 1:1 odds (represented as 1.0000)
+    outcome.bump = bump;
+}
+
+#[derive(Accounts)]
+pub struct CreatePropMarket<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        seeds = [Player::SEED_PREFIX.as_bytes(), player.id.to_le_bytes().as_ref()],
+        bump = player.bump
+    )]
+    pub player: Account<'info, Player>,
+    
+    #[account(
+        init,
+        payer = admin,
+        space = Prop::SIZE,
+        seeds = [Prop::SEED_PREFIX.as_bytes(), market.key().as_ref()],
+        bump
+    )]
+    pub prop: Account<'info, Prop>,
+    
+    #[account(
+        init,
+        payer = admin,
+        space = Outcome::SIZE,
+        seeds = [b"outcome", market.key().as_ref(), &Prop::OVER.to_le_bytes()],
+        bump
+    )]
+    pub over_outcome: Account<'info, Outcome>,
+    
+    /// CHECK: This is the PDA that will have authority over the over escrow
+    #[account(
+        seeds = [b"escrow", market.key().as_ref(), &Prop::OVER.to_le_bytes()],
+        bump,
+    )]
+    pub over_escrow_authority: UncheckedAccount<'info>,
+    
+    #[account(
+        init_if_needed,
+        payer = admin,
+        associated_token::mint = mint,
+        associated_token::authority = over_escrow_authority,
+        associated_token::token_program = token_program,
+    )]
+    pub over_escrow_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        init,
+        payer = admin,
+        space = Outcome::SIZE,
+        seeds = [b"outcome", market.key().as_ref(), &Prop::UNDER.to_le_bytes()],
+        bump
+    )]
+    pub under_outcome: Account<'info, Outcome>,
+    
+    /// CHECK: This is the PDA that will have authority over the under escrow
+    #[account(
+        seeds = [b"escrow", market.key().as_ref(), &Prop::UNDER.to_le_bytes()],
+        bump,
+    )]
+    pub under_escrow_authority: UncheckedAccount<'info>,
+    
+    #[account(
+        init_if_needed,
+        payer = admin,
+        associated_token::mint = mint,
+        associated_token::authority = under_escrow_authority,
+        associated_token::token_program = token_program,
+    )]
+    pub under_escrow_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// Required on markets with a book
+    #[account(
+        mut,
+        seeds = [MarketBook::SEED_PREFIX.as_bytes(), market.key().as_ref()],
+        bump = market_book.load()?.bump
+    )]
+    pub market_book: Option<AccountLoader<'info, MarketBook>>,
+    
+    /// The token mint for the escrow accounts
+    #[account(address = market.collateral_mint @ PredictionMarketError::InvalidMint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        seeds = [PlatformConfig::SEED_PREFIX.as_bytes()],
+        bump = platform_config.bump,
+        constraint = platform_config.admin == admin.key() @ PredictionMarketError::UnauthorizedAdmin
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
\ No newline at end of file
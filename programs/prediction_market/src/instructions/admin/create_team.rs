@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use crate::state::{PlatformConfig, Region, Team, MAX_LOGO_URI_LEN, MAX_TEAM_NAME_LEN, MAX_TEAM_TAG_LEN};
+use crate::errors::PredictionMarketError;
+
+/// Registers a team, which outcomes can then back with `add_outcome`.
+pub fn create_team(
+    ctx: Context<CreateTeam>,
+    name: String,
+    tag: String,
+    region: Region,
+    logo_uri: String,
+) -> Result<()> {
+    let platform_config = &mut ctx.accounts.platform_config;
+    let team = &mut ctx.accounts.team;
+    
+    // Validations
+    require!(
+        name.len() <= MAX_TEAM_NAME_LEN && tag.len() <= MAX_TEAM_TAG_LEN,
+        PredictionMarketError::NameTooLong
+    );
+    
+    require!(
+        logo_uri.len() <= MAX_LOGO_URI_LEN,
+        PredictionMarketError::UriTooLong
+    );
+    
+    team.version = Team::VERSION;
+    team.id = platform_config.teams_count;
+    team.name = name;
+    team.tag = tag;
+    team.region = region;
+    team.logo_uri = logo_uri;
+    team.rating = Team::INITIAL_RATING;
+    team.bump = ctx.bumps.team;
+    
+    platform_config.teams_count = platform_config.teams_count.checked_add(1)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    
+    msg!("Team registered: {} ({})", team.name, team.tag);
+    msg!("Team ID: {}", team.id);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CreateTeam<'info> {
+    #[account(
+        mut,
+        seeds = [PlatformConfig::SEED_PREFIX.as_bytes()],
+        bump = platform_config.bump,
+        constraint = platform_config.admin == admin.key() @ PredictionMarketError::UnauthorizedAdmin
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    
+    #[account(
+        init,
+        payer = admin,
+        space = Team::SIZE,
+        seeds = [Team::SEED_PREFIX.as_bytes(), platform_config.teams_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub team: Account<'info, Team>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
\ No newline at end of file
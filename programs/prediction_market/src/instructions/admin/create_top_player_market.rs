@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use crate::state::{Market, MarketStatus, PlatformConfig, Prop, PropKind, StatKind};
+use crate::errors::PredictionMarketError;
+
+/// Turns a new market without outcomes into a prop on which player posts the
+/// highest `stat`, such as "Top ACS of the match". Each player then gets an
+/// outcome from `add_top_player`, and the market is resolved with
+/// `resolve_top_player_market`.
+pub fn create_top_player_market(ctx: Context<CreateTopPlayerMarket>, stat: StatKind) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    
+    // Validations
+    require!(
+        market.status == MarketStatus::Active,
+        PredictionMarketError::MarketNotActive
+    );
+    
+    require!(
+        market.outcomes.is_empty() && !market.has_prop,
+        PredictionMarketError::InvalidProp
+    );
+    
+    let prop = &mut ctx.accounts.prop;
+    prop.version = Prop::VERSION;
+    prop.market = market.key();
+    prop.player = Pubkey::default();
+    prop.stat = stat;
+    prop.line = 0;
+    prop.stat_value = None;
+    prop.bump = ctx.bumps.prop;
+    prop.kind = PropKind::TopPlayer;
+    market.has_prop = true;
+    
+    msg!("Top player prop market {}: {}", market.id, stat.label());
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CreateTopPlayerMarket<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        init,
+        payer = admin,
+        space = Prop::SIZE,
+        seeds = [Prop::SEED_PREFIX.as_bytes(), market.key().as_ref()],
+        bump
+    )]
+    pub prop: Account<'info, Prop>,
+    
+    #[account(
+        seeds = [PlatformConfig::SEED_PREFIX.as_bytes()],
+        bump = platform_config.bump,
+        constraint = platform_config.admin == admin.key() @ PredictionMarketError::UnauthorizedAdmin
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
\ No newline at end of file
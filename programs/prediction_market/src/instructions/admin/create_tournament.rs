@@ -0,0 +1,111 @@
+use anchor_lang::prelude::*;
+use crate::state::{
+    Market, MarketStatus, PlatformConfig, Region, Tournament, TournamentMarket, TournamentTier, MAX_TOURNAMENT_NAME_LEN,
+};
+use crate::errors::PredictionMarketError;
+
+/// Opens a tournament around `outright_market`, the market on who wins it.
+/// Its outcomes are the teams taking part, which are eliminated one by one
+/// as they are knocked out. Match markets join it with `add_tournament_match`,
+/// or at creation by passing it to `create_market`.
+pub fn create_tournament(
+    ctx: Context<CreateTournament>,
+    name: String,
+    tier: TournamentTier,
+    region: Region,
+    start_date: i64,
+    end_date: i64,
+) -> Result<()> {
+    let platform_config = &mut ctx.accounts.platform_config;
+    let outright_market = &mut ctx.accounts.outright_market;
+    let tournament = &mut ctx.accounts.tournament;
+    
+    // Validations
+    require!(
+        outright_market.status == MarketStatus::Active,
+        PredictionMarketError::MarketNotActive
+    );
+    
+    require!(
+        outright_market.tournament.is_none(),
+        PredictionMarketError::MarketAlreadyInTournament
+    );
+    
+    require!(
+        name.len() <= MAX_TOURNAMENT_NAME_LEN,
+        PredictionMarketError::NameTooLong
+    );
+    
+    require!(
+        end_date >= start_date,
+        PredictionMarketError::InvalidTimeRange
+    );
+    
+    tournament.version = Tournament::VERSION;
+    tournament.id = platform_config.tournaments_count;
+    tournament.outright_market = outright_market.key();
+    tournament.matches = Vec::new();
+    tournament.grand_final = None;
+    tournament.eliminated_count = 0;
+    tournament.bump = ctx.bumps.tournament;
+    tournament.name = name;
+    tournament.tier = tier;
+    tournament.region = region;
+    tournament.start_date = start_date;
+    tournament.end_date = end_date;
+    
+    outright_market.tournament = Some(tournament.id);
+    
+    let tournament_market = &mut ctx.accounts.tournament_market;
+    tournament_market.version = TournamentMarket::VERSION;
+    tournament_market.tournament = tournament.id;
+    tournament_market.market = outright_market.key();
+    tournament_market.bump = ctx.bumps.tournament_market;
+    
+    platform_config.tournaments_count = platform_config.tournaments_count.checked_add(1)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    
+    msg!("Tournament {} created: {}", tournament.id, tournament.name);
+    msg!("Outright market: {}", outright_market.name);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CreateTournament<'info> {
+    #[account(
+        mut,
+        seeds = [PlatformConfig::SEED_PREFIX.as_bytes()],
+        bump = platform_config.bump,
+        constraint = platform_config.admin == admin.key() @ PredictionMarketError::UnauthorizedAdmin
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    
+    #[account(
+        init,
+        payer = admin,
+        space = Tournament::SIZE,
+        seeds = [Tournament::SEED_PREFIX.as_bytes(), platform_config.tournaments_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub tournament: Account<'info, Tournament>,
+    
+    #[account(mut)]
+    pub outright_market: Account<'info, Market>,
+    
+    /// Indexes the outright under the tournament. Loaded if the market is
+    /// already linked, so that it is rejected as in a tournament
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = TournamentMarket::SIZE,
+        seeds = [TournamentMarket::SEED_PREFIX.as_bytes(), outright_market.key().as_ref()],
+        bump
+    )]
+    pub tournament_market: Account<'info, TournamentMarket>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
\ No newline at end of file
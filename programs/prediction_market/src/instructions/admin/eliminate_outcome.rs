@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+use crate::state::{Market, MarketBook, MarketStatus, Outcome, Tournament};
+use crate::errors::PredictionMarketError;
+
+/// Knocks an outcome of a tournament's outright market out. Its odds drop
+/// to zero at once and it takes no more bets; its stake stays in the pool
+/// for whoever wins.
+pub fn eliminate_outcome(ctx: Context<EliminateOutcome>) -> Result<()> {
+    let market = &ctx.accounts.market;
+    let outcome = &mut ctx.accounts.outcome;
+    let tournament = &mut ctx.accounts.tournament;
+    
+    // Validations
+    require!(
+        market.status == MarketStatus::Active,
+        PredictionMarketError::MarketNotActive
+    );
+    
+    require!(
+        !outcome.eliminated,
+        PredictionMarketError::OutcomeEliminated
+    );
+    
+    outcome.eliminated = true;
+    outcome.odds = 0;
+    tournament.eliminated_count = tournament.eliminated_count.checked_add(1)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    
+    // Markets with a book stop pricing the outcome too
+    if market.has_book {
+        let market_book = ctx.accounts.market_book
+            .as_ref()
+            .ok_or(PredictionMarketError::InvalidMarketBook)?;
+        market_book.load_mut()?.eliminate(outcome.id)?;
+    }
+    
+    msg!("Outcome eliminated: {}", outcome.name);
+    msg!("Outcome ID: {}", outcome.id);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct EliminateOutcome<'info> {
+    #[account(
+        mut,
+        seeds = [Tournament::SEED_PREFIX.as_bytes(), tournament.id.to_le_bytes().as_ref()],
+        bump = tournament.bump
+    )]
+    pub tournament: Account<'info, Tournament>,
+    
+    #[account(
+        address = tournament.outright_market @ PredictionMarketError::InvalidTournament
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        mut,
+        seeds = [b"outcome", market.key().as_ref(), outcome.id.to_le_bytes().as_ref()],
+        bump = outcome.bump
+    )]
+    pub outcome: Account<'info, Outcome>,
+    
+    /// Required on markets with a book
+    #[account(
+        mut,
+        seeds = [MarketBook::SEED_PREFIX.as_bytes(), market.key().as_ref()],
+        bump = market_book.load()?.bump
+    )]
+    pub market_book: Option<AccountLoader<'info, MarketBook>>,
+    
+    #[account(
+        constraint = market.oracle == oracle.key() @ PredictionMarketError::UnauthorizedOracle
+    )]
+    pub oracle: Signer<'info>,
+}
\ No newline at end of file
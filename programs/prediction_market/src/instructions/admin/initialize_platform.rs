@@ -1,23 +1,27 @@
 use anchor_lang::prelude::*;
-use crate::state::PlatformConfig;
+use crate::state::{BetLimits, PlatformConfig};
 
 pub fn initialize_platform(ctx: Context<InitializePlatform>) -> Result<()> {
     let platform_config = &mut ctx.accounts.platform_config;
     let admin = &ctx.accounts.admin;
-
+    
     // Initialize platform configuration
+    platform_config.version = PlatformConfig::VERSION;
     platform_config.admin = admin.key();
     platform_config.oracle_authority = admin.key(); // Initially set oracle to admin, can be changed later
-    platform_config.treasury = ctx.accounts.treasury.key();
-    platform_config.default_fee_percentage = 2; // Default 2% fee
+    platform_config.default_fee_bps = 200; // Default 2% fee
     platform_config.markets_count = 0;
     platform_config.total_volume = 0;
     platform_config.paused = false;
+    platform_config.default_bet_limits = BetLimits::default(); // No limits until the admin sets them
+    platform_config.referral_share_bps = 0; // No referral rewards until the admin sets a share
+    platform_config.lp_fee_share_bps = 0; // No LP fee share until the admin sets one
+    platform_config.max_creator_fee_bps = 0; // No creator fees until the admin allows them
+    platform_config.fee_tiers = Default::default(); // No fee discounts until the admin sets tiers
     platform_config.bump = ctx.bumps.platform_config;
-
+    
     msg!("Platform initialized with admin: {:?}", admin.key());
-    msg!("Platform treasury set to: {:?}", ctx.accounts.treasury.key());
-
+    
     Ok(())
 }
 
@@ -26,9 +30,6 @@ pub struct InitializePlatform<'info> {
     #[account(mut)]
     pub admin: Signer<'info>,
     
-    /// CHECK: This is the treasury account that will receive fees
-    pub treasury: UncheckedAccount<'info>,
-    
     #[account(
         init,
         payer = admin,
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use crate::state::Credential;
+
+/// Issues or renews `user`'s credential from the signing attestation
+/// authority. An `expires_at` of 0 never expires.
+pub fn issue_credential(ctx: Context<IssueCredential>, user: Pubkey, expires_at: i64) -> Result<()> {
+    let credential = &mut ctx.accounts.credential;
+    
+    credential.version = Credential::VERSION;
+    credential.authority = ctx.accounts.authority.key();
+    credential.user = user;
+    credential.expires_at = expires_at;
+    credential.bump = ctx.bumps.credential;
+    
+    msg!("Credential for {} issued by {}", user, credential.authority);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct IssueCredential<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = Credential::SIZE,
+        seeds = [Credential::SEED_PREFIX.as_bytes(), authority.key().as_ref(), user.as_ref()],
+        bump
+    )]
+    pub credential: Account<'info, Credential>,
+    
+    pub system_program: Program<'info, System>,
+}
\ No newline at end of file
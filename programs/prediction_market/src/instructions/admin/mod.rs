@@ -4,10 +4,80 @@ pub mod add_outcome;
 pub mod resolve_market;
 pub mod withdraw_fees;
 pub mod cancel_market;
+pub mod add_collateral_mint;
+pub mod set_collateral_enabled;
+pub mod set_default_bet_limits;
+pub mod set_bet_limits;
+pub mod set_access_gate;
+pub mod add_to_allowlist;
+pub mod remove_from_allowlist;
+pub mod issue_credential;
+pub mod revoke_credential;
+pub mod set_referral_share;
+pub mod set_max_creator_fee;
+pub mod set_creator_fee;
+pub mod set_fee_tiers;
+pub mod set_fee_model;
+pub mod create_market_book;
+pub mod create_tournament;
+pub mod add_tournament_match;
+pub mod eliminate_outcome;
+pub mod create_house;
+pub mod set_parlay_limits;
+pub mod withdraw_house_liquidity;
+pub mod set_market_condition;
+pub mod create_team;
+pub mod create_player;
+pub mod create_prop_market;
+pub mod resolve_prop_market;
+pub mod create_top_player_market;
+pub mod add_top_player;
+pub mod resolve_top_player_market;
+pub mod seed_odds;
+pub mod set_lp_fee_share;
+pub mod set_fixed_odds;
+pub mod set_outcome_odds;
+pub mod sweep_escrows;
+pub mod set_rates_teams;
 
 pub use initialize_platform::*;
 pub use create_market::*;
 pub use add_outcome::*;
 pub use resolve_market::*;
 pub use withdraw_fees::*;
-pub use cancel_market::*;
\ No newline at end of file
+pub use cancel_market::*;
+pub use add_collateral_mint::*;
+pub use set_collateral_enabled::*;
+pub use set_default_bet_limits::*;
+pub use set_bet_limits::*;
+pub use set_access_gate::*;
+pub use add_to_allowlist::*;
+pub use remove_from_allowlist::*;
+pub use issue_credential::*;
+pub use revoke_credential::*;
+pub use set_referral_share::*;
+pub use set_max_creator_fee::*;
+pub use set_creator_fee::*;
+pub use set_fee_tiers::*;
+pub use set_fee_model::*;
+pub use create_market_book::*;
+pub use create_tournament::*;
+pub use add_tournament_match::*;
+pub use eliminate_outcome::*;
+pub use create_house::*;
+pub use set_parlay_limits::*;
+pub use withdraw_house_liquidity::*;
+pub use set_market_condition::*;
+pub use create_team::*;
+pub use create_player::*;
+pub use create_prop_market::*;
+pub use resolve_prop_market::*;
+pub use create_top_player_market::*;
+pub use add_top_player::*;
+pub use resolve_top_player_market::*;
+pub use seed_odds::*;
+pub use set_lp_fee_share::*;
+pub use set_fixed_odds::*;
+pub use set_outcome_odds::*;
+pub use sweep_escrows::*;
+pub use set_rates_teams::*;
\ No newline at end of file
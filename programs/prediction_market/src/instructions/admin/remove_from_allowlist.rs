@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+use crate::state::{AllowlistEntry, PlatformConfig};
+use crate::errors::PredictionMarketError;
+
+/// Removes a user from the allowlist and returns the entry's rent to the
+/// admin. Positions the user already holds are unaffected.
+pub fn remove_from_allowlist(ctx: Context<RemoveFromAllowlist>) -> Result<()> {
+    msg!("User {} removed from the allowlist", ctx.accounts.allowlist_entry.user);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RemoveFromAllowlist<'info> {
+    #[account(
+        seeds = [PlatformConfig::SEED_PREFIX.as_bytes()],
+        bump = platform_config.bump,
+        constraint = platform_config.admin == admin.key() @ PredictionMarketError::UnauthorizedAdmin
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        mut,
+        close = admin,
+        seeds = [AllowlistEntry::SEED_PREFIX.as_bytes(), allowlist_entry.user.as_ref()],
+        bump = allowlist_entry.bump
+    )]
+    pub allowlist_entry: Account<'info, AllowlistEntry>,
+}
\ No newline at end of file
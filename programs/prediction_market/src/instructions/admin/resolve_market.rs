@@ -1,10 +1,60 @@
 use anchor_lang::prelude::*;
-use crate::state::{Market, MarketStatus, PlatformConfig};
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::{
+    CollateralConfig, House, LiquidityPool, Market, MarketBook, MarketCondition, MarketStatus, Outcome, PlatformConfig, Prop, Team, Tournament,
+};
 use crate::errors::PredictionMarketError;
 
-pub fn resolve_market(ctx: Context<ResolveMarket>, winning_outcome_id: u8) -> Result<()> {
+/// Resolves the market and settles its escrows.
+///
+/// Every losing outcome's escrow is swept into the winning escrow. The
+/// platform fee, less winners' fee tier rebates and the referrers' and
+/// liquidity providers' shares, is sent to the treasury and the creator fee
+/// to the creator, so the winning escrow holds exactly what winners,
+/// referrers and LPs can claim. `remaining_accounts` must hold, for each
+/// losing outcome in `market.outcomes` order, its escrow authority followed
+/// by its escrow token account.
+///
+/// A market with a book can pass only the first of its losing escrows, as
+/// many as fit the transaction, and have `sweep_escrows` sweep the rest.
+/// Fees are paid, and claims open, once the last of them is swept.
+///
+/// A tournament's outright market can be resolved as soon as the grand
+/// final has been, passing its `grand_final_winner` outcome, and only to
+/// the outcome backing the same team.
+///
+/// A conditional market is refunded instead, as if cancelled, once its
+/// parent market has gone against the condition.
+///
+/// A head-to-head market set to rate its teams with `set_rates_teams` moves
+/// their ratings by the result, and must pass its `losing_outcome` and both
+/// teams. Other markets leave ratings alone and need none of them.
+///
+/// Prop markets are resolved from the player's stat with
+/// `resolve_prop_market` or `resolve_top_player_market` instead.
+pub fn resolve_market<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ResolveMarket<'info>>,
+    winning_outcome_id: u8,
+) -> Result<()> {
+    // Validations
+    require!(
+        !ctx.accounts.market.has_prop,
+        PredictionMarketError::InvalidProp
+    );
+    
+    settle(ctx, winning_outcome_id)
+}
+
+/// Settles the market on `winning_outcome_id`, shared by `resolve_market`
+/// and the prop resolutions.
+pub(crate) fn settle<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ResolveMarket<'info>>,
+    winning_outcome_id: u8,
+) -> Result<()> {
     let market = &mut ctx.accounts.market;
     let oracle = &ctx.accounts.oracle;
+    let winning_outcome = &ctx.accounts.winning_outcome;
     let clock = Clock::get()?;
     
     // Validations
@@ -18,35 +68,334 @@ pub fn resolve_market(ctx: Context<ResolveMarket>, winning_outcome_id: u8) -> Re
         PredictionMarketError::MarketAlreadyResolved
     );
     
-    require!(
-        clock.unix_timestamp >= market.end_time,
-        PredictionMarketError::MarketNotEnded
-    );
+    // Conditional markets only stand if their parent resolved to the outcome
+    // they depend on
+    if market.has_condition {
+        let condition = ctx.accounts.condition
+            .as_ref()
+            .ok_or(PredictionMarketError::InvalidCondition)?;
+        let parent_market = ctx.accounts.parent_market
+            .as_ref()
+            .filter(|parent_market| parent_market.key() == condition.parent_market)
+            .ok_or(PredictionMarketError::InvalidCondition)?;
+        let is_met = condition.is_met(parent_market)
+            .ok_or(PredictionMarketError::ConditionNotSettled)?;
+        if !is_met {
+            refund(market, ctx.accounts.market_book.as_ref(), ctx.accounts.house.as_mut())?;
+            msg!("Market refunded, condition not met: {}", market.name);
+            return Ok(());
+        }
+    }
     
     // Check that the winning outcome exists in this market
     let outcome_exists = market.outcomes.contains(&winning_outcome_id);
     require!(
-        outcome_exists,
+        outcome_exists && winning_outcome.id == winning_outcome_id,
         PredictionMarketError::OutcomeNotFound
     );
     
+    // Tournament markets need their tournament to tell whether they are
+    // its outright, which is decided by the grand final
+    let mut decided_by_grand_final = false;
+    if let Some(tournament_id) = market.tournament {
+        let tournament = ctx.accounts.tournament
+            .as_ref()
+            .filter(|tournament| tournament.id == tournament_id)
+            .ok_or(PredictionMarketError::InvalidTournament)?;
+        if tournament.outright_market == market.key() {
+            let grand_final = ctx.accounts.grand_final
+                .as_ref()
+                .filter(|grand_final| tournament.grand_final == Some(grand_final.key()))
+                .ok_or(PredictionMarketError::GrandFinalNotResolved)?;
+            require!(
+                grand_final.status == MarketStatus::Resolved,
+                PredictionMarketError::GrandFinalNotResolved
+            );
+            
+            // The outright goes to whichever team won the grand final
+            let grand_final_winner = ctx.accounts.grand_final_winner
+                .as_ref()
+                .filter(|outcome| outcome.market == grand_final.key() && grand_final.winner == Some(outcome.id))
+                .ok_or(PredictionMarketError::InvalidTournament)?;
+            
+            require!(
+                !winning_outcome.eliminated,
+                PredictionMarketError::OutcomeEliminated
+            );
+            
+            require!(
+                winning_outcome.team.is_some() && winning_outcome.team == grand_final_winner.team,
+                PredictionMarketError::NotGrandFinalWinner
+            );
+            decided_by_grand_final = true;
+        }
+    }
+    
+    require!(
+        clock.unix_timestamp >= market.end_time || decided_by_grand_final,
+        PredictionMarketError::MarketNotEnded
+    );
+    
+    // Nobody could claim the pool, so the market should be cancelled instead.
+    // Fixed-odds markets have no pool, and the house simply owes nothing.
+    require!(
+        winning_outcome.total_staked > 0 || market.has_fixed_odds,
+        PredictionMarketError::NoWinningStake
+    );
+    
+    let losing_outcomes: Vec<u8> = market
+        .outcomes
+        .iter()
+        .copied()
+        .filter(|id| *id != winning_outcome_id)
+        .collect();
+    
+    // Without a book every losing escrow is swept now. A book market can
+    // sweep any leading part of them, and `sweep_escrows` the rest
+    let swept = ctx.remaining_accounts.len() / 2;
+    require!(
+        ctx.remaining_accounts.len().is_multiple_of(2)
+            && swept <= losing_outcomes.len()
+            && (market.has_book || swept == losing_outcomes.len()),
+        PredictionMarketError::InvalidSettlementAccounts
+    );
+    
     // Update market status
     market.status = MarketStatus::Resolved;
     market.resolved = true;
     market.winner = Some(winning_outcome_id);
+    market.unswept_escrows = (losing_outcomes.len() - swept) as u8;
+    
+    // The house now only reserves what it owes the winners
+    if market.has_fixed_odds {
+        let house = ctx.accounts.house
+            .as_mut()
+            .ok_or(PredictionMarketError::InvalidHouse)?;
+        house.reserve_for(market, winning_outcome.liability)?;
+    }
+    
+    // Head-to-head results move their teams' ratings, on the markets that opt in
+    if market.rates_teams {
+        let market_key = market.key();
+        let losing_outcome = ctx.accounts.losing_outcome
+            .as_ref()
+            .filter(|outcome| market.outcomes.len() == 2 && outcome.market == market_key && outcome.id == losing_outcomes[0])
+            .ok_or(PredictionMarketError::InvalidTeam)?;
+        let (Some(winning_team_id), Some(losing_team_id)) = (winning_outcome.team, losing_outcome.team) else {
+            return err!(PredictionMarketError::InvalidTeam);
+        };
+        let (Some(winning_team), Some(losing_team)) = (&mut ctx.accounts.winning_team, &mut ctx.accounts.losing_team) else {
+            return err!(PredictionMarketError::InvalidTeam);
+        };
+        require!(
+            winning_team.id == winning_team_id && losing_team.id == losing_team_id,
+            PredictionMarketError::InvalidTeam
+        );
+    
+        let change = Team::rating_change(winning_team.rating, losing_team.rating);
+        winning_team.rating = winning_team.rating.saturating_add(change);
+        losing_team.rating = losing_team.rating.saturating_sub(change);
+        msg!("Ratings: {} {}, {} {}", winning_team.name, winning_team.rating, losing_team.name, losing_team.rating);
+    }
     
     msg!("Market resolved: {}", market.name);
     msg!("Winning outcome ID: {}", winning_outcome_id);
     
+    sweep(ctx.accounts, ctx.remaining_accounts, ctx.program_id, &losing_outcomes[..swept])?;
+    if ctx.accounts.market.unswept_escrows == 0 {
+        pay_out(ctx.accounts, ctx.bumps.winning_escrow_authority)?;
+    } else {
+        msg!("Escrows left to sweep: {}", ctx.accounts.market.unswept_escrows);
+    }
+    
+    Ok(())
+}
+
+/// Sweeps the escrows of `outcome_ids`, passed in `escrows` as each one's
+/// escrow authority followed by its escrow token account, into the winning
+/// escrow.
+pub(crate) fn sweep<'info>(
+    accounts: &ResolveMarket<'info>,
+    escrows: &'info [AccountInfo<'info>],
+    program_id: &Pubkey,
+    outcome_ids: &[u8],
+) -> Result<()> {
+    let market_key = accounts.market.key();
+    let mint_key = accounts.mint.key();
+    let token_program_key = accounts.token_program.key();
+    
+    for (outcome_id, escrow) in outcome_ids.iter().zip(escrows.chunks(2)) {
+        let escrow_authority = &escrow[0];
+        let escrow_token_account = &escrow[1];
+        let outcome_id_bytes = outcome_id.to_le_bytes();
+    
+        let (expected_authority, bump) = Pubkey::find_program_address(
+            &[b"escrow", market_key.as_ref(), outcome_id_bytes.as_ref()],
+            program_id,
+        );
+        require_keys_eq!(
+            escrow_authority.key(),
+            expected_authority,
+            PredictionMarketError::InvalidSettlementAccounts
+        );
+        require_keys_eq!(
+            escrow_token_account.key(),
+            get_associated_token_address_with_program_id(&expected_authority, &mint_key, &token_program_key),
+            PredictionMarketError::InvalidSettlementAccounts
+        );
+    
+        let balance = InterfaceAccount::<TokenAccount>::try_from(escrow_token_account)?.amount;
+        if balance == 0 {
+            continue;
+        }
+    
+        let bump = [bump];
+        let escrow_seeds = &[
+            b"escrow",
+            market_key.as_ref(),
+            outcome_id_bytes.as_ref(),
+            &bump
+        ];
+        let signer_seeds = &[&escrow_seeds[..]];
+    
+        let cpi_accounts = TransferChecked {
+            from: escrow_token_account.clone(),
+            mint: accounts.mint.to_account_info(),
+            to: accounts.winning_escrow_token_account.to_account_info(),
+            authority: escrow_authority.clone(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds
+        );
+        token_interface::transfer_checked(cpi_ctx, balance, accounts.mint.decimals)?;
+    }
+    
+    Ok(())
+}
+
+/// Pays the fees out of the winning escrow once every losing escrow has been
+/// swept into it, leaving exactly what winners, referrers and LPs can claim.
+pub(crate) fn pay_out(accounts: &mut ResolveMarket, winning_escrow_bump: u8) -> Result<()> {
+    let market = &mut accounts.market;
+    let winning_outcome = &accounts.winning_outcome;
+    let market_key = market.key();
+    
+    // Transfer-fee mints withhold part of each sweep, so settle on what
+    // actually reached the winning escrow. Fixed-odds stakes are held by
+    // the house instead.
+    accounts.winning_escrow_token_account.reload()?;
+    let settled_pool = accounts.winning_escrow_token_account.amount;
+    if settled_pool < market.total_pool && !market.has_fixed_odds {
+        market.total_pool = settled_pool;
+    }
+    
+    let bump = [winning_escrow_bump];
+    let outcome_id_bytes = winning_outcome.id.to_le_bytes();
+    let escrow_seeds = &[
+        b"escrow",
+        market_key.as_ref(),
+        outcome_id_bytes.as_ref(),
+        &bump
+    ];
+    let signer_seeds = &[&escrow_seeds[..]];
+    
+    // Winners' fee tier rebates and the referrers' part of what is left of
+    // the fee stay in the winning escrow until claimed; the rest goes to
+    // this collateral's treasury
+    market.fee_rebates = market.fee_rebate(winning_outcome.discounted_stake, winning_outcome.total_staked)?;
+    let net_fee = market.fee_amount(winning_outcome.total_staked)?
+        .checked_sub(market.fee_rebates)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    market.referral_fees = market.referral_fee_amount(net_fee)?;
+    let mut treasury_fee = net_fee
+        .checked_sub(market.referral_fees)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    
+    // LPs earn their share of the fee on the part of the pool they staked.
+    // Markets charging at bet time have no fee left to share.
+    if market.has_liquidity {
+        let liquidity_pool = accounts.liquidity_pool
+            .as_mut()
+            .ok_or(PredictionMarketError::InvalidLiquidityPool)?;
+        liquidity_pool.fees = liquidity_pool.fee_amount(net_fee, market.total_pool)?.min(treasury_fee);
+        treasury_fee -= liquidity_pool.fees;
+    }
+    if treasury_fee > 0 {
+        let cpi_accounts = TransferChecked {
+            from: accounts.winning_escrow_token_account.to_account_info(),
+            mint: accounts.mint.to_account_info(),
+            to: accounts.treasury_token_account.to_account_info(),
+            authority: accounts.winning_escrow_authority.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds
+        );
+        token_interface::transfer_checked(cpi_ctx, treasury_fee, accounts.mint.decimals)?;
+    }
+    
+    // The creator's fee goes straight to the creator
+    let creator_fee = market.creator_fee_amount()?;
+    if creator_fee > 0 {
+        let creator_token_account = accounts.creator_token_account
+            .as_ref()
+            .ok_or(PredictionMarketError::InvalidSettlementAccounts)?;
+        let cpi_accounts = TransferChecked {
+            from: accounts.winning_escrow_token_account.to_account_info(),
+            mint: accounts.mint.to_account_info(),
+            to: creator_token_account.to_account_info(),
+            authority: accounts.winning_escrow_authority.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds
+        );
+        token_interface::transfer_checked(cpi_ctx, creator_fee, accounts.mint.decimals)?;
+    }
+    
+    // Markets with a book price the result in it, on the pool it pays out
+    if market.has_book {
+        let market_book = accounts.market_book
+            .as_ref()
+            .ok_or(PredictionMarketError::InvalidMarketBook)?;
+        market_book.load_mut()?.settle(market, winning_outcome.id)?;
+    }
+    
+    msg!("Fee collected: {}", treasury_fee);
+    msg!("Fee rebates: {}", market.fee_rebates);
+    msg!("Referral fees: {}", market.referral_fees);
+    msg!("Creator fee: {}", creator_fee);
+    
+    Ok(())
+}
+
+/// Cancels the market instead of settling it, so every bettor can claim a
+/// refund. On fixed-odds markets the house reserves every stake to refund.
+pub(crate) fn refund(
+    market: &mut Market,
+    market_book: Option<&AccountLoader<MarketBook>>,
+    house: Option<&mut Account<House>>,
+) -> Result<()> {
+    market.status = MarketStatus::Cancelled;
+    if market.has_book {
+        let market_book = market_book.ok_or(PredictionMarketError::InvalidMarketBook)?;
+        market_book.load_mut()?.cancel();
+    }
+    if market.has_fixed_odds {
+        let house = house.ok_or(PredictionMarketError::InvalidHouse)?;
+        house.reserve_for(market, market.total_pool)?;
+    }
+    
     Ok(())
 }
 
 #[derive(Accounts)]
 pub struct ResolveMarket<'info> {
-    #[account(
-        mut,
-        constraint = market.status != MarketStatus::Resolved @ PredictionMarketError::MarketAlreadyResolved
-    )]
+    #[account(mut)]
     pub market: Account<'info, Market>,
     
     #[account(
@@ -59,4 +408,124 @@ pub struct ResolveMarket<'info> {
         bump = platform_config.bump
     )]
     pub platform_config: Account<'info, PlatformConfig>,
-}
\ No newline at end of file
+    
+    #[account(
+        seeds = [b"outcome", market.key().as_ref(), winning_outcome.id.to_le_bytes().as_ref()],
+        bump = winning_outcome.bump
+    )]
+    pub winning_outcome: Account<'info, Outcome>,
+    
+    /// CHECK: This is the PDA that has authority over the winning escrow
+    #[account(
+        seeds = [b"escrow", market.key().as_ref(), winning_outcome.id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub winning_escrow_authority: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        address = winning_outcome.escrow_pubkey,
+        constraint = winning_escrow_token_account.mint == mint.key() @ PredictionMarketError::InvalidMint
+    )]
+    pub winning_escrow_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        seeds = [CollateralConfig::SEED_PREFIX.as_bytes(), market.collateral_mint.as_ref()],
+        bump = collateral_config.bump
+    )]
+    pub collateral_config: Account<'info, CollateralConfig>,
+    
+    #[account(
+        mut,
+        address = collateral_config.treasury @ PredictionMarketError::UnauthorizedAdmin
+    )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// Required when the market charges a creator fee
+    #[account(
+        mut,
+        constraint = creator_token_account.owner == market.creator @ PredictionMarketError::UnauthorizedAdmin,
+        constraint = creator_token_account.mint == mint.key() @ PredictionMarketError::InvalidMint
+    )]
+    pub creator_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Required on markets with a book
+    #[account(
+        mut,
+        seeds = [MarketBook::SEED_PREFIX.as_bytes(), market.key().as_ref()],
+        bump = market_book.load()?.bump
+    )]
+    pub market_book: Option<AccountLoader<'info, MarketBook>>,
+    
+    /// Required on tournament markets
+    #[account(
+        seeds = [Tournament::SEED_PREFIX.as_bytes(), tournament.id.to_le_bytes().as_ref()],
+        bump = tournament.bump
+    )]
+    pub tournament: Option<Account<'info, Tournament>>,
+    
+    /// The tournament's grand final, required on its outright market
+    pub grand_final: Option<Account<'info, Market>>,
+    
+    /// The grand final's winning outcome, required on an outright market
+    pub grand_final_winner: Option<Account<'info, Outcome>>,
+    
+    /// Required on conditional markets
+    #[account(
+        seeds = [MarketCondition::SEED_PREFIX.as_bytes(), market.key().as_ref()],
+        bump = condition.bump
+    )]
+    pub condition: Option<Account<'info, MarketCondition>>,
+    
+    /// The condition's parent market, required on conditional markets
+    pub parent_market: Option<Account<'info, Market>>,
+    
+    /// Required on prop markets
+    #[account(
+        mut,
+        seeds = [Prop::SEED_PREFIX.as_bytes(), market.key().as_ref()],
+        bump = prop.bump
+    )]
+    pub prop: Option<Account<'info, Prop>>,
+    
+    /// The house in the market's collateral, required on fixed-odds markets
+    #[account(
+        mut,
+        seeds = [House::SEED_PREFIX.as_bytes(), market.collateral_mint.as_ref()],
+        bump = house.bump
+    )]
+    pub house: Option<Account<'info, House>>,
+    
+    /// Required on markets with liquidity
+    #[account(
+        mut,
+        seeds = [LiquidityPool::SEED_PREFIX.as_bytes(), market.key().as_ref()],
+        bump = liquidity_pool.bump
+    )]
+    pub liquidity_pool: Option<Account<'info, LiquidityPool>>,
+    
+    /// The other outcome, required on markets that rate their teams
+    pub losing_outcome: Option<Account<'info, Outcome>>,
+    
+    /// The winning and losing outcomes' teams, required on markets that
+    /// rate their teams
+    #[account(
+        mut,
+        seeds = [Team::SEED_PREFIX.as_bytes(), winning_team.id.to_le_bytes().as_ref()],
+        bump = winning_team.bump
+    )]
+    pub winning_team: Option<Account<'info, Team>>,
+    
+    #[account(
+        mut,
+        seeds = [Team::SEED_PREFIX.as_bytes(), losing_team.id.to_le_bytes().as_ref()],
+        bump = losing_team.bump
+    )]
+    pub losing_team: Option<Account<'info, Team>>,
+    
+    /// The mint of the token staked on this market
+    #[account(address = market.collateral_mint @ PredictionMarketError::InvalidMint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use super::resolve_market::{refund, settle, ResolveMarket};
+use crate::state::{MarketStatus, PropKind};
+use crate::errors::PredictionMarketError;
+
+/// Resolves a line prop market from the player's final `stat_value`, settling it
+/// on over or under exactly as `resolve_market` would. A value landing
+/// exactly on the line is a push, and the market is refunded instead.
+pub fn resolve_prop_market<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ResolveMarket<'info>>,
+    stat_value: u64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let market = &ctx.accounts.market;
+    
+    // Validations
+    require!(
+        market.status != MarketStatus::Resolved && market.status != MarketStatus::Cancelled,
+        PredictionMarketError::MarketAlreadyResolved
+    );
+    
+    require!(
+        clock.unix_timestamp >= market.end_time,
+        PredictionMarketError::MarketNotEnded
+    );
+    
+    require!(
+        market.has_prop,
+        PredictionMarketError::InvalidProp
+    );
+    
+    let prop = ctx.accounts.prop
+        .as_mut()
+        .filter(|prop| prop.kind == PropKind::Line)
+        .ok_or(PredictionMarketError::InvalidProp)?;
+    prop.stat_value = Some(stat_value);
+    msg!("Prop stat reported: {}", stat_value);
+    
+    match prop.winning_outcome(stat_value) {
+        Some(winning_outcome_id) => settle(ctx, winning_outcome_id),
+        None => {
+            let market = &mut ctx.accounts.market;
+            refund(market, ctx.accounts.market_book.as_ref(), ctx.accounts.house.as_mut())?;
+    
+            msg!("Market refunded, prop pushed: {}", market.name);
+            Ok(())
+        }
+    }
+}
\ No newline at end of file
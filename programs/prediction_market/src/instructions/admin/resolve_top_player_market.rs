@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use super::resolve_market::{settle, ResolveMarket};
+use crate::state::PropKind;
+use crate::errors::PredictionMarketError;
+
+/// Resolves a top player prop to the player the oracle reports as posting
+/// the highest statistic, `stat_value`, passing that player's outcome as
+/// the winner. It then settles exactly as `resolve_market` would.
+pub fn resolve_top_player_market<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ResolveMarket<'info>>,
+    player_id: u64,
+    stat_value: u64,
+) -> Result<()> {
+    let winning_outcome = &ctx.accounts.winning_outcome;
+    
+    // Validations
+    require!(
+        ctx.accounts.market.has_prop,
+        PredictionMarketError::InvalidProp
+    );
+    
+    require!(
+        winning_outcome.player == Some(player_id),
+        PredictionMarketError::OutcomeNotFound
+    );
+    
+    let winning_outcome_id = winning_outcome.id;
+    let prop = ctx.accounts.prop
+        .as_mut()
+        .filter(|prop| prop.kind == PropKind::TopPlayer)
+        .ok_or(PredictionMarketError::InvalidProp)?;
+    prop.stat_value = Some(stat_value);
+    msg!("Top player: {}, with {}", player_id, stat_value);
+    
+    settle(ctx, winning_outcome_id)
+}
\ No newline at end of file
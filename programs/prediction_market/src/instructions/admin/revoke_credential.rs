@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+use crate::state::Credential;
+
+/// Revokes a credential the signing authority issued and returns its rent
+pub fn revoke_credential(ctx: Context<RevokeCredential>) -> Result<()> {
+    msg!("Credential for {} revoked", ctx.accounts.credential.user);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RevokeCredential<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        close = authority,
+        seeds = [Credential::SEED_PREFIX.as_bytes(), authority.key().as_ref(), credential.user.as_ref()],
+        bump = credential.bump
+    )]
+    pub credential: Account<'info, Credential>,
+}
\ No newline at end of file
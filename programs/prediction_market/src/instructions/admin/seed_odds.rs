@@ -0,0 +1,117 @@
+use anchor_lang::prelude::*;
+use crate::state::{Market, MarketBook, MarketStatus, Outcome, PlatformConfig, Team};
+use crate::errors::PredictionMarketError;
+
+/// Opens a head-to-head market between two registered teams at the fair
+/// odds of their ratings rather than even, before the first bet. Markets
+/// with a book are priced in it too.
+pub fn seed_odds(ctx: Context<SeedOdds>) -> Result<()> {
+    let market = &ctx.accounts.market;
+    let home_outcome = &mut ctx.accounts.home_outcome;
+    let away_outcome = &mut ctx.accounts.away_outcome;
+    let home_team = &ctx.accounts.home_team;
+    let away_team = &ctx.accounts.away_team;
+    
+    // Validations
+    require!(
+        market.status == MarketStatus::Active,
+        PredictionMarketError::MarketNotActive
+    );
+    
+    require!(
+        market.total_pool == 0,
+        PredictionMarketError::MarketHasBets
+    );
+    
+    // Fixed-odds markets are priced by the oracle's posted odds
+    require!(
+        !market.has_fixed_odds,
+        PredictionMarketError::InvalidMarketMode
+    );
+    
+    require!(
+        market.outcomes.len() == 2 && home_outcome.id != away_outcome.id,
+        PredictionMarketError::OutcomeNotFound
+    );
+    
+    require!(
+        home_outcome.team == Some(home_team.id) && away_outcome.team == Some(away_team.id),
+        PredictionMarketError::InvalidTeam
+    );
+    
+    seed(market, ctx.accounts.market_book.as_ref(), (home_outcome, home_team), (away_outcome, away_team))
+}
+
+/// Prices `home` and `away` at the fair odds of their teams' ratings, in the
+/// outcomes and in the market's book if it has one
+pub(crate) fn seed(
+    market: &Market,
+    market_book: Option<&AccountLoader<MarketBook>>,
+    home: (&mut Outcome, &Team),
+    away: (&mut Outcome, &Team),
+) -> Result<()> {
+    let (home_outcome, home_team) = home;
+    let (away_outcome, away_team) = away;
+    
+    home_outcome.odds = Team::seeded_odds(home_team.rating, away_team.rating);
+    away_outcome.odds = Team::seeded_odds(away_team.rating, home_team.rating);
+    
+    if market.has_book {
+        let market_book = market_book.ok_or(PredictionMarketError::InvalidMarketBook)?;
+        let mut market_book = market_book.load_mut()?;
+        market_book.seed_price(home_outcome.id, home_outcome.odds)?;
+        market_book.seed_price(away_outcome.id, away_outcome.odds)?;
+    }
+    
+    msg!("Odds seeded: {} {}, {} {}", home_team.name, home_outcome.odds, away_team.name, away_outcome.odds);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SeedOdds<'info> {
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        mut,
+        seeds = [b"outcome", market.key().as_ref(), home_outcome.id.to_le_bytes().as_ref()],
+        bump = home_outcome.bump
+    )]
+    pub home_outcome: Account<'info, Outcome>,
+    
+    #[account(
+        mut,
+        seeds = [b"outcome", market.key().as_ref(), away_outcome.id.to_le_bytes().as_ref()],
+        bump = away_outcome.bump
+    )]
+    pub away_outcome: Account<'info, Outcome>,
+    
+    #[account(
+        seeds = [Team::SEED_PREFIX.as_bytes(), home_team.id.to_le_bytes().as_ref()],
+        bump = home_team.bump
+    )]
+    pub home_team: Account<'info, Team>,
+    
+    #[account(
+        seeds = [Team::SEED_PREFIX.as_bytes(), away_team.id.to_le_bytes().as_ref()],
+        bump = away_team.bump
+    )]
+    pub away_team: Account<'info, Team>,
+    
+    /// Required on markets with a book
+    #[account(
+        mut,
+        seeds = [MarketBook::SEED_PREFIX.as_bytes(), market.key().as_ref()],
+        bump = market_book.load()?.bump
+    )]
+    pub market_book: Option<AccountLoader<'info, MarketBook>>,
+    
+    #[account(
+        seeds = [PlatformConfig::SEED_PREFIX.as_bytes()],
+        bump = platform_config.bump,
+        constraint = platform_config.admin == admin.key() @ PredictionMarketError::UnauthorizedAdmin
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    
+    pub admin: Signer<'info>,
+}
\ No newline at end of file
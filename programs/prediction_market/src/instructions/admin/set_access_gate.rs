@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use crate::state::{AccessGate, Market, MarketStatus, PlatformConfig};
+use crate::errors::PredictionMarketError;
+
+/// Restricts who may bet on a market from now on. Bets already placed are
+/// kept whatever the new gate.
+pub fn set_access_gate(ctx: Context<SetAccessGate>, access_gate: AccessGate) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    
+    // Validations
+    require!(
+        market.status == MarketStatus::Active,
+        PredictionMarketError::MarketNotActive
+    );
+    
+    market.access_gate = access_gate;
+    
+    msg!("Market {} access gate set to {:?}", market.id, access_gate);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetAccessGate<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        seeds = [PlatformConfig::SEED_PREFIX.as_bytes()],
+        bump = platform_config.bump,
+        constraint = platform_config.admin == admin.key() @ PredictionMarketError::UnauthorizedAdmin
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    
+    pub admin: Signer<'info>,
+}
\ No newline at end of file
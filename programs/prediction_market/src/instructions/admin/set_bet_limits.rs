@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+use crate::state::{BetLimits, Market, MarketStatus, PlatformConfig};
+use crate::errors::PredictionMarketError;
+
+/// Overrides one market's betting limits. Bets already placed are kept even
+/// if they exceed the new limits.
+pub fn set_bet_limits(ctx: Context<SetBetLimits>, limits: BetLimits) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    
+    // Validations
+    require!(
+        market.status == MarketStatus::Active,
+        PredictionMarketError::MarketNotActive
+    );
+    
+    require!(
+        limits.is_valid(),
+        PredictionMarketError::InvalidBetLimits
+    );
+    
+    market.bet_limits = limits;
+    
+    msg!("Market {} bet limits set to {:?}", market.id, limits);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetBetLimits<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        seeds = [PlatformConfig::SEED_PREFIX.as_bytes()],
+        bump = platform_config.bump,
+        constraint = platform_config.admin == admin.key() @ PredictionMarketError::UnauthorizedAdmin
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    
+    pub admin: Signer<'info>,
+}
\ No newline at end of file
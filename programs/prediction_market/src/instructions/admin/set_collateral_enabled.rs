@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::state::{CollateralConfig, PlatformConfig};
+use crate::errors::PredictionMarketError;
+
+/// Enables or disables a collateral mint for new markets. Markets already
+/// using it keep settling normally.
+pub fn set_collateral_enabled(ctx: Context<SetCollateralEnabled>, enabled: bool) -> Result<()> {
+    let collateral_config = &mut ctx.accounts.collateral_config;
+    
+    collateral_config.enabled = enabled;
+    
+    msg!("Collateral mint {} enabled: {}", collateral_config.mint, enabled);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetCollateralEnabled<'info> {
+    #[account(
+        seeds = [PlatformConfig::SEED_PREFIX.as_bytes()],
+        bump = platform_config.bump,
+        constraint = platform_config.admin == admin.key() @ PredictionMarketError::UnauthorizedAdmin
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    
+    pub admin: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [CollateralConfig::SEED_PREFIX.as_bytes(), collateral_config.mint.as_ref()],
+        bump = collateral_config.bump
+    )]
+    pub collateral_config: Account<'info, CollateralConfig>,
+}
\ No newline at end of file
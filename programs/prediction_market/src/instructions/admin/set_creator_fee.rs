@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+use crate::state::{Market, MarketStatus, PlatformConfig, BPS_DENOMINATOR};
+use crate::errors::PredictionMarketError;
+
+/// Hands a market to its creator, such as a tournament organizer, who earns
+/// `creator_fee_bps` of the pool at settlement. Only possible before the
+/// first bet, so bettors always know the fees they pay.
+pub fn set_creator_fee(ctx: Context<SetCreatorFee>, creator_fee_bps: u16) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let platform_config = &ctx.accounts.platform_config;
+    
+    // Validations
+    require!(
+        market.status == MarketStatus::Active,
+        PredictionMarketError::MarketNotActive
+    );
+    
+    require!(
+        market.total_pool == 0,
+        PredictionMarketError::MarketHasBets
+    );
+    
+    require!(
+        creator_fee_bps <= platform_config.max_creator_fee_bps,
+        PredictionMarketError::CreatorFeeTooHigh
+    );
+    
+    // Together the fees can take at most the whole pool
+    require!(
+        market.fee_bps as u64 + creator_fee_bps as u64 <= BPS_DENOMINATOR,
+        PredictionMarketError::CreatorFeeTooHigh
+    );
+    
+    market.creator = ctx.accounts.creator.key();
+    market.creator_fee_bps = creator_fee_bps;
+    
+    msg!("Market {} creator {} earns {} bps", market.id, market.creator, creator_fee_bps);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetCreatorFee<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        seeds = [PlatformConfig::SEED_PREFIX.as_bytes()],
+        bump = platform_config.bump,
+        constraint = platform_config.admin == admin.key() @ PredictionMarketError::UnauthorizedAdmin
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    
+    pub admin: Signer<'info>,
+    
+    /// CHECK: Wallet that earns the creator fee
+    pub creator: UncheckedAccount<'info>,
+}
\ No newline at end of file
@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+use crate::state::{BetLimits, PlatformConfig};
+use crate::errors::PredictionMarketError;
+
+/// Sets the betting limits copied to markets created from now on
+pub fn set_default_bet_limits(ctx: Context<SetDefaultBetLimits>, limits: BetLimits) -> Result<()> {
+    let platform_config = &mut ctx.accounts.platform_config;
+    
+    // Validations
+    require!(
+        limits.is_valid(),
+        PredictionMarketError::InvalidBetLimits
+    );
+    
+    platform_config.default_bet_limits = limits;
+    
+    msg!("Default bet limits set to {:?}", limits);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetDefaultBetLimits<'info> {
+    #[account(
+        mut,
+        seeds = [PlatformConfig::SEED_PREFIX.as_bytes()],
+        bump = platform_config.bump,
+        constraint = platform_config.admin == admin.key() @ PredictionMarketError::UnauthorizedAdmin
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    
+    pub admin: Signer<'info>,
+}
\ No newline at end of file
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+use crate::state::{FeeModel, Market, MarketStatus, PlatformConfig};
+use crate::errors::PredictionMarketError;
+
+/// Chooses what a market's platform fee is charged on, and when. Only
+/// possible before the first bet, so bettors always know the fees they pay.
+pub fn set_fee_model(ctx: Context<SetFeeModel>, fee_model: FeeModel) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    
+    // Validations
+    require!(
+        market.status == MarketStatus::Active,
+        PredictionMarketError::MarketNotActive
+    );
+    
+    require!(
+        market.total_pool == 0,
+        PredictionMarketError::MarketHasBets
+    );
+    
+    market.fee_model = fee_model;
+    
+    msg!("Market {} fee model: {:?}", market.id, fee_model);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetFeeModel<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        seeds = [PlatformConfig::SEED_PREFIX.as_bytes()],
+        bump = platform_config.bump,
+        constraint = platform_config.admin == admin.key() @ PredictionMarketError::UnauthorizedAdmin
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    
+    pub admin: Signer<'info>,
+}
\ No newline at end of file
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::state::{FeeTier, PlatformConfig};
+use crate::errors::PredictionMarketError;
+
+/// Replaces the volume-based fee tiers. A user's discount applies to bets
+/// placed from then on, based on their lifetime volume before each bet.
+pub fn set_fee_tiers(ctx: Context<SetFeeTiers>, fee_tiers: [FeeTier; PlatformConfig::FEE_TIERS]) -> Result<()> {
+    let platform_config = &mut ctx.accounts.platform_config;
+    
+    // Validations
+    require!(
+        fee_tiers.iter().all(FeeTier::is_valid),
+        PredictionMarketError::InvalidFeeTiers
+    );
+    
+    platform_config.fee_tiers = fee_tiers;
+    
+    msg!("Fee tiers set: {:?}", fee_tiers);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetFeeTiers<'info> {
+    #[account(
+        mut,
+        seeds = [PlatformConfig::SEED_PREFIX.as_bytes()],
+        bump = platform_config.bump,
+        constraint = platform_config.admin == admin.key() @ PredictionMarketError::UnauthorizedAdmin
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    
+    pub admin: Signer<'info>,
+}
\ No newline at end of file
@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use crate::state::{CollateralMode, House, Market, MarketStatus, PlatformConfig};
+use crate::errors::PredictionMarketError;
+
+/// Makes a market take bets at fixed odds against the house in its
+/// collateral instead of into a parimutuel pool. The house may owe at most
+/// `max_liability` on any one outcome, or without limit if zero. Only
+/// possible before the first bet, and not on markets with a book.
+pub fn set_fixed_odds(ctx: Context<SetFixedOdds>, max_liability: u64) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    
+    // Validations
+    require!(
+        market.status == MarketStatus::Active,
+        PredictionMarketError::MarketNotActive
+    );
+    
+    require!(
+        market.total_pool == 0,
+        PredictionMarketError::MarketHasBets
+    );
+    
+    require!(
+        market.collateral_mode == CollateralMode::Token,
+        PredictionMarketError::InvalidCollateralMode
+    );
+    
+    // A book prices the pool, which fixed-odds markets don't have
+    require!(
+        !market.has_book,
+        PredictionMarketError::InvalidMarketMode
+    );
+    
+    market.has_fixed_odds = true;
+    market.max_liability = max_liability;
+    
+    msg!("Market {} takes fixed odds against house: {}", market.id, ctx.accounts.house.key());
+    msg!("Max liability per outcome: {}", max_liability);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetFixedOdds<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    
+    /// The house that will take the market's bets
+    #[account(
+        seeds = [House::SEED_PREFIX.as_bytes(), market.collateral_mint.as_ref()],
+        bump = house.bump
+    )]
+    pub house: Account<'info, House>,
+    
+    #[account(
+        seeds = [PlatformConfig::SEED_PREFIX.as_bytes()],
+        bump = platform_config.bump,
+        constraint = platform_config.admin == admin.key() @ PredictionMarketError::UnauthorizedAdmin
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    
+    pub admin: Signer<'info>,
+}
\ No newline at end of file
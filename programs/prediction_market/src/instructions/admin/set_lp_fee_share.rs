@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::state::{PlatformConfig, BPS_DENOMINATOR};
+use crate::errors::PredictionMarketError;
+
+/// Sets the share of market fees, in basis points, that liquidity providers
+/// earn on pools opened from now on
+pub fn set_lp_fee_share(ctx: Context<SetLpFeeShare>, lp_fee_share_bps: u16) -> Result<()> {
+    let platform_config = &mut ctx.accounts.platform_config;
+    
+    // Validations
+    require!(
+        lp_fee_share_bps as u64 <= BPS_DENOMINATOR,
+        PredictionMarketError::InvalidFeeShare
+    );
+    
+    platform_config.lp_fee_share_bps = lp_fee_share_bps;
+    
+    msg!("LP fee share set to {} bps", lp_fee_share_bps);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetLpFeeShare<'info> {
+    #[account(
+        mut,
+        seeds = [PlatformConfig::SEED_PREFIX.as_bytes()],
+        bump = platform_config.bump,
+        constraint = platform_config.admin == admin.key() @ PredictionMarketError::UnauthorizedAdmin
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    
+    pub admin: Signer<'info>,
+}
\ No newline at end of file
@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+use crate::state::{Market, MarketCondition, MarketStatus, PlatformConfig};
+use crate::errors::PredictionMarketError;
+
+/// Makes a market conditional on `parent_market` resolving to
+/// `parent_outcome_id`. Only possible before the first bet. If the parent
+/// goes any other way or is cancelled, the market is refunded at settlement.
+pub fn set_market_condition(ctx: Context<SetMarketCondition>, parent_outcome_id: u8) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let parent_market = &ctx.accounts.parent_market;
+    
+    // Validations
+    require!(
+        market.status == MarketStatus::Active,
+        PredictionMarketError::MarketNotActive
+    );
+    
+    require!(
+        market.total_pool == 0,
+        PredictionMarketError::MarketHasBets
+    );
+    
+    require!(
+        parent_market.key() != market.key() && parent_market.status == MarketStatus::Active,
+        PredictionMarketError::InvalidCondition
+    );
+    
+    require!(
+        parent_market.outcomes.contains(&parent_outcome_id),
+        PredictionMarketError::OutcomeNotFound
+    );
+    
+    let condition = &mut ctx.accounts.condition;
+    condition.version = MarketCondition::VERSION;
+    condition.market = market.key();
+    condition.parent_market = parent_market.key();
+    condition.parent_outcome_id = parent_outcome_id;
+    condition.bump = ctx.bumps.condition;
+    
+    market.has_condition = true;
+    
+    msg!("Market {} conditional on market {} outcome {}", market.id, parent_market.id, parent_outcome_id);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetMarketCondition<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    
+    pub parent_market: Account<'info, Market>,
+    
+    #[account(
+        init,
+        payer = admin,
+        space = MarketCondition::SIZE,
+        seeds = [MarketCondition::SEED_PREFIX.as_bytes(), market.key().as_ref()],
+        bump
+    )]
+    pub condition: Account<'info, MarketCondition>,
+    
+    #[account(
+        seeds = [PlatformConfig::SEED_PREFIX.as_bytes()],
+        bump = platform_config.bump,
+        constraint = platform_config.admin == admin.key() @ PredictionMarketError::UnauthorizedAdmin
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
\ No newline at end of file
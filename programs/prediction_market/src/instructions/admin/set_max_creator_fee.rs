@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::state::{PlatformConfig, BPS_DENOMINATOR};
+use crate::errors::PredictionMarketError;
+
+/// Sets the highest creator fee, in basis points, a market can be given.
+/// Markets that already charge more keep their fee.
+pub fn set_max_creator_fee(ctx: Context<SetMaxCreatorFee>, max_creator_fee_bps: u16) -> Result<()> {
+    let platform_config = &mut ctx.accounts.platform_config;
+    
+    // Validations
+    require!(
+        max_creator_fee_bps as u64 <= BPS_DENOMINATOR,
+        PredictionMarketError::CreatorFeeTooHigh
+    );
+    
+    platform_config.max_creator_fee_bps = max_creator_fee_bps;
+    
+    msg!("Max creator fee set to {} bps", max_creator_fee_bps);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetMaxCreatorFee<'info> {
+    #[account(
+        mut,
+        seeds = [PlatformConfig::SEED_PREFIX.as_bytes()],
+        bump = platform_config.bump,
+        constraint = platform_config.admin == admin.key() @ PredictionMarketError::UnauthorizedAdmin
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    
+    pub admin: Signer<'info>,
+}
\ No newline at end of file
@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use crate::state::{Market, MarketStatus, Outcome, PlatformConfig, BPS_DENOMINATOR};
+use crate::errors::PredictionMarketError;
+
+/// Posts the odds a fixed-odds market's outcome is offered at, scaled by
+/// 10000. Bets already placed keep the payout they locked in.
+pub fn set_outcome_odds(ctx: Context<SetOutcomeOdds>, odds: u64) -> Result<()> {
+    let market = &ctx.accounts.market;
+    let outcome = &mut ctx.accounts.outcome;
+    
+    // Validations
+    require!(
+        market.has_fixed_odds,
+        PredictionMarketError::InvalidMarketMode
+    );
+    
+    require!(
+        market.status == MarketStatus::Active,
+        PredictionMarketError::MarketNotActive
+    );
+    
+    require!(
+        !outcome.eliminated,
+        PredictionMarketError::OutcomeEliminated
+    );
+    
+    require!(
+        odds > BPS_DENOMINATOR,
+        PredictionMarketError::InvalidOdds
+    );
+    
+    outcome.odds = odds;
+    
+    msg!("Outcome {} odds: {}", outcome.name, odds);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetOutcomeOdds<'info> {
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        mut,
+        seeds = [b"outcome", market.key().as_ref(), outcome.id.to_le_bytes().as_ref()],
+        bump = outcome.bump
+    )]
+    pub outcome: Account<'info, Outcome>,
+    
+    #[account(
+        seeds = [PlatformConfig::SEED_PREFIX.as_bytes()],
+        bump = platform_config.bump
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    
+    /// The market's oracle or the platform admin
+    #[account(
+        constraint = authority.key() == market.oracle
+            || authority.key() == platform_config.admin @ PredictionMarketError::UnauthorizedOracle
+    )]
+    pub authority: Signer<'info>,
+}
\ No newline at end of file
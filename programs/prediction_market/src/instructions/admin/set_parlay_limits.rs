@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use crate::state::{House, LegLimits, ParlayLimits, PlatformConfig};
+use crate::errors::PredictionMarketError;
+
+/// Replaces the house's parlay limits. Open parlays are kept even if they
+/// exceed the new limits.
+pub fn set_parlay_limits(ctx: Context<SetParlayLimits>, limits: ParlayLimits, leg_limits: LegLimits) -> Result<()> {
+    // Validations
+    require!(
+        limits.is_valid(),
+        PredictionMarketError::InvalidParlayLegCount
+    );
+    
+    require!(
+        limits.caps_payout() && leg_limits.is_valid(),
+        PredictionMarketError::InvalidParlayLimits
+    );
+    
+    let house = &mut ctx.accounts.house;
+    house.limits = limits;
+    house.leg_limits = leg_limits;
+    
+    msg!("House {} parlay limits set to {:?}", house.mint, limits);
+    msg!("Leg limits: {:?}", leg_limits);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetParlayLimits<'info> {
+    #[account(
+        mut,
+        seeds = [House::SEED_PREFIX.as_bytes(), house.mint.as_ref()],
+        bump = house.bump
+    )]
+    pub house: Account<'info, House>,
+    
+    #[account(
+        seeds = [PlatformConfig::SEED_PREFIX.as_bytes()],
+        bump = platform_config.bump,
+        constraint = platform_config.admin == admin.key() @ PredictionMarketError::UnauthorizedAdmin
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    
+    pub admin: Signer<'info>,
+}
\ No newline at end of file
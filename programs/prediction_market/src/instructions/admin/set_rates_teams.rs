@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use crate::state::{Market, MarketStatus, PlatformConfig};
+use crate::errors::PredictionMarketError;
+
+/// Chooses whether resolving a head-to-head market moves its teams'
+/// ratings. Opt in one market per match, such as its match winner, so
+/// that a result moves the ratings once however many markets it settles.
+pub fn set_rates_teams(ctx: Context<SetRatesTeams>, rates_teams: bool) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    
+    // Validations
+    require!(
+        market.status == MarketStatus::Active,
+        PredictionMarketError::MarketNotActive
+    );
+    
+    market.rates_teams = rates_teams;
+    
+    msg!("Market {} rates teams: {}", market.id, rates_teams);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetRatesTeams<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        seeds = [PlatformConfig::SEED_PREFIX.as_bytes()],
+        bump = platform_config.bump,
+        constraint = platform_config.admin == admin.key() @ PredictionMarketError::UnauthorizedAdmin
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    
+    pub admin: Signer<'info>,
+}
\ No newline at end of file
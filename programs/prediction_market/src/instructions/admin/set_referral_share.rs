@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::state::{PlatformConfig, BPS_DENOMINATOR};
+use crate::errors::PredictionMarketError;
+
+/// Sets the share of market fees, in basis points, that referrers earn on
+/// markets created from now on
+pub fn set_referral_share(ctx: Context<SetReferralShare>, referral_share_bps: u16) -> Result<()> {
+    let platform_config = &mut ctx.accounts.platform_config;
+    
+    // Validations
+    require!(
+        referral_share_bps as u64 <= BPS_DENOMINATOR,
+        PredictionMarketError::InvalidReferralShare
+    );
+    
+    platform_config.referral_share_bps = referral_share_bps;
+    
+    msg!("Referral share set to {} bps", referral_share_bps);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetReferralShare<'info> {
+    #[account(
+        mut,
+        seeds = [PlatformConfig::SEED_PREFIX.as_bytes()],
+        bump = platform_config.bump,
+        constraint = platform_config.admin == admin.key() @ PredictionMarketError::UnauthorizedAdmin
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    
+    pub admin: Signer<'info>,
+}
\ No newline at end of file
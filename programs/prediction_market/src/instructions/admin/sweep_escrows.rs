@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+use super::resolve_market::{pay_out, sweep, ResolveMarket};
+use crate::state::MarketStatus;
+use crate::errors::PredictionMarketError;
+
+/// Continues settling a book market that was resolved before all of its
+/// losing escrows were swept, in batches small enough for one transaction.
+/// `remaining_accounts` holds the next losing outcomes, in `market.outcomes`
+/// order from where the last batch stopped, each as its escrow authority
+/// followed by its escrow token account. The batch that sweeps the last of
+/// them pays out the fees, and winners can claim from then on.
+pub fn sweep_escrows<'info>(ctx: Context<'_, '_, 'info, 'info, ResolveMarket<'info>>) -> Result<()> {
+    let market = &ctx.accounts.market;
+    
+    // Validations
+    require!(
+        market.status == MarketStatus::Resolved,
+        PredictionMarketError::MarketNotResolved
+    );
+    
+    require!(
+        market.winner == Some(ctx.accounts.winning_outcome.id),
+        PredictionMarketError::OutcomeNotFound
+    );
+    
+    let unswept = market.unswept_escrows as usize;
+    let swept = ctx.remaining_accounts.len() / 2;
+    require!(
+        ctx.remaining_accounts.len().is_multiple_of(2) && swept > 0 && swept <= unswept,
+        PredictionMarketError::InvalidSettlementAccounts
+    );
+    
+    let losing_outcomes: Vec<u8> = market
+        .outcomes
+        .iter()
+        .copied()
+        .filter(|id| market.winner != Some(*id))
+        .collect();
+    let first = losing_outcomes.len() - unswept;
+    
+    sweep(ctx.accounts, ctx.remaining_accounts, ctx.program_id, &losing_outcomes[first..first + swept])?;
+    
+    let market = &mut ctx.accounts.market;
+    market.unswept_escrows -= swept as u8;
+    msg!("Swept escrows: {}", swept);
+    msg!("Escrows left to sweep: {}", market.unswept_escrows);
+    
+    if market.unswept_escrows == 0 {
+        pay_out(ctx.accounts, ctx.bumps.winning_escrow_authority)?;
+    }
+    
+    Ok(())
+}
\ No newline at end of file
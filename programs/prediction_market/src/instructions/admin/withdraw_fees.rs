@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, TokenAccount, Token, Transfer};
-use crate::state::PlatformConfig;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::{CollateralConfig, PlatformConfig};
 use crate::errors::PredictionMarketError;
 
 pub fn withdraw_fees(ctx: Context<WithdrawFees>) -> Result<()> {
@@ -15,8 +15,9 @@ pub fn withdraw_fees(ctx: Context<WithdrawFees>) -> Result<()> {
     );
     
     // Transfer tokens from treasury to admin
-    let cpi_accounts = Transfer {
+    let cpi_accounts = TransferChecked {
         from: ctx.accounts.treasury_token_account.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
         to: ctx.accounts.admin_token_account.to_account_info(),
         authority: ctx.accounts.treasury_authority.to_account_info(),
     };
@@ -37,7 +38,7 @@ pub fn withdraw_fees(ctx: Context<WithdrawFees>) -> Result<()> {
     );
     
     // Execute the transfer
-    token::transfer(cpi_ctx, amount)?;
+    token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.mint.decimals)?;
     
     msg!("Fees withdrawn: {}", amount);
     
@@ -57,21 +58,26 @@ pub struct WithdrawFees<'info> {
     pub admin: Signer<'info>,
     
     /// The mint of the token being used for fees
-    pub mint: Account<'info, anchor_spl::token::Mint>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        seeds = [CollateralConfig::SEED_PREFIX.as_bytes(), mint.key().as_ref()],
+        bump = collateral_config.bump
+    )]
+    pub collateral_config: Account<'info, CollateralConfig>,
     
     #[account(
         mut,
         constraint = admin_token_account.owner == admin.key() @ PredictionMarketError::UnauthorizedAdmin,
         constraint = admin_token_account.mint == mint.key() @ PredictionMarketError::InvalidMint
     )]
-    pub admin_token_account: Account<'info, TokenAccount>,
+    pub admin_token_account: InterfaceAccount<'info, TokenAccount>,
     
     #[account(
         mut,
-        constraint = treasury_token_account.mint == mint.key() @ PredictionMarketError::InvalidMint,
-        constraint = platform_config.treasury == treasury_token_account.key() @ PredictionMarketError::UnauthorizedAdmin
+        address = collateral_config.treasury @ PredictionMarketError::UnauthorizedAdmin
     )]
-    pub treasury_token_account: Account<'info, TokenAccount>,
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
     
     /// CHECK: This is the PDA that has authority over the treasury
     #[account(
@@ -80,6 +86,6 @@ pub struct WithdrawFees<'info> {
     )]
     pub treasury_authority: UncheckedAccount<'info>,
     
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
\ No newline at end of file
@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::{House, PlatformConfig};
+use crate::errors::PredictionMarketError;
+
+/// Withdraws liquidity from the house vault, leaving enough to pay every
+/// open parlay if they all win and what fixed-odds markets have reserved.
+pub fn withdraw_house_liquidity(ctx: Context<WithdrawHouseLiquidity>, amount: u64) -> Result<()> {
+    let house = &ctx.accounts.house;
+    
+    // Validations
+    require!(
+        amount > 0 && amount <= house.free_liquidity(ctx.accounts.vault.amount),
+        PredictionMarketError::InsufficientLiquidity
+    );
+    
+    let mint_key = house.mint;
+    let bump = [house.bump];
+    let house_seeds = &[
+        House::SEED_PREFIX.as_bytes(),
+        mint_key.as_ref(),
+        &bump
+    ];
+    let signer_seeds = &[&house_seeds[..]];
+    
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.vault.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
+        to: ctx.accounts.admin_token_account.to_account_info(),
+        authority: house.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds
+    );
+    token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.mint.decimals)?;
+    
+    msg!("House liquidity withdrawn: {}", amount);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct WithdrawHouseLiquidity<'info> {
+    #[account(
+        seeds = [House::SEED_PREFIX.as_bytes(), house.mint.as_ref()],
+        bump = house.bump
+    )]
+    pub house: Account<'info, House>,
+    
+    #[account(
+        mut,
+        address = house.vault @ PredictionMarketError::InvalidTreasury
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(address = house.mint @ PredictionMarketError::InvalidMint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        constraint = admin_token_account.owner == admin.key() @ PredictionMarketError::UnauthorizedAdmin,
+        constraint = admin_token_account.mint == mint.key() @ PredictionMarketError::InvalidMint
+    )]
+    pub admin_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        seeds = [PlatformConfig::SEED_PREFIX.as_bytes()],
+        bump = platform_config.bump,
+        constraint = platform_config.admin == admin.key() @ PredictionMarketError::UnauthorizedAdmin
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    
+    pub admin: Signer<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
\ No newline at end of file
@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+use crate::state::{CollateralConfig, Market, PlatformConfig, MAX_MARKET_DESCRIPTION_LEN, MAX_MARKET_NAME_LEN, MAX_OUTCOMES};
+use crate::state::legacy::LegacyMarket;
+use crate::errors::PredictionMarketError;
+use super::{is_current, load_legacy, rewrite};
+
+/// Brings a market up to the current layout, which has room for the longest
+/// name and description a market can hold. The admin names the collateral
+/// mint its outcomes' escrows were opened for, which the first version of
+/// the program never recorded; `migrate_outcome` then checks each escrow
+/// against it.
+pub fn migrate_market(ctx: Context<MigrateMarket>) -> Result<()> {
+    let account = ctx.accounts.market.to_account_info();
+    let platform_config = &ctx.accounts.platform_config;
+    
+    // Validations
+    require!(
+        !is_current::<Market>(&account, |market| Market::space_for(market.outcomes.len()), |market| market.version, Market::VERSION)?,
+        PredictionMarketError::InvalidMigration
+    );
+    
+    let legacy: LegacyMarket = load_legacy(&account, Market::DISCRIMINATOR, LegacyMarket::space)?;
+    
+    // The first version never took a bet, so there is no pool to carry over
+    require!(
+        legacy.total_pool == 0,
+        PredictionMarketError::InvalidMigration
+    );
+    
+    // Strings and outcomes beyond what the current layout holds cannot be
+    // carried over
+    require!(
+        legacy.name.len() <= MAX_MARKET_NAME_LEN,
+        PredictionMarketError::InvalidMigration
+    );
+    require!(
+        legacy.description.len() <= MAX_MARKET_DESCRIPTION_LEN,
+        PredictionMarketError::InvalidMigration
+    );
+    require!(
+        legacy.outcomes.len() <= MAX_OUTCOMES,
+        PredictionMarketError::InvalidMigration
+    );
+    
+    let migrated = legacy.migrate(
+        ctx.accounts.collateral_config.mint,
+        platform_config.default_bet_limits,
+        platform_config.referral_share_bps,
+    );
+    
+    rewrite(&account, &ctx.accounts.admin, &ctx.accounts.system_program, &migrated, Market::SIZE)?;
+    
+    msg!("Migrated market: {}", account.key());
+    msg!("Collateral mint: {}", migrated.collateral_mint);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct MigrateMarket<'info> {
+    /// CHECK: Decoded by hand, since it is still in an earlier layout
+    #[account(mut, owner = crate::ID @ PredictionMarketError::InvalidMigration)]
+    pub market: UncheckedAccount<'info>,
+    
+    #[account(
+        seeds = [PlatformConfig::SEED_PREFIX.as_bytes()],
+        bump = platform_config.bump,
+        constraint = platform_config.admin == admin.key() @ PredictionMarketError::UnauthorizedAdmin
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    
+    #[account(
+        seeds = [CollateralConfig::SEED_PREFIX.as_bytes(), collateral_config.mint.as_ref()],
+        bump = collateral_config.bump
+    )]
+    pub collateral_config: Account<'info, CollateralConfig>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
\ No newline at end of file
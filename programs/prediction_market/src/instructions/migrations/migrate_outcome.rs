@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::get_associated_token_address;
+use crate::state::{Market, Outcome, MAX_OUTCOME_NAME_LEN};
+use crate::state::legacy::LegacyOutcome;
+use crate::errors::PredictionMarketError;
+use super::{is_current, load_legacy, rewrite};
+
+/// Brings an outcome up to the current layout, which has room for the
+/// longest name an outcome can hold. Its market must be migrated first, and
+/// its escrow must be the SPL Token account for the market's collateral.
+pub fn migrate_outcome(ctx: Context<MigrateOutcome>) -> Result<()> {
+    let account = ctx.accounts.outcome.to_account_info();
+    let market = &ctx.accounts.market;
+    
+    // Validations
+    require!(
+        !is_current::<Outcome>(&account, |_| Outcome::SIZE, |outcome| outcome.version, Outcome::VERSION)?,
+        PredictionMarketError::InvalidMigration
+    );
+    
+    let legacy: LegacyOutcome = load_legacy(&account, Outcome::DISCRIMINATOR, LegacyOutcome::space)?;
+    
+    // Claims are only derived for an outcome nothing was staked on, which
+    // every outcome of the first version is
+    require!(
+        legacy.total_staked == 0,
+        PredictionMarketError::InvalidMigration
+    );
+    require!(
+        legacy.market == market.key(),
+        PredictionMarketError::InvalidMigration
+    );
+    let escrow_authority = Pubkey::find_program_address(
+        &[b"escrow", market.key().as_ref(), &legacy.id.to_le_bytes()],
+        &crate::ID,
+    ).0;
+    require!(
+        legacy.escrow_pubkey == get_associated_token_address(&escrow_authority, &market.collateral_mint),
+        PredictionMarketError::InvalidMint
+    );
+    
+    // Strings longer than the current layout holds cannot be carried over
+    require!(
+        legacy.name.len() <= MAX_OUTCOME_NAME_LEN,
+        PredictionMarketError::InvalidMigration
+    );
+    
+    let migrated = legacy.migrate();
+    
+    rewrite(&account, &ctx.accounts.payer, &ctx.accounts.system_program, &migrated, Outcome::SIZE)?;
+    
+    msg!("Migrated outcome: {}", account.key());
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct MigrateOutcome<'info> {
+    /// CHECK: Decoded by hand, since it is still in an earlier layout
+    #[account(mut, owner = crate::ID @ PredictionMarketError::InvalidMigration)]
+    pub outcome: UncheckedAccount<'info>,
+    
+    pub market: Account<'info, Market>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
\ No newline at end of file
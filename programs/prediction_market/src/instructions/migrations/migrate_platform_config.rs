@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use crate::state::PlatformConfig;
+use crate::state::legacy::LegacyPlatformConfig;
+use crate::errors::PredictionMarketError;
+use super::{is_current, load_legacy, rewrite};
+
+/// Brings the platform config up to the current layout. Its percent fee
+/// becomes basis points, and its single treasury is dropped in favour of a
+/// treasury per collateral mint.
+pub fn migrate_platform_config(ctx: Context<MigratePlatformConfig>) -> Result<()> {
+    let account = ctx.accounts.platform_config.to_account_info();
+    
+    // Validations
+    require!(
+        !is_current::<PlatformConfig>(&account, |_| PlatformConfig::SIZE, |config| config.version, PlatformConfig::VERSION)?,
+        PredictionMarketError::InvalidMigration
+    );
+    
+    let legacy: LegacyPlatformConfig = load_legacy(&account, PlatformConfig::DISCRIMINATOR, |_| LegacyPlatformConfig::SIZE)?;
+    let migrated = legacy.migrate();
+    
+    rewrite(&account, &ctx.accounts.payer, &ctx.accounts.system_program, &migrated, PlatformConfig::SIZE)?;
+    
+    msg!("Migrated platform config: {}", account.key());
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct MigratePlatformConfig<'info> {
+    /// CHECK: Decoded by hand, since it is still in an earlier layout
+    #[account(mut, owner = crate::ID @ PredictionMarketError::InvalidMigration)]
+    pub platform_config: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
\ No newline at end of file
@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use crate::errors::PredictionMarketError;
+
+pub mod migrate_platform_config;
+pub mod migrate_market;
+pub mod migrate_outcome;
+
+pub use migrate_platform_config::*;
+pub use migrate_market::*;
+pub use migrate_outcome::*;
+
+/// Whether `account` is already in the current layout: decoding at the
+/// `current` version and allocated at the `size` that layout gives it.
+/// Accounts from the first version carry no version byte, so it is their
+/// size that sets apart any that happen to decode
+fn is_current<T: AccountDeserialize>(
+    account: &AccountInfo,
+    size: impl Fn(&T) -> usize,
+    version: impl Fn(&T) -> u8,
+    current: u8,
+) -> Result<bool> {
+    let data = account.try_borrow_data()?;
+    Ok(T::try_deserialize(&mut &data[..]).is_ok_and(|decoded| version(&decoded) == current && data.len() == size(&decoded)))
+}
+
+/// Decodes `account` in an earlier layout, checking its discriminator first
+/// and then that it is exactly the size that layout was allocated at
+fn load_legacy<T: AnchorDeserialize>(
+    account: &AccountInfo,
+    discriminator: &[u8],
+    space: impl Fn(&T) -> usize,
+) -> Result<T> {
+    let data = account.try_borrow_data()?;
+    require!(
+        data.len() >= 8 && &data[..8] == discriminator,
+        PredictionMarketError::InvalidMigration
+    );
+    let legacy = T::deserialize(&mut &data[8..]).map_err(|_| PredictionMarketError::InvalidMigration)?;
+    require!(
+        data.len() == space(&legacy),
+        PredictionMarketError::InvalidMigration
+    );
+    Ok(legacy)
+}
+
+/// Resizes `account` to `space`, with `payer` topping up rent, and writes
+/// `migrated` over the old data
+fn rewrite<'info, T: AccountSerialize>(
+    account: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    migrated: &T,
+    space: usize,
+) -> Result<()> {
+    let top_up = Rent::get()?.minimum_balance(space).saturating_sub(account.lamports());
+    if top_up > 0 {
+        let cpi_accounts = Transfer {
+            from: payer.to_account_info(),
+            to: account.clone(),
+        };
+        system_program::transfer(CpiContext::new(system_program.to_account_info(), cpi_accounts), top_up)?;
+    }
+    
+    account.resize(space)?;
+    migrated.try_serialize(&mut &mut account.try_borrow_mut_data()?[..])
+}
\ No newline at end of file
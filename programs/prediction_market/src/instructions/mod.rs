@@ -1,5 +1,7 @@
 pub mod admin;
 pub mod users;
+pub mod migrations;
 
 pub use admin::*;
-pub use users::*;
\ No newline at end of file
+pub use users::*;
+pub use migrations::*;
\ No newline at end of file
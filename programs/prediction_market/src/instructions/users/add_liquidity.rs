@@ -0,0 +1,315 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{self, Mint, MintTo, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::{
+    AllowlistEntry, CollateralMode, Credential, FeeModel, LiquidityPool, Market, MarketBook, MarketStatus, Outcome, PlatformConfig,
+    UserProfile, BPS_DENOMINATOR, MAX_OUTCOMES,
+};
+use crate::errors::PredictionMarketError;
+
+/// Deposits `amount` into the market's liquidity pool, staked across its
+/// outcomes, and mints LP tokens for the provider's share of the pool.
+///
+/// The first deposit is split in proportion to the market's stakes, or by
+/// its seeded odds if nothing is staked yet, or evenly, giving eliminated
+/// outcomes nothing. Later deposits follow the pool's own split, even onto
+/// outcomes eliminated since, so every LP token stays a claim on the same
+/// stake of each outcome.
+///
+/// Like a bet, a deposit must pass the market's access gate and pool limit
+/// and the provider's self-exclusion and deposit limits.
+///
+/// `remaining_accounts` must hold, for every outcome in `market.outcomes`
+/// order, the writable `Outcome` and its escrow token account.
+pub fn add_liquidity<'info>(
+    ctx: Context<'_, '_, 'info, 'info, AddLiquidity<'info>>,
+    amount: u64,
+) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let liquidity_pool = &mut ctx.accounts.liquidity_pool;
+    let clock = Clock::get()?;
+    
+    // Validations
+    require!(
+        market.status == MarketStatus::Active,
+        PredictionMarketError::MarketNotActive
+    );
+    
+    require!(
+        clock.unix_timestamp < market.end_time,
+        PredictionMarketError::MarketAlreadyClosed
+    );
+    
+    require!(
+        amount > 0,
+        PredictionMarketError::InvalidLiquidityAmount
+    );
+    
+    // Only token markets, since payouts come out of several escrows at once
+    require!(
+        market.collateral_mode == CollateralMode::Token,
+        PredictionMarketError::InvalidCollateralMode
+    );
+    
+    // Fixed-odds bets are backed by the house rather than a pool
+    require!(
+        !market.has_fixed_odds,
+        PredictionMarketError::InvalidMarketMode
+    );
+    
+    // A fee charged at bet time has all gone to the treasury, leaving no
+    // share for the pool
+    require!(
+        market.fee_model != FeeModel::AtBet,
+        PredictionMarketError::FeeChargedAtBet
+    );
+    
+    // Deposits and withdrawals touch every outcome's escrow at once, which
+    // only fits a transaction up to `MAX_OUTCOMES`
+    require!(
+        market.outcomes.len() <= MAX_OUTCOMES,
+        PredictionMarketError::TooManyOutcomes
+    );
+    
+    require!(
+        ctx.remaining_accounts.len() == market.outcomes.len() * 2,
+        PredictionMarketError::InvalidSettlementAccounts
+    );
+    
+    // Providers pass the same gate as bettors
+    market.access_gate.check(
+        ctx.accounts.provider.key(),
+        ctx.accounts.allowlist_entry.as_deref(),
+        ctx.accounts.credential.as_deref(),
+        clock.unix_timestamp,
+    )?;
+    
+    // Liquidity fills the pool like any bet, checked against the amount sent
+    let pool = market.total_pool.checked_add(amount)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    market.bet_limits.check_pool(pool)?;
+    
+    // Self-exclusion and deposit limits cover liquidity too, though it can
+    // be withdrawn again and so doesn't count towards the fee tier
+    let user_profile = &mut ctx.accounts.user_profile;
+    user_profile.version = UserProfile::VERSION;
+    user_profile.user = ctx.accounts.provider.key();
+    user_profile.bump = ctx.bumps.user_profile;
+    user_profile.record_deposit(amount, clock.unix_timestamp)?;
+    
+    // A fresh pool takes the platform's current fee share
+    if liquidity_pool.version == 0 {
+        liquidity_pool.version = LiquidityPool::VERSION;
+        liquidity_pool.market = market.key();
+        liquidity_pool.lp_mint = ctx.accounts.lp_mint.key();
+        liquidity_pool.fee_share_bps = ctx.accounts.platform_config.lp_fee_share_bps;
+        liquidity_pool.stakes = vec![0; market.outcomes.len()];
+        liquidity_pool.bump = ctx.bumps.liquidity_pool;
+        market.has_liquidity = true;
+    }
+    
+    let mut outcomes = Vec::with_capacity(market.outcomes.len());
+    for (outcome_id, accounts) in market.outcomes.iter().zip(ctx.remaining_accounts.chunks(2)) {
+        let outcome = Account::<Outcome>::try_from(&accounts[0])?;
+        require!(
+            outcome.market == market.key() && outcome.id == *outcome_id,
+            PredictionMarketError::OutcomeNotFound
+        );
+        require_keys_eq!(
+            accounts[1].key(),
+            outcome.escrow_pubkey,
+            PredictionMarketError::InvalidSettlementAccounts
+        );
+        outcomes.push(outcome);
+    }
+    
+    // LP tokens are priced on the pool's whole stake, so later deposits
+    // follow its split exactly, stake on eliminated outcomes included
+    let has_stake = liquidity_pool.total_stake() > 0;
+    let weights: Vec<u64> = outcomes
+        .iter()
+        .zip(&liquidity_pool.stakes)
+        .map(|(outcome, pool_stake)| match outcome.odds {
+            _ if has_stake => *pool_stake,
+            _ if outcome.eliminated => 0,
+            _ if market.total_pool > 0 => outcome.total_staked,
+            // Seeded odds price the outcome's chance at 10000 / odds
+            0 => BPS_DENOMINATOR,
+            odds => BPS_DENOMINATOR * BPS_DENOMINATOR / odds,
+        })
+        .collect();
+    let parts = LiquidityPool::split(amount, &weights)?;
+    
+    // Stake each part in its outcome's escrow
+    let mut stake = 0u64;
+    for (index, (outcome, part)) in outcomes.iter_mut().zip(parts).enumerate() {
+        if part == 0 {
+            continue;
+        }
+    
+        let escrow = &ctx.remaining_accounts[index * 2 + 1];
+        let escrow_balance_before = InterfaceAccount::<TokenAccount>::try_from(escrow)?.amount;
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.provider_token_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            to: escrow.clone(),
+            authority: ctx.accounts.provider.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+        token_interface::transfer_checked(cpi_ctx, part, ctx.accounts.mint.decimals)?;
+    
+        // Transfer-fee mints withhold part of the transfer, so credit only
+        // what the escrow actually received
+        let received = InterfaceAccount::<TokenAccount>::try_from(escrow)?.amount
+            .checked_sub(escrow_balance_before)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+    
+        // The pool is one more claimant on each outcome it first stakes on
+        if liquidity_pool.stakes[index] == 0 {
+            outcome.claimants_remaining = outcome.claimants_remaining.checked_add(1)
+                .ok_or(PredictionMarketError::MathOverflow)?;
+        }
+        liquidity_pool.stakes[index] = liquidity_pool.stakes[index].checked_add(received)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        outcome.total_staked = outcome.total_staked.checked_add(received)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        market.total_pool = market.total_pool.checked_add(received)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        stake = stake.checked_add(received)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+    
+        if market.has_book {
+            let market_book = ctx.accounts.market_book
+                .as_ref()
+                .ok_or(PredictionMarketError::InvalidMarketBook)?;
+            market_book.load_mut()?.record_bet(market, outcome.id, received)?;
+        }
+        outcome.exit(ctx.program_id)?;
+    }
+    
+    require!(
+        stake > 0,
+        PredictionMarketError::InvalidLiquidityAmount
+    );
+    
+    // Mint the provider's LP tokens, priced on the pool before this deposit
+    let lp_tokens = liquidity_pool.lp_tokens_for(stake)?;
+    require!(
+        lp_tokens > 0,
+        PredictionMarketError::InvalidLiquidityAmount
+    );
+    
+    let market_key = market.key();
+    let bump = [liquidity_pool.bump];
+    let pool_seeds = &[
+        LiquidityPool::SEED_PREFIX.as_bytes(),
+        market_key.as_ref(),
+        &bump
+    ];
+    let signer_seeds = &[&pool_seeds[..]];
+    
+    let cpi_accounts = MintTo {
+        mint: ctx.accounts.lp_mint.to_account_info(),
+        to: ctx.accounts.provider_lp_token_account.to_account_info(),
+        authority: liquidity_pool.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds
+    );
+    token_interface::mint_to(cpi_ctx, lp_tokens)?;
+    
+    liquidity_pool.lp_supply = liquidity_pool.lp_supply.checked_add(lp_tokens)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    
+    msg!("Liquidity added: {}", stake);
+    msg!("LP tokens minted: {}", lp_tokens);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AddLiquidity<'info> {
+    #[account(mut)]
+    pub provider: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = market.status == MarketStatus::Active @ PredictionMarketError::MarketNotActive
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        seeds = [PlatformConfig::SEED_PREFIX.as_bytes()],
+        bump = platform_config.bump
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    
+    #[account(
+        init_if_needed,
+        payer = provider,
+        space = LiquidityPool::SIZE,
+        seeds = [LiquidityPool::SEED_PREFIX.as_bytes(), market.key().as_ref()],
+        bump
+    )]
+    pub liquidity_pool: Account<'info, LiquidityPool>,
+    
+    #[account(
+        init_if_needed,
+        payer = provider,
+        seeds = [LiquidityPool::LP_MINT_SEED_PREFIX.as_bytes(), market.key().as_ref()],
+        bump,
+        mint::decimals = mint.decimals,
+        mint::authority = liquidity_pool,
+        mint::token_program = token_program
+    )]
+    pub lp_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        init_if_needed,
+        payer = provider,
+        associated_token::mint = lp_mint,
+        associated_token::authority = provider,
+        associated_token::token_program = token_program
+    )]
+    pub provider_lp_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// The mint of the token staked on this market
+    #[account(address = market.collateral_mint @ PredictionMarketError::InvalidMint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        constraint = provider_token_account.owner == provider.key() @ PredictionMarketError::UnauthorizedAdmin,
+        constraint = provider_token_account.mint == mint.key() @ PredictionMarketError::InvalidMint
+    )]
+    pub provider_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// Required on markets with a book
+    #[account(
+        mut,
+        seeds = [MarketBook::SEED_PREFIX.as_bytes(), market.key().as_ref()],
+        bump = market_book.load()?.bump
+    )]
+    pub market_book: Option<AccountLoader<'info, MarketBook>>,
+    
+    #[account(
+        init_if_needed,
+        payer = provider,
+        space = UserProfile::SIZE,
+        seeds = [UserProfile::SEED_PREFIX.as_bytes(), provider.key().as_ref()],
+        bump
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+    
+    /// Required on allowlist-gated markets
+    pub allowlist_entry: Option<Account<'info, AllowlistEntry>>,
+    
+    /// Required on attestation-gated markets
+    pub credential: Option<Account<'info, Credential>>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
\ No newline at end of file
@@ -0,0 +1,117 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::{House, Market, MarketStatus, UserPosition};
+use crate::errors::PredictionMarketError;
+
+/// Pays a fixed-odds bet out of the house vault: the payout it locked in
+/// if its outcome won, or its stake back if the market was cancelled.
+pub fn claim_fixed_odds_payout(ctx: Context<ClaimFixedOddsPayout>) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let house = &mut ctx.accounts.house;
+    let user_position = &mut ctx.accounts.user_position;
+    
+    // Validations
+    require!(
+        market.has_fixed_odds,
+        PredictionMarketError::InvalidMarketMode
+    );
+    
+    require!(
+        !user_position.claimed,
+        PredictionMarketError::AlreadyClaimed
+    );
+    
+    let amount = match market.status {
+        MarketStatus::Resolved => {
+            require!(
+                market.winner == Some(user_position.outcome),
+                PredictionMarketError::NotWinner
+            );
+            user_position.payout
+        }
+        MarketStatus::Cancelled => user_position.amount,
+        _ => return err!(PredictionMarketError::MarketNotResolved),
+    };
+    
+    if amount > 0 {
+        let mint_key = house.mint;
+        let bump = [house.bump];
+        let house_seeds = &[
+            House::SEED_PREFIX.as_bytes(),
+            mint_key.as_ref(),
+            &bump
+        ];
+        let signer_seeds = &[&house_seeds[..]];
+    
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.vault.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            to: ctx.accounts.user_token_account.to_account_info(),
+            authority: house.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds
+        );
+        token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.mint.decimals)?;
+    }
+    
+    // What is paid no longer needs reserving
+    user_position.claimed = true;
+    let reserve = market.house_reserve.checked_sub(amount)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    house.reserve_for(market, reserve)?;
+    
+    msg!("Fixed-odds payout claimed: {}", amount);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClaimFixedOddsPayout<'info> {
+    pub user: Signer<'info>,
+    
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        mut,
+        seeds = [House::SEED_PREFIX.as_bytes(), market.collateral_mint.as_ref()],
+        bump = house.bump
+    )]
+    pub house: Account<'info, House>,
+    
+    #[account(
+        mut,
+        address = house.vault @ PredictionMarketError::InvalidHouse
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [
+            b"user_position",
+            user.key().as_ref(),
+            market.key().as_ref(),
+            user_position.outcome.to_le_bytes().as_ref()
+        ],
+        bump = user_position.bump,
+        constraint = !user_position.claimed @ PredictionMarketError::AlreadyClaimed,
+        constraint = user_position.user == user.key() @ PredictionMarketError::UnauthorizedAdmin
+    )]
+    pub user_position: Account<'info, UserPosition>,
+    
+    /// The mint of the token being paid out
+    #[account(address = market.collateral_mint @ PredictionMarketError::InvalidMint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        constraint = user_token_account.owner == user.key() @ PredictionMarketError::UnauthorizedAdmin,
+        constraint = user_token_account.mint == mint.key() @ PredictionMarketError::InvalidMint
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
\ No newline at end of file
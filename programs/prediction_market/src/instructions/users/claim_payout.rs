@@ -1,86 +1,64 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Transfer, TokenAccount, Token, Mint};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use crate::state::{Market, Outcome, UserPosition, MarketStatus};
 use crate::errors::PredictionMarketError;
+use super::escrow_payout::EscrowPayout;
 
 pub fn claim_payout(ctx: Context<ClaimPayout>) -> Result<()> {
     let market = &ctx.accounts.market;
     let user_position = &mut ctx.accounts.user_position;
-    let _winner_outcome = &ctx.accounts.outcome;
     
     // Validations
     require!(
-        market.status == MarketStatus::Resolved,
-        PredictionMarketError::MarketNotResolved
+        !market.has_fixed_odds,
+        PredictionMarketError::InvalidMarketMode
     );
     
     require!(
-        !user_position.claimed,
-        PredictionMarketError::AlreadyClaimed
+        market.status == MarketStatus::Resolved,
+        PredictionMarketError::MarketNotResolved
     );
     
     require!(
-        market.winner.unwrap() == user_position.outcome,
-        PredictionMarketError::NotWinner
+        market.unswept_escrows == 0,
+        PredictionMarketError::SettlementPending
     );
     
-    // Calculate payout based on pool and user's share
-    let total_market_pool = market.total_pool;
-    let fee_amount = (total_market_pool * market.fee_percentage as u64) / 100;
-    let distributable_pool = total_market_pool.checked_sub(fee_amount)
-        .ok_or(PredictionMarketError::MathOverflow)?;
-
-    // Calculate user's share based on their proportion of winning bets
-    let total_winning_outcome_staked = ctx.accounts.outcome.total_staked;
-    
-    // Prevent division by zero
     require!(
-        total_winning_outcome_staked > 0,
-        PredictionMarketError::InsufficientLiquidity
+        !user_position.claimed,
+        PredictionMarketError::AlreadyClaimed
     );
     
-    // Calculate payout proportional to user's contribution to winning pool
-    let user_share_numerator = user_position.amount;
-    let user_share_denominator = total_winning_outcome_staked;
-    
-    // Use checked math to avoid overflows
-    let payout = (user_share_numerator as u128)
-        .checked_mul(distributable_pool as u128)
-        .ok_or(PredictionMarketError::MathOverflow)?
-        .checked_div(user_share_denominator as u128)
-        .ok_or(PredictionMarketError::MathOverflow)? as u64;
-    
-    // Transfer tokens from escrow to user
-    let cpi_accounts = Transfer {
-        from: ctx.accounts.escrow_token_account.to_account_info(),
-        to: ctx.accounts.user_token_account.to_account_info(),
-        authority: ctx.accounts.escrow_authority.to_account_info(),
-    };
-    
-    // Create the CPI context with signer seeds for the escrow PDA
-    let bump = [ctx.bumps.escrow_authority];
-    let market_key = market.key();
-    let outcome_id_bytes = ctx.accounts.outcome.id.to_le_bytes();
-    
-    let escrow_seeds = &[
-        b"escrow",
-        market_key.as_ref(),
-        outcome_id_bytes.as_ref(),
-        &bump
-    ];
-    
-    let signer_seeds = &[&escrow_seeds[..]];
-    let cpi_ctx = CpiContext::new_with_signer(
-        ctx.accounts.token_program.to_account_info(),
-        cpi_accounts,
-        signer_seeds
+    require!(
+        market.winner == Some(user_position.outcome),
+        PredictionMarketError::NotWinner
     );
     
-    // Execute the transfer
-    token::transfer(cpi_ctx, payout)?;
+    // Calculate payout proportional to user's contribution to winning pool,
+    // plus their fee tier rebate. Losing stakes were swept into this escrow
+    // when the market was settled, and the last claimer takes whatever rounding
+    // left behind.
+    let payout = market.claim_amount(user_position, &ctx.accounts.outcome)?;
+    
+    // Send tokens from escrow to user, unwrapping SOL on native markets
+    EscrowPayout {
+        market,
+        outcome_id: ctx.accounts.outcome.id,
+        user: &ctx.accounts.user,
+        mint: &ctx.accounts.mint,
+        user_token_account: &ctx.accounts.user_token_account,
+        unwrap_account: &ctx.accounts.unwrap_account,
+        escrow_token_account: &ctx.accounts.escrow_token_account,
+        escrow_authority: &ctx.accounts.escrow_authority,
+        escrow_bump: ctx.bumps.escrow_authority,
+        token_program: &ctx.accounts.token_program,
+        system_program: &ctx.accounts.system_program,
+    }
+    .pay(payout)?;
     
     // Mark position as claimed
     user_position.claimed = true;
+    ctx.accounts.outcome.record_claim(payout)?;
     
     msg!("Payout claimed: {}", payout);
     
@@ -98,7 +76,7 @@ pub struct ClaimPayout<'info> {
     pub market: Account<'info, Market>,
     
     #[account(
-        constraint = outcome.id == market.winner.unwrap() @ PredictionMarketError::OutcomeNotFound,
+        mut,
         seeds = [b"outcome", market.key().as_ref(), outcome.id.to_le_bytes().as_ref()],
         bump = outcome.bump
     )]
@@ -119,22 +97,28 @@ pub struct ClaimPayout<'info> {
     pub user_position: Account<'info, UserPosition>,
     
     /// The mint of the token being used for payouts
-    pub mint: Account<'info, Mint>,
+    #[account(address = market.collateral_mint @ PredictionMarketError::InvalidMint)]
+    pub mint: InterfaceAccount<'info, Mint>,
     
+    /// Required on token markets
     #[account(
         mut,
         constraint = user_token_account.owner == user.key() @ PredictionMarketError::UnauthorizedAdmin,
         constraint = user_token_account.mint == mint.key() @ PredictionMarketError::InvalidMint
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub user_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// CHECK: Temporary wSOL account at `["unwrap", user]`, required on native
+    /// SOL markets; created and closed within the instruction
+    #[account(mut)]
+    pub unwrap_account: Option<UncheckedAccount<'info>>,
     
     #[account(
         mut,
-        seeds = [b"escrow", market.key().as_ref(), outcome.id.to_le_bytes().as_ref()],
-        bump,
+        address = outcome.escrow_pubkey,
         constraint = escrow_token_account.mint == mint.key() @ PredictionMarketError::InvalidMint
     )]
-    pub escrow_token_account: Account<'info, TokenAccount>,
+    pub escrow_token_account: InterfaceAccount<'info, TokenAccount>,
     
     /// CHECK: This is the PDA that has authority over the escrow
     #[account(
@@ -143,6 +127,6 @@ pub struct ClaimPayout<'info> {
     )]
     pub escrow_authority: UncheckedAccount<'info>,
     
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
\ No newline at end of file
@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::{Market, MarketStatus, Referrer};
+use crate::errors::PredictionMarketError;
+use super::escrow_payout::EscrowPayout;
+
+/// Pays a referrer their share of a resolved market's referral fees, out of
+/// the winning escrow, and closes their referrer account.
+pub fn claim_referral_rewards(ctx: Context<ClaimReferralRewards>) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let referrer_account = &ctx.accounts.referrer_account;
+    
+    // Validations
+    require!(
+        market.status == MarketStatus::Resolved,
+        PredictionMarketError::MarketNotResolved
+    );
+    
+    require!(
+        market.unswept_escrows == 0,
+        PredictionMarketError::SettlementPending
+    );
+    
+    let winner = market.winner.ok_or(PredictionMarketError::MarketNotResolved)?;
+    let reward = market.referral_claim_amount(referrer_account.volume)?;
+    
+    if reward > 0 {
+        EscrowPayout {
+            market,
+            outcome_id: winner,
+            user: &ctx.accounts.referrer,
+            mint: &ctx.accounts.mint,
+            user_token_account: &ctx.accounts.referrer_token_account,
+            unwrap_account: &ctx.accounts.unwrap_account,
+            escrow_token_account: &ctx.accounts.escrow_token_account,
+            escrow_authority: &ctx.accounts.escrow_authority,
+            escrow_bump: ctx.bumps.escrow_authority,
+            token_program: &ctx.accounts.token_program,
+            system_program: &ctx.accounts.system_program,
+        }
+        .pay(reward)?;
+    }
+    
+    market.referral_fees_claimed = market.referral_fees_claimed.checked_add(reward)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    market.referrers_remaining = market.referrers_remaining.checked_sub(1)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    
+    msg!("Referral rewards claimed: {}", reward);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClaimReferralRewards<'info> {
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = market.status == MarketStatus::Resolved @ PredictionMarketError::MarketNotResolved
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        mut,
+        close = referrer,
+        seeds = [Referrer::SEED_PREFIX.as_bytes(), market.key().as_ref(), referrer.key().as_ref()],
+        bump = referrer_account.bump
+    )]
+    pub referrer_account: Account<'info, Referrer>,
+    
+    #[account(address = market.collateral_mint @ PredictionMarketError::InvalidMint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    /// Required on token markets
+    #[account(
+        mut,
+        constraint = referrer_token_account.owner == referrer.key() @ PredictionMarketError::UnauthorizedAdmin,
+        constraint = referrer_token_account.mint == mint.key() @ PredictionMarketError::InvalidMint
+    )]
+    pub referrer_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// CHECK: Temporary wSOL account at `["unwrap", referrer]`, required on
+    /// native SOL markets; created and closed within the instruction
+    #[account(mut)]
+    pub unwrap_account: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: This is the PDA that has authority over the winning escrow
+    #[account(
+        seeds = [b"escrow", market.key().as_ref(), market.winner.unwrap_or_default().to_le_bytes().as_ref()],
+        bump
+    )]
+    pub escrow_authority: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = escrow_authority,
+        associated_token::token_program = token_program,
+    )]
+    pub escrow_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
\ No newline at end of file
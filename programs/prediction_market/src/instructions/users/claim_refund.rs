@@ -0,0 +1,119 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::{Market, Outcome, UserPosition, MarketStatus};
+use crate::errors::PredictionMarketError;
+use super::escrow_payout::EscrowPayout;
+
+pub fn claim_refund(ctx: Context<ClaimRefund>) -> Result<()> {
+    let market = &ctx.accounts.market;
+    let user_position = &mut ctx.accounts.user_position;
+    
+    // Validations
+    require!(
+        !market.has_fixed_odds,
+        PredictionMarketError::InvalidMarketMode
+    );
+    
+    require!(
+        market.status == MarketStatus::Cancelled,
+        PredictionMarketError::MarketNotCancelled
+    );
+    
+    require!(
+        !user_position.claimed,
+        PredictionMarketError::AlreadyClaimed
+    );
+    
+    // A cancelled market returns every stake in full, with no fee
+    let refund = user_position.amount;
+    
+    // Send tokens from escrow to user, unwrapping SOL on native markets
+    EscrowPayout {
+        market,
+        outcome_id: ctx.accounts.outcome.id,
+        user: &ctx.accounts.user,
+        mint: &ctx.accounts.mint,
+        user_token_account: &ctx.accounts.user_token_account,
+        unwrap_account: &ctx.accounts.unwrap_account,
+        escrow_token_account: &ctx.accounts.escrow_token_account,
+        escrow_authority: &ctx.accounts.escrow_authority,
+        escrow_bump: ctx.bumps.escrow_authority,
+        token_program: &ctx.accounts.token_program,
+        system_program: &ctx.accounts.system_program,
+    }
+    .pay(refund)?;
+    
+    // Mark position as claimed
+    user_position.claimed = true;
+    ctx.accounts.outcome.record_claim(refund)?;
+    
+    msg!("Refund claimed: {}", refund);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClaimRefund<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    #[account(
+        constraint = market.status == MarketStatus::Cancelled @ PredictionMarketError::MarketNotCancelled
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        mut,
+        seeds = [b"outcome", market.key().as_ref(), outcome.id.to_le_bytes().as_ref()],
+        bump = outcome.bump
+    )]
+    pub outcome: Account<'info, Outcome>,
+    
+    #[account(
+        mut,
+        seeds = [
+            b"user_position",
+            user.key().as_ref(),
+            market.key().as_ref(),
+            outcome.id.to_le_bytes().as_ref()
+        ],
+        bump = user_position.bump,
+        constraint = !user_position.claimed @ PredictionMarketError::AlreadyClaimed,
+        constraint = user_position.user == user.key() @ PredictionMarketError::UnauthorizedAdmin
+    )]
+    pub user_position: Account<'info, UserPosition>,
+    
+    /// The mint of the token being refunded
+    #[account(address = market.collateral_mint @ PredictionMarketError::InvalidMint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    /// Required on token markets
+    #[account(
+        mut,
+        constraint = user_token_account.owner == user.key() @ PredictionMarketError::UnauthorizedAdmin,
+        constraint = user_token_account.mint == mint.key() @ PredictionMarketError::InvalidMint
+    )]
+    pub user_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// CHECK: Temporary wSOL account at `["unwrap", user]`, required on native
+    /// SOL markets; created and closed within the instruction
+    #[account(mut)]
+    pub unwrap_account: Option<UncheckedAccount<'info>>,
+    
+    #[account(
+        mut,
+        address = outcome.escrow_pubkey,
+        constraint = escrow_token_account.mint == mint.key() @ PredictionMarketError::InvalidMint
+    )]
+    pub escrow_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// CHECK: This is the PDA that has authority over the escrow
+    #[account(
+        seeds = [b"escrow", market.key().as_ref(), outcome.id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub escrow_authority: UncheckedAccount<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
\ No newline at end of file
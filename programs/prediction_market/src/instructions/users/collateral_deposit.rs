@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use anchor_spl::token_interface::{self, Mint, SyncNative, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::CollateralMode;
+use crate::errors::PredictionMarketError;
+
+/// Accounts for taking a user's collateral, shared by stakes and fees paid
+/// at bet time.
+pub struct CollateralDeposit<'a, 'info> {
+    pub collateral_mode: CollateralMode,
+    pub user: &'a Signer<'info>,
+    pub mint: &'a InterfaceAccount<'info, Mint>,
+    pub user_token_account: &'a Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: &'a Interface<'info, TokenInterface>,
+    pub system_program: &'a Program<'info, System>,
+}
+
+impl<'a, 'info> CollateralDeposit<'a, 'info> {
+    /// Moves `amount` into `to`, a token account in the market's mint: from
+    /// the user's token account on token markets, or as lamports wrapped
+    /// straight into `to` on native SOL markets.
+    ///
+    /// Returns the amount to credit the user with. Transfer-fee mints
+    /// withhold part of a token transfer, so that is what `to` actually
+    /// received. Wrapping syncs every lamport `to` holds, including any sent
+    /// to it directly, so only the lamports the user sent are credited.
+    pub fn deposit(&self, to: AccountInfo<'info>, amount: u64) -> Result<u64> {
+        let cpi_program = self.token_program.to_account_info();
+        match self.collateral_mode {
+            CollateralMode::Token => {
+                let user_token_account = self.user_token_account
+                    .as_ref()
+                    .ok_or(PredictionMarketError::InvalidCollateralMode)?;
+                let balance_before = token_balance(&to)?;
+                let cpi_accounts = TransferChecked {
+                    from: user_token_account.to_account_info(),
+                    mint: self.mint.to_account_info(),
+                    to: to.clone(),
+                    authority: self.user.to_account_info(),
+                };
+                let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+                token_interface::transfer_checked(cpi_ctx, amount, self.mint.decimals)?;
+    
+                let received = token_balance(&to)?
+                    .checked_sub(balance_before)
+                    .ok_or(PredictionMarketError::MathOverflow)?;
+                Ok(received)
+            }
+            CollateralMode::NativeSol => {
+                let cpi_accounts = Transfer {
+                    from: self.user.to_account_info(),
+                    to: to.clone(),
+                };
+                let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), cpi_accounts);
+                system_program::transfer(cpi_ctx, amount)?;
+    
+                let cpi_accounts = SyncNative { account: to };
+                token_interface::sync_native(CpiContext::new(cpi_program, cpi_accounts))?;
+                Ok(amount)
+            }
+        }
+    }
+}
+
+fn token_balance(account: &AccountInfo) -> Result<u64> {
+    let data = account.try_borrow_data()?;
+    Ok(TokenAccount::try_deserialize(&mut &data[..])?.amount)
+}
\ No newline at end of file
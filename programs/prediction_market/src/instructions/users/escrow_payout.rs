@@ -0,0 +1,157 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::system_program::{self, Allocate, Assign, CreateAccount, Transfer};
+use anchor_spl::token::spl_token;
+use anchor_spl::token_interface::{
+    self, CloseAccount, InitializeAccount3, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+use crate::state::{CollateralMode, Market};
+use crate::errors::PredictionMarketError;
+
+/// Accounts for paying a user out of an outcome escrow, shared by payouts
+/// and refunds.
+pub struct EscrowPayout<'a, 'info> {
+    pub market: &'a Account<'info, Market>,
+    pub outcome_id: u8,
+    pub user: &'a Signer<'info>,
+    pub mint: &'a InterfaceAccount<'info, Mint>,
+    pub user_token_account: &'a Option<InterfaceAccount<'info, TokenAccount>>,
+    pub unwrap_account: &'a Option<UncheckedAccount<'info>>,
+    pub escrow_token_account: &'a InterfaceAccount<'info, TokenAccount>,
+    pub escrow_authority: &'a UncheckedAccount<'info>,
+    pub escrow_bump: u8,
+    pub token_program: &'a Interface<'info, TokenInterface>,
+    pub system_program: &'a Program<'info, System>,
+}
+
+impl<'a, 'info> EscrowPayout<'a, 'info> {
+    /// Sends `amount` out of the escrow: to the user's token account on token
+    /// markets, or unwrapped into the user's wallet on native SOL markets.
+    pub fn pay(&self, amount: u64) -> Result<()> {
+        let market_key = self.market.key();
+        let outcome_id_bytes = self.outcome_id.to_le_bytes();
+        let bump = [self.escrow_bump];
+        let escrow_seeds = &[
+            b"escrow",
+            market_key.as_ref(),
+            outcome_id_bytes.as_ref(),
+            &bump
+        ];
+        let signer_seeds = &[&escrow_seeds[..]];
+    
+        match self.market.collateral_mode {
+            CollateralMode::Token => {
+                let user_token_account = self
+                    .user_token_account
+                    .as_ref()
+                    .ok_or(PredictionMarketError::InvalidCollateralMode)?;
+                self.transfer_from_escrow(user_token_account.to_account_info(), amount, signer_seeds)
+            }
+            CollateralMode::NativeSol => {
+                let unwrap_account = self
+                    .unwrap_account
+                    .as_ref()
+                    .ok_or(PredictionMarketError::InvalidCollateralMode)?;
+                self.unwrap(unwrap_account.to_account_info(), amount, signer_seeds)
+            }
+        }
+    }
+    
+    fn transfer_from_escrow(&self, to: AccountInfo<'info>, amount: u64, signer_seeds: &[&[&[u8]]]) -> Result<()> {
+        let cpi_accounts = TransferChecked {
+            from: self.escrow_token_account.to_account_info(),
+            mint: self.mint.to_account_info(),
+            to,
+            authority: self.escrow_authority.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds
+        );
+        token_interface::transfer_checked(cpi_ctx, amount, self.mint.decimals)
+    }
+    
+    /// wSOL can only be unwrapped by closing a token account, and the escrow
+    /// must stay open for other claimers. The payout goes through a temporary
+    /// wSOL account at `["unwrap", user]` that is closed into the user's
+    /// wallet within this instruction.
+    fn unwrap(&self, unwrap_account: AccountInfo<'info>, amount: u64, signer_seeds: &[&[&[u8]]]) -> Result<()> {
+        let user_key = self.user.key();
+        let (expected, unwrap_bump) = Pubkey::find_program_address(
+            &[b"unwrap", user_key.as_ref()],
+            &crate::ID,
+        );
+        require_keys_eq!(
+            unwrap_account.key(),
+            expected,
+            PredictionMarketError::InvalidCollateralMode
+        );
+    
+        let unwrap_bump = [unwrap_bump];
+        let unwrap_seeds = &[b"unwrap".as_ref(), user_key.as_ref(), &unwrap_bump];
+        let unwrap_signer = &[&unwrap_seeds[..]];
+    
+        let space = spl_token::state::Account::LEN;
+        let rent = Rent::get()?.minimum_balance(space);
+        let system = self.system_program.to_account_info();
+        let owner = self.token_program.key();
+    
+        // Anyone can send lamports to the address, which would make
+        // create_account fail, so fall back to allocate and assign
+        let current_lamports = unwrap_account.lamports();
+        if current_lamports == 0 {
+            let cpi_accounts = CreateAccount {
+                from: self.user.to_account_info(),
+                to: unwrap_account.clone(),
+            };
+            system_program::create_account(
+                CpiContext::new_with_signer(system.clone(), cpi_accounts, unwrap_signer),
+                rent,
+                space as u64,
+                &owner,
+            )?;
+        } else {
+            let top_up = rent.saturating_sub(current_lamports);
+            if top_up > 0 {
+                let cpi_accounts = Transfer {
+                    from: self.user.to_account_info(),
+                    to: unwrap_account.clone(),
+                };
+                system_program::transfer(CpiContext::new(system.clone(), cpi_accounts), top_up)?;
+            }
+            let cpi_accounts = Allocate { account_to_allocate: unwrap_account.clone() };
+            system_program::allocate(
+                CpiContext::new_with_signer(system.clone(), cpi_accounts, unwrap_signer),
+                space as u64,
+            )?;
+            let cpi_accounts = Assign { account_to_assign: unwrap_account.clone() };
+            system_program::assign(
+                CpiContext::new_with_signer(system, cpi_accounts, unwrap_signer),
+                &owner,
+            )?;
+        }
+    
+        // The escrow authority owns the temporary account, so one signer covers
+        // the transfer in and the close out
+        let cpi_accounts = InitializeAccount3 {
+            account: unwrap_account.clone(),
+            mint: self.mint.to_account_info(),
+            authority: self.escrow_authority.to_account_info(),
+        };
+        token_interface::initialize_account3(CpiContext::new(self.token_program.to_account_info(), cpi_accounts))?;
+    
+        self.transfer_from_escrow(unwrap_account.clone(), amount, signer_seeds)?;
+    
+        let cpi_accounts = CloseAccount {
+            account: unwrap_account,
+            destination: self.user.to_account_info(),
+            authority: self.escrow_authority.to_account_info(),
+        };
+        token_interface::close_account(CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds
+        ))
+    }
+}
\ No newline at end of file
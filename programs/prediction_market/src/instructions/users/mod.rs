@@ -1,5 +1,27 @@
+mod escrow_payout;
+mod collateral_deposit;
 pub mod place_bet;
 pub mod claim_payout;
+pub mod claim_refund;
+pub mod set_self_exclusion;
+pub mod set_deposit_limits;
+pub mod claim_referral_rewards;
+pub mod refresh_odds;
+pub mod place_parlay;
+pub mod settle_parlay;
+pub mod add_liquidity;
+pub mod remove_liquidity;
+pub mod claim_fixed_odds_payout;
 
 pub use place_bet::*;
-pub use claim_payout::*;
\ No newline at end of file
+pub use claim_payout::*;
+pub use claim_refund::*;
+pub use set_self_exclusion::*;
+pub use set_deposit_limits::*;
+pub use claim_referral_rewards::*;
+pub use refresh_odds::*;
+pub use place_parlay::*;
+pub use settle_parlay::*;
+pub use add_liquidity::*;
+pub use remove_liquidity::*;
+pub use claim_fixed_odds_payout::*;
\ No newline at end of file
@@ -1,8 +1,16 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Transfer, TokenAccount, Token, Mint};
-use crate::state::{Market, Outcome, UserPosition, MarketStatus};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::{
+    AllowlistEntry, CollateralConfig, Credential, FeeModel, House, Market, MarketBook, MarketStake, Outcome, PlatformConfig,
+    Referrer, UserPosition, UserProfile, MarketStatus, BPS_DENOMINATOR,
+};
 use crate::errors::PredictionMarketError;
+use super::collateral_deposit::CollateralDeposit;
 
+/// Places a bet on `outcome`. On a parimutuel market the stake joins the
+/// outcome's escrow. On a fixed-odds market it goes to the house vault, and
+/// the bet locks in `amount * odds / 10000` at the outcome's posted odds,
+/// which the house must be able to cover.
 pub fn place_bet(ctx: Context<PlaceBet>, amount: u64) -> Result<()> {
     let market = &mut ctx.accounts.market;
     let outcome = &mut ctx.accounts.outcome;
@@ -25,44 +33,202 @@ pub fn place_bet(ctx: Context<PlaceBet>, amount: u64) -> Result<()> {
         PredictionMarketError::InvalidBetAmount
     );
     
-    // Transfer tokens from user to outcome's escrow account
-    let cpi_accounts = Transfer {
-        from: ctx.accounts.user_token_account.to_account_info(),
-        to: ctx.accounts.escrow_token_account.to_account_info(),
-        authority: ctx.accounts.user.to_account_info(),
+    require!(
+        !outcome.eliminated,
+        PredictionMarketError::OutcomeEliminated
+    );
+    
+    // A fee charged at bet time goes wholly to the treasury, leaving no
+    // referral share to pay out
+    require!(
+        ctx.accounts.referrer.is_none() || market.fee_model != FeeModel::AtBet,
+        PredictionMarketError::FeeChargedAtBet
+    );
+    
+    // Gated markets need the user's allowlist entry or credential
+    market.access_gate.check(
+        ctx.accounts.user.key(),
+        ctx.accounts.allowlist_entry.as_deref(),
+        ctx.accounts.credential.as_deref(),
+        clock.unix_timestamp,
+    )?;
+    
+    // Limits are checked against the amount sent, which is never less than
+    // what a transfer-fee mint credits. The position limit covers the
+    // user's stakes on every outcome of the market.
+    let market_stake = &mut ctx.accounts.market_stake;
+    market_stake.version = MarketStake::VERSION;
+    market_stake.market = market.key();
+    market_stake.user = ctx.accounts.user.key();
+    market_stake.bump = ctx.bumps.market_stake;
+    market_stake.record_bet(amount)?;
+    let pool = market.total_pool.checked_add(amount)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    market.bet_limits.check(amount, market_stake.amount, pool)?;
+    
+    // Self-exclusion and deposit limits span every market the user bets in,
+    // and so does the volume that sets the user's fee tier
+    let user_profile = &mut ctx.accounts.user_profile;
+    user_profile.version = UserProfile::VERSION;
+    user_profile.user = ctx.accounts.user.key();
+    user_profile.bump = ctx.bumps.user_profile;
+    let fee_discount_bps = ctx.accounts.platform_config.fee_discount_bps(user_profile.total_volume);
+    user_profile.record_bet(amount, clock.unix_timestamp)?;
+    
+    // Markets charging their fee at bet time send it to the treasury, and
+    // only the rest is staked
+    let collateral = CollateralDeposit {
+        collateral_mode: market.collateral_mode,
+        user: &ctx.accounts.user,
+        mint: &ctx.accounts.mint,
+        user_token_account: &ctx.accounts.user_token_account,
+        token_program: &ctx.accounts.token_program,
+        system_program: &ctx.accounts.system_program,
     };
+    let bet_fee = market.bet_fee_amount(amount, fee_discount_bps)?;
+    if bet_fee > 0 {
+        let treasury_token_account = match (&ctx.accounts.collateral_config, &ctx.accounts.treasury_token_account) {
+            (Some(collateral_config), Some(treasury_token_account))
+                if collateral_config.treasury == treasury_token_account.key() => treasury_token_account,
+            _ => return err!(PredictionMarketError::InvalidTreasury),
+        };
+        collateral.deposit(treasury_token_account.to_account_info(), bet_fee)?;
+    }
+    let stake = amount.checked_sub(bet_fee)
+        .ok_or(PredictionMarketError::MathOverflow)?;
     
-    let cpi_program = ctx.accounts.token_program.to_account_info();
-    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-    token::transfer(cpi_ctx, amount)?;
+    // Move the stake into the outcome's escrow account, or the house vault
+    // on fixed-odds markets
+    let stake_account = if market.has_fixed_odds {
+        ctx.accounts.house_vault
+            .as_mut()
+            .filter(|house_vault| ctx.accounts.house.as_ref().is_some_and(|house| house.vault == house_vault.key()))
+            .ok_or(PredictionMarketError::InvalidHouse)?
+    } else {
+        &mut ctx.accounts.escrow_token_account
+    };
+    let received = collateral.deposit(stake_account.to_account_info(), stake)?;
+    stake_account.reload()?;
+    let stake_balance = stake_account.amount;
+    require!(
+        received > 0,
+        PredictionMarketError::InvalidBetAmount
+    );
+    
+    // The discount is rebated out of the platform fee if this stake wins,
+    // unless it was already taken off a fee charged at bet time
+    let discounted = match market.fee_model {
+        FeeModel::AtBet => 0,
+        _ => (received as u128 * fee_discount_bps as u128 / BPS_DENOMINATOR as u128) as u64,
+    };
     
     // Calculate shares based on current odds
     // For simplicity, we'll use a 1:1 ratio initially
-    let shares = amount;
+    let shares = received;
+    
+    // Fixed-odds bets lock in their payout, and the house reserves enough
+    // to pay whichever outcome it would owe most on
+    if market.has_fixed_odds {
+        require!(
+            outcome.odds > BPS_DENOMINATOR,
+            PredictionMarketError::InvalidOdds
+        );
+    
+        let payout = (received as u128)
+            .checked_mul(outcome.odds as u128)
+            .ok_or(PredictionMarketError::MathOverflow)?
+            / BPS_DENOMINATOR as u128;
+        let payout = u64::try_from(payout).map_err(|_| PredictionMarketError::MathOverflow)?;
+        outcome.liability = outcome.liability.checked_add(payout)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        require!(
+            market.max_liability == 0 || outcome.liability <= market.max_liability,
+            PredictionMarketError::LiabilityLimitExceeded
+        );
+    
+        let reserve = market.house_reserve.max(outcome.liability);
+        let house = ctx.accounts.house
+            .as_mut()
+            .ok_or(PredictionMarketError::InvalidHouse)?;
+        house.reserve_for(market, reserve)?;
+        require!(
+            house.liability <= stake_balance,
+            PredictionMarketError::InsufficientLiquidity
+        );
+        user_position.payout = user_position.payout.checked_add(payout)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+    }
+    
+    // A fresh position is one more claimant on this outcome
+    if user_position.amount == 0 {
+        outcome.claimants_remaining = outcome.claimants_remaining.checked_add(1)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+    }
     
     // Initialize or update user position
+    user_position.version = UserPosition::VERSION;
     user_position.user = ctx.accounts.user.key();
     user_position.market = market.key();
     user_position.outcome = outcome.id;
-    user_position.amount = user_position.amount.checked_add(amount)
+    user_position.amount = user_position.amount.checked_add(received)
         .ok_or(PredictionMarketError::MathOverflow)?;
     user_position.shares = user_position.shares.checked_add(shares)
         .ok_or(PredictionMarketError::MathOverflow)?;
+    user_position.discounted_amount = user_position.discounted_amount.checked_add(discounted)
+        .ok_or(PredictionMarketError::MathOverflow)?;
     user_position.timestamp = clock.unix_timestamp;
     user_position.claimed = false;
     user_position.bump = ctx.bumps.user_position;
     
     // Update outcome and market stats
-    outcome.total_staked = outcome.total_staked.checked_add(amount)
+    outcome.total_staked = outcome.total_staked.checked_add(received)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    outcome.discounted_stake = outcome.discounted_stake.checked_add(discounted)
         .ok_or(PredictionMarketError::MathOverflow)?;
-    market.total_pool = market.total_pool.checked_add(amount)
+    market.total_pool = market.total_pool.checked_add(received)
         .ok_or(PredictionMarketError::MathOverflow)?;
     
+    // Markets with a book reprice every outcome in it
+    if market.has_book {
+        let market_book = ctx.accounts.market_book
+            .as_ref()
+            .ok_or(PredictionMarketError::InvalidMarketBook)?;
+        market_book.load_mut()?.record_bet(market, outcome.id, received)?;
+    }
+    
+    // Attribute the bet to its referrer, who must be someone else
+    match (&ctx.accounts.referrer, &mut ctx.accounts.referrer_account) {
+        (Some(referrer), Some(referrer_account)) => {
+            require_keys_neq!(
+                referrer.key(),
+                ctx.accounts.user.key(),
+                PredictionMarketError::InvalidReferrer
+            );
+    
+            // A fresh referrer is one more claimant on the referral fees
+            if referrer_account.volume == 0 {
+                market.referrers_remaining = market.referrers_remaining.checked_add(1)
+                    .ok_or(PredictionMarketError::MathOverflow)?;
+            }
+    
+            referrer_account.version = Referrer::VERSION;
+            referrer_account.market = market.key();
+            referrer_account.referrer = referrer.key();
+            referrer_account.bump = ctx.bumps.referrer_account.ok_or(PredictionMarketError::InvalidReferrer)?;
+            referrer_account.record_volume(received)?;
+            market.referred_volume = market.referred_volume.checked_add(received)
+                .ok_or(PredictionMarketError::MathOverflow)?;
+        }
+        (None, None) => {}
+        _ => return err!(PredictionMarketError::InvalidReferrer),
+    }
+    
     // Update odds (simplified version - can be more complex in reality)
     // In a real implementation, this would adjust based on relative liquidity
     
     msg!("Bet placed on outcome: {}", outcome.name);
-    msg!("Amount: {}", amount);
+    msg!("Amount: {}", received);
+    msg!("Fee: {}", bet_fee);
     
     Ok(())
 }
@@ -78,6 +244,12 @@ pub struct PlaceBet<'info> {
     )]
     pub market: Account<'info, Market>,
     
+    #[account(
+        seeds = [PlatformConfig::SEED_PREFIX.as_bytes()],
+        bump = platform_config.bump
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    
     #[account(
         mut,
         seeds = [b"outcome", market.key().as_ref(), outcome.id.to_le_bytes().as_ref()],
@@ -88,15 +260,7 @@ pub struct PlaceBet<'info> {
     #[account(
         init_if_needed,
         payer = user,
-        space = 8 +   // Discriminator
-               32 +   // user
-               32 +   // market
-               1 +    // outcome
-               8 +    // amount
-               8 +    // shares
-               8 +    // timestamp
-               1 +    // claimed
-               1,     // bump
+        space = UserPosition::SIZE,
         seeds = [
             b"user_position",
             user.key().as_ref(),
@@ -107,24 +271,96 @@ pub struct PlaceBet<'info> {
     )]
     pub user_position: Account<'info, UserPosition>,
     
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = MarketStake::SIZE,
+        seeds = [MarketStake::SEED_PREFIX.as_bytes(), market.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub market_stake: Account<'info, MarketStake>,
+    
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserProfile::SIZE,
+        seeds = [UserProfile::SEED_PREFIX.as_bytes(), user.key().as_ref()],
+        bump
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+    
+    /// Required on allowlist-gated markets
+    pub allowlist_entry: Option<Account<'info, AllowlistEntry>>,
+    
+    /// Required on attestation-gated markets
+    pub credential: Option<Account<'info, Credential>>,
+    
+    /// CHECK: Wallet credited with referring this bet, if any
+    pub referrer: Option<UncheckedAccount<'info>>,
+    
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = Referrer::SIZE,
+        seeds = [
+            Referrer::SEED_PREFIX.as_bytes(),
+            market.key().as_ref(),
+            referrer.as_ref().ok_or(PredictionMarketError::InvalidReferrer)?.key().as_ref()
+        ],
+        bump
+    )]
+    pub referrer_account: Option<Account<'info, Referrer>>,
+    
     /// The mint of the token being bet
-    pub mint: Account<'info, Mint>,
+    #[account(address = market.collateral_mint @ PredictionMarketError::InvalidMint)]
+    pub mint: InterfaceAccount<'info, Mint>,
     
+    /// Required on token markets; native SOL markets take lamports from `user`
     #[account(
         mut,
         constraint = user_token_account.owner == user.key() @ PredictionMarketError::UnauthorizedAdmin,
         constraint = user_token_account.mint == mint.key() @ PredictionMarketError::InvalidMint
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub user_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Required on markets charging their fee at bet time
+    #[account(
+        seeds = [CollateralConfig::SEED_PREFIX.as_bytes(), market.collateral_mint.as_ref()],
+        bump = collateral_config.bump
+    )]
+    pub collateral_config: Option<Account<'info, CollateralConfig>>,
+    
+    /// Required on markets charging their fee at bet time
+    #[account(mut)]
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Required on markets with a book
+    #[account(
+        mut,
+        seeds = [MarketBook::SEED_PREFIX.as_bytes(), market.key().as_ref()],
+        bump = market_book.load()?.bump
+    )]
+    pub market_book: Option<AccountLoader<'info, MarketBook>>,
+    
+    /// The house in the market's collateral, required on fixed-odds markets
+    #[account(
+        mut,
+        seeds = [House::SEED_PREFIX.as_bytes(), market.collateral_mint.as_ref()],
+        bump = house.bump
+    )]
+    pub house: Option<Account<'info, House>>,
+    
+    /// The house's vault, required on fixed-odds markets
+    #[account(mut)]
+    pub house_vault: Option<InterfaceAccount<'info, TokenAccount>>,
     
     #[account(
         mut,
-        seeds = [b"escrow", market.key().as_ref(), outcome.id.to_le_bytes().as_ref()],
-        bump,
+        address = outcome.escrow_pubkey,
         constraint = escrow_token_account.mint == mint.key() @ PredictionMarketError::InvalidMint
     )]
-    pub escrow_token_account: Account<'info, TokenAccount>,
+    pub escrow_token_account: InterfaceAccount<'info, TokenAccount>,
     
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
\ No newline at end of file
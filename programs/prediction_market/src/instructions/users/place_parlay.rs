@@ -0,0 +1,178 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::{
+    AccessGate, House, LegResult, Market, MarketStatus, Outcome, Parlay, ParlayLeg, ParlayStatus, UserProfile,
+    BPS_DENOMINATOR,
+};
+use crate::errors::PredictionMarketError;
+
+/// Places a parlay against the house, at each leg's current odds up to the
+/// house's cap. Every leg is a `(market, outcome)` pair passed in
+/// `remaining_accounts`, on a distinct open market in the house's collateral
+/// with at least its minimum pool, at odds above even. The whole payout is
+/// reserved out of the house's liquidity until it settles.
+pub fn place_parlay<'info>(
+    ctx: Context<'_, '_, 'info, 'info, PlaceParlay<'info>>,
+    parlay_id: u64,
+    stake: u64,
+) -> Result<()> {
+    let house = &mut ctx.accounts.house;
+    let clock = Clock::get()?;
+    
+    // Validations
+    require!(
+        stake > 0,
+        PredictionMarketError::InvalidBetAmount
+    );
+    
+    require!(
+        ctx.remaining_accounts.len().is_multiple_of(2),
+        PredictionMarketError::InvalidParlayLeg
+    );
+    
+    let mut legs: Vec<ParlayLeg> = Vec::new();
+    for accounts in ctx.remaining_accounts.chunks(2) {
+        let market = Account::<Market>::try_from(&accounts[0])?;
+        let outcome = Account::<Outcome>::try_from(&accounts[1])?;
+        require!(
+            market.status == MarketStatus::Active,
+            PredictionMarketError::MarketNotActive
+        );
+        require!(
+            clock.unix_timestamp < market.end_time,
+            PredictionMarketError::MarketAlreadyClosed
+        );
+        require!(
+            outcome.market == market.key() && market.outcomes.contains(&outcome.id),
+            PredictionMarketError::OutcomeNotFound
+        );
+        require!(
+            !outcome.eliminated,
+            PredictionMarketError::OutcomeEliminated
+        );
+    
+        // The house can't check gates, and odds of one or less can only
+        // lower the payout. Fixed-odds legs are taken at their posted odds,
+        // and every leg at no more than the house's cap.
+        let quoted = if market.has_fixed_odds { outcome.odds } else { market.odds(outcome.total_staked)? };
+        let odds = house.leg_limits.leg_odds(&market, quoted)?;
+        require!(
+            market.collateral_mint == house.mint
+                && market.access_gate == AccessGate::Open
+                && odds > BPS_DENOMINATOR
+                && legs.iter().all(|leg| leg.market != market.key()),
+            PredictionMarketError::InvalidParlayLeg
+        );
+    
+        legs.push(ParlayLeg {
+            market: market.key(),
+            outcome_id: outcome.id,
+            odds,
+            result: LegResult::Pending,
+        });
+    }
+    
+    // Self-exclusion and deposit limits cover parlays too
+    let user_profile = &mut ctx.accounts.user_profile;
+    user_profile.version = UserProfile::VERSION;
+    user_profile.user = ctx.accounts.user.key();
+    user_profile.bump = ctx.bumps.user_profile;
+    user_profile.record_bet(stake, clock.unix_timestamp)?;
+    
+    // Move the stake into the house vault
+    let vault_balance_before = ctx.accounts.vault.amount;
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.user_token_account.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
+        to: ctx.accounts.vault.to_account_info(),
+        authority: ctx.accounts.user.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token_interface::transfer_checked(cpi_ctx, stake, ctx.accounts.mint.decimals)?;
+    
+    // Transfer-fee mints withhold part of the transfer, so stake only what
+    // the vault actually received
+    ctx.accounts.vault.reload()?;
+    let received = ctx.accounts.vault.amount
+        .checked_sub(vault_balance_before)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    
+    let payout = Parlay::payout_at(received, legs.iter().map(|leg| leg.odds))?;
+    let liability = house.liability.checked_add(payout)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    house.limits.check(legs.len(), received, payout, liability)?;
+    require!(
+        liability <= ctx.accounts.vault.amount,
+        PredictionMarketError::InsufficientLiquidity
+    );
+    house.liability = liability;
+    
+    let parlay = &mut ctx.accounts.parlay;
+    parlay.version = Parlay::VERSION;
+    parlay.id = parlay_id;
+    parlay.user = ctx.accounts.user.key();
+    parlay.house = house.key();
+    parlay.stake = received;
+    parlay.payout = payout;
+    parlay.legs = legs;
+    parlay.status = ParlayStatus::Open;
+    parlay.paid = 0;
+    parlay.bump = ctx.bumps.parlay;
+    
+    msg!("Parlay placed with {} legs", parlay.legs.len());
+    msg!("Stake: {}", parlay.stake);
+    msg!("Payout: {}", parlay.payout);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(parlay_id: u64)]
+pub struct PlaceParlay<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [House::SEED_PREFIX.as_bytes(), house.mint.as_ref()],
+        bump = house.bump
+    )]
+    pub house: Account<'info, House>,
+    
+    #[account(
+        mut,
+        address = house.vault @ PredictionMarketError::InvalidTreasury
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        init,
+        payer = user,
+        space = Parlay::SIZE,
+        seeds = [Parlay::SEED_PREFIX.as_bytes(), user.key().as_ref(), parlay_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub parlay: Account<'info, Parlay>,
+    
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserProfile::SIZE,
+        seeds = [UserProfile::SEED_PREFIX.as_bytes(), user.key().as_ref()],
+        bump
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+    
+    #[account(address = house.mint @ PredictionMarketError::InvalidMint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        constraint = user_token_account.owner == user.key() @ PredictionMarketError::UnauthorizedAdmin,
+        constraint = user_token_account.mint == mint.key() @ PredictionMarketError::InvalidMint
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
\ No newline at end of file
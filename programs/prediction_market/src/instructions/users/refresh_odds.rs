@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use crate::state::{Market, Outcome};
+use crate::errors::PredictionMarketError;
+
+/// Writes each outcome's current odds, net of the market's fees, to its
+/// `Outcome` account. `remaining_accounts` must hold every outcome of the
+/// market, writable, in `market.outcomes` order. Markets with a book have
+/// its prices kept current by every bet instead, and fixed-odds markets
+/// have their odds posted. Outcomes nothing is staked on keep the odds
+/// they opened at, which may have been seeded from team ratings.
+pub fn refresh_odds<'info>(ctx: Context<'_, '_, 'info, 'info, RefreshOdds<'info>>) -> Result<()> {
+    let market = &ctx.accounts.market;
+    
+    // Validations
+    require!(
+        !market.has_fixed_odds,
+        PredictionMarketError::InvalidMarketMode
+    );
+    
+    require!(
+        ctx.remaining_accounts.len() == market.outcomes.len(),
+        PredictionMarketError::OutcomeNotFound
+    );
+    
+    for (outcome_id, account) in market.outcomes.iter().zip(ctx.remaining_accounts) {
+        let mut outcome = Account::<Outcome>::try_from(account)?;
+        require!(
+            outcome.market == market.key() && outcome.id == *outcome_id,
+            PredictionMarketError::OutcomeNotFound
+        );
+    
+        // Eliminated outcomes stay worthless, and unstaked ones keep their opening odds
+        if outcome.eliminated {
+            outcome.odds = 0;
+        } else if outcome.total_staked > 0 {
+            outcome.odds = market.odds(outcome.total_staked)?;
+        }
+        outcome.exit(ctx.program_id)?;
+    }
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RefreshOdds<'info> {
+    pub market: Account<'info, Market>,
+}
\ No newline at end of file
@@ -0,0 +1,228 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Burn, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::{LiquidityPool, Market, MarketBook, MarketStatus, Outcome};
+use crate::errors::PredictionMarketError;
+
+/// Burns `lp_tokens` for their exact share of the liquidity pool.
+///
+/// While the market takes bets the share is of the pool's stake on each
+/// outcome, withdrawn from the outcome escrows. A cancelled market refunds
+/// the same share of the stakes. A resolved market pays the share of what
+/// the pool's winning stake claims plus the LPs' part of the fee, worked out
+/// by the first redemption, out of the winning escrow. Between the market
+/// closing and settling, liquidity is locked.
+///
+/// `remaining_accounts` must hold, for every outcome in `market.outcomes`
+/// order, the writable `Outcome`, its escrow authority and its escrow token
+/// account.
+pub fn remove_liquidity<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RemoveLiquidity<'info>>,
+    lp_tokens: u64,
+) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let liquidity_pool = &mut ctx.accounts.liquidity_pool;
+    let clock = Clock::get()?;
+    
+    // Validations
+    require!(
+        lp_tokens > 0 && lp_tokens <= liquidity_pool.lp_supply,
+        PredictionMarketError::InvalidLiquidityAmount
+    );
+    
+    let is_open = market.status == MarketStatus::Active && clock.unix_timestamp < market.end_time;
+    require!(
+        is_open || market.status == MarketStatus::Resolved || market.status == MarketStatus::Cancelled,
+        PredictionMarketError::LiquidityLocked
+    );
+    
+    require!(
+        market.unswept_escrows == 0,
+        PredictionMarketError::SettlementPending
+    );
+    
+    require!(
+        ctx.remaining_accounts.len() == market.outcomes.len() * 3,
+        PredictionMarketError::InvalidSettlementAccounts
+    );
+    
+    let cpi_accounts = Burn {
+        mint: ctx.accounts.lp_mint.to_account_info(),
+        from: ctx.accounts.provider_lp_token_account.to_account_info(),
+        authority: ctx.accounts.provider.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token_interface::burn(cpi_ctx, lp_tokens)?;
+    
+    let market_key = market.key();
+    let mut withdrawn = 0u64;
+    for index in 0..market.outcomes.len() {
+        let outcome_id = market.outcomes[index];
+        let accounts = &ctx.remaining_accounts[index * 3..index * 3 + 3];
+        let mut outcome = Account::<Outcome>::try_from(&accounts[0])?;
+        require!(
+            outcome.market == market_key && outcome.id == outcome_id,
+            PredictionMarketError::OutcomeNotFound
+        );
+    
+        let outcome_id_bytes = outcome_id.to_le_bytes();
+        let (expected_authority, bump) = Pubkey::find_program_address(
+            &[b"escrow", market_key.as_ref(), outcome_id_bytes.as_ref()],
+            ctx.program_id,
+        );
+        require_keys_eq!(
+            accounts[1].key(),
+            expected_authority,
+            PredictionMarketError::InvalidSettlementAccounts
+        );
+        require_keys_eq!(
+            accounts[2].key(),
+            outcome.escrow_pubkey,
+            PredictionMarketError::InvalidSettlementAccounts
+        );
+    
+        let amount = if market.status == MarketStatus::Resolved {
+            // Only the winning escrow holds anything once resolved
+            if market.winner != Some(outcome_id) {
+                continue;
+            }
+    
+            // The pool claims its winning stake once, as a single claimant,
+            // and LPs then redeem their share of it with the fees
+            if !liquidity_pool.settled {
+                let stake = liquidity_pool.stakes[index];
+                let payout = if stake > 0 {
+                    let payout = market.stake_claim_amount(stake, 0, &outcome)?;
+                    outcome.record_claim(payout)?;
+                    payout
+                } else {
+                    0
+                };
+                liquidity_pool.redeemable = payout.checked_add(liquidity_pool.fees)
+                    .ok_or(PredictionMarketError::MathOverflow)?;
+                liquidity_pool.settled = true;
+            }
+            let amount = liquidity_pool.share_of(liquidity_pool.redeemable, lp_tokens)?;
+            liquidity_pool.redeemable = liquidity_pool.redeemable.checked_sub(amount)
+                .ok_or(PredictionMarketError::MathOverflow)?;
+            amount
+        } else {
+            let amount = liquidity_pool.share_of(liquidity_pool.stakes[index], lp_tokens)?;
+            if amount == 0 {
+                continue;
+            }
+            liquidity_pool.stakes[index] -= amount;
+            let is_last = liquidity_pool.stakes[index] == 0;
+    
+            if market.status == MarketStatus::Cancelled {
+                // Refunded like a position, which claims when the last of
+                // the pool's stake leaves
+                if is_last {
+                    outcome.record_claim(amount)?;
+                } else {
+                    outcome.total_claimed = outcome.total_claimed.checked_add(amount)
+                        .ok_or(PredictionMarketError::MathOverflow)?;
+                }
+            } else {
+                outcome.total_staked = outcome.total_staked.checked_sub(amount)
+                    .ok_or(PredictionMarketError::MathOverflow)?;
+                market.total_pool = market.total_pool.checked_sub(amount)
+                    .ok_or(PredictionMarketError::MathOverflow)?;
+                if is_last {
+                    outcome.claimants_remaining = outcome.claimants_remaining.checked_sub(1)
+                        .ok_or(PredictionMarketError::MathOverflow)?;
+                }
+    
+                if market.has_book {
+                    let market_book = ctx.accounts.market_book
+                        .as_ref()
+                        .ok_or(PredictionMarketError::InvalidMarketBook)?;
+                    market_book.load_mut()?.record_withdrawal(market, outcome_id, amount)?;
+                }
+            }
+            amount
+        };
+    
+        if amount > 0 {
+            let bump = [bump];
+            let escrow_seeds = &[
+                b"escrow",
+                market_key.as_ref(),
+                outcome_id_bytes.as_ref(),
+                &bump
+            ];
+            let signer_seeds = &[&escrow_seeds[..]];
+    
+            let cpi_accounts = TransferChecked {
+                from: accounts[2].clone(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.provider_token_account.to_account_info(),
+                authority: accounts[1].clone(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                cpi_accounts,
+                signer_seeds
+            );
+            token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.mint.decimals)?;
+        }
+        outcome.exit(ctx.program_id)?;
+        withdrawn = withdrawn.checked_add(amount)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+    }
+    
+    liquidity_pool.lp_supply -= lp_tokens;
+    
+    msg!("LP tokens burned: {}", lp_tokens);
+    msg!("Liquidity removed: {}", withdrawn);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RemoveLiquidity<'info> {
+    pub provider: Signer<'info>,
+    
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        mut,
+        seeds = [LiquidityPool::SEED_PREFIX.as_bytes(), market.key().as_ref()],
+        bump = liquidity_pool.bump
+    )]
+    pub liquidity_pool: Account<'info, LiquidityPool>,
+    
+    #[account(
+        mut,
+        address = liquidity_pool.lp_mint @ PredictionMarketError::InvalidLiquidityPool
+    )]
+    pub lp_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        constraint = provider_lp_token_account.owner == provider.key() @ PredictionMarketError::UnauthorizedAdmin,
+        constraint = provider_lp_token_account.mint == lp_mint.key() @ PredictionMarketError::InvalidMint
+    )]
+    pub provider_lp_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// The mint of the token staked on this market
+    #[account(address = market.collateral_mint @ PredictionMarketError::InvalidMint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        constraint = provider_token_account.owner == provider.key() @ PredictionMarketError::UnauthorizedAdmin,
+        constraint = provider_token_account.mint == mint.key() @ PredictionMarketError::InvalidMint
+    )]
+    pub provider_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// Required on markets with a book
+    #[account(
+        mut,
+        seeds = [MarketBook::SEED_PREFIX.as_bytes(), market.key().as_ref()],
+        bump = market_book.load()?.bump
+    )]
+    pub market_book: Option<AccountLoader<'info, MarketBook>>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
\ No newline at end of file
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use crate::state::UserProfile;
+
+/// Caps what the user can bet per day and per week across all markets.
+/// Zero removes a limit, subject to the cooldown.
+pub fn set_deposit_limits(ctx: Context<SetDepositLimits>, daily: u64, weekly: u64) -> Result<()> {
+    let user_profile = &mut ctx.accounts.user_profile;
+    let clock = Clock::get()?;
+    
+    user_profile.version = UserProfile::VERSION;
+    user_profile.user = ctx.accounts.user.key();
+    user_profile.bump = ctx.bumps.user_profile;
+    user_profile.set_deposit_limits(daily, weekly, clock.unix_timestamp)?;
+    
+    msg!("User {} deposit limits set to {} daily, {} weekly", user_profile.user, daily, weekly);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetDepositLimits<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserProfile::SIZE,
+        seeds = [UserProfile::SEED_PREFIX.as_bytes(), user.key().as_ref()],
+        bump
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+    
+    pub system_program: Program<'info, System>,
+}
\ No newline at end of file
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use crate::state::UserProfile;
+
+/// Excludes the user from betting in every market until `until`. Passing a
+/// past time lifts the exclusion, subject to the cooldown.
+pub fn set_self_exclusion(ctx: Context<SetSelfExclusion>, until: i64) -> Result<()> {
+    let user_profile = &mut ctx.accounts.user_profile;
+    let clock = Clock::get()?;
+    
+    user_profile.version = UserProfile::VERSION;
+    user_profile.user = ctx.accounts.user.key();
+    user_profile.bump = ctx.bumps.user_profile;
+    user_profile.set_self_exclusion(until, clock.unix_timestamp)?;
+    
+    msg!("User {} self-excluded until {}", user_profile.user, until);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetSelfExclusion<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserProfile::SIZE,
+        seeds = [UserProfile::SEED_PREFIX.as_bytes(), user.key().as_ref()],
+        bump
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+    
+    pub system_program: Program<'info, System>,
+}
\ No newline at end of file
@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::{House, LegResult, Market, MarketStatus, Parlay, ParlayStatus};
+use crate::errors::PredictionMarketError;
+
+/// Settlement crank, callable by anyone. Records the result of each leg
+/// whose market has finished, passed as `remaining_accounts` in leg order,
+/// and settles the parlay once one leg has lost or none is left pending:
+/// the user is paid from the house vault and the reserved payout released.
+pub fn settle_parlay<'info>(ctx: Context<'_, '_, 'info, 'info, SettleParlay<'info>>) -> Result<()> {
+    let parlay = &mut ctx.accounts.parlay;
+    let house = &mut ctx.accounts.house;
+    
+    // Validations
+    require!(
+        parlay.status == ParlayStatus::Open,
+        PredictionMarketError::ParlayAlreadySettled
+    );
+    
+    require!(
+        ctx.remaining_accounts.len() == parlay.legs.len(),
+        PredictionMarketError::InvalidParlayLeg
+    );
+    
+    for (leg, account) in parlay.legs.iter_mut().zip(ctx.remaining_accounts) {
+        let market = Account::<Market>::try_from(account)?;
+        require_keys_eq!(
+            market.key(),
+            leg.market,
+            PredictionMarketError::InvalidParlayLeg
+        );
+    
+        // Cancelled markets void the leg
+        leg.result = match market.status {
+            MarketStatus::Resolved if market.winner == Some(leg.outcome_id) => LegResult::Won,
+            MarketStatus::Resolved => LegResult::Lost,
+            MarketStatus::Cancelled => LegResult::Void,
+            _ => LegResult::Pending,
+        };
+    }
+    
+    let (status, paid) = parlay.settlement()?;
+    
+    house.liability = house.liability.checked_sub(parlay.payout)
+        .ok_or(PredictionMarketError::MathOverflow)?;
+    parlay.status = status;
+    parlay.paid = paid;
+    
+    if paid > 0 {
+        let mint_key = house.mint;
+        let bump = [house.bump];
+        let house_seeds = &[
+            House::SEED_PREFIX.as_bytes(),
+            mint_key.as_ref(),
+            &bump
+        ];
+        let signer_seeds = &[&house_seeds[..]];
+    
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.vault.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            to: ctx.accounts.user_token_account.to_account_info(),
+            authority: house.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds
+        );
+        token_interface::transfer_checked(cpi_ctx, paid, ctx.accounts.mint.decimals)?;
+    }
+    
+    msg!("Parlay settled: {:?}", parlay.status);
+    msg!("Paid: {}", parlay.paid);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SettleParlay<'info> {
+    #[account(
+        mut,
+        seeds = [Parlay::SEED_PREFIX.as_bytes(), parlay.user.as_ref(), parlay.id.to_le_bytes().as_ref()],
+        bump = parlay.bump,
+        has_one = house
+    )]
+    pub parlay: Account<'info, Parlay>,
+    
+    #[account(mut)]
+    pub house: Account<'info, House>,
+    
+    #[account(
+        mut,
+        address = house.vault @ PredictionMarketError::InvalidTreasury
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(address = house.mint @ PredictionMarketError::InvalidMint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    /// Token account of the parlay's user, paid if it wins or is void
+    #[account(
+        mut,
+        constraint = user_token_account.owner == parlay.user @ PredictionMarketError::UnauthorizedAdmin,
+        constraint = user_token_account.mint == mint.key() @ PredictionMarketError::InvalidMint
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
\ No newline at end of file
@@ -5,50 +5,294 @@ pub mod instructions;
 pub mod errors;
 
 use instructions::*;
+use state::{AccessGate, BetLimits, CollateralMode, FeeModel, FeeTier, LegLimits, ParlayLimits, PlatformConfig, Region, StatKind, TournamentTier};
 
 
 declare_id!("4asst9oqh9cAryCAViQ2pySSESqP9TLd5nEaz5BJfrxL");
 
-#[program]
-pub mod prediction_market {
+// `#[program]` expands to IDL instructions that still call the deprecated
+// `AccountInfo::realloc`, so its expansion gets a module of its own
+#[allow(deprecated)]
+mod interface {
     use super::*;
-
-    // Platform management
-    pub fn initialize_platform(ctx: Context<InitializePlatform>) -> Result<()> {
-        instructions::initialize_platform(ctx)
-    }
-
-    // Market management
-    pub fn create_market(
-        ctx: Context<CreateMarket>,
-        name: String,
-        description: String,
-        start_time: i64,
-        end_time: i64,
-        custom_fee_percentage: Option<u8>,
-    ) -> Result<()> {
-        instructions::create_market(ctx, name, description, start_time, end_time, custom_fee_percentage)
-    }
-
-    pub fn add_outcome(ctx: Context<AddOutcome>, name: String, outcome_id: u8) -> Result<()> {
-        instructions::add_outcome(ctx, name, outcome_id)
-    }
-
-    // Betting functions
-    pub fn place_bet(ctx: Context<PlaceBet>, amount: u64) -> Result<()> {
-        instructions::place_bet(ctx, amount)
-    }
-
-    pub fn resolve_market(ctx: Context<ResolveMarket>, winning_outcome_id: u8) -> Result<()> {
-        instructions::resolve_market(ctx, winning_outcome_id)
-    }
-
-    pub fn claim_payout(ctx: Context<ClaimPayout>) -> Result<()> {
-        instructions::claim_payout(ctx)
-    }
-
-    // Admin functions
-    pub fn withdraw_fees(ctx: Context<WithdrawFees>) -> Result<()> {
-        instructions::withdraw_fees(ctx)
+    
+    #[program]
+    pub mod prediction_market {
+        use super::*;
+        
+        // Platform management
+        pub fn initialize_platform(ctx: Context<InitializePlatform>) -> Result<()> {
+            instructions::initialize_platform(ctx)
+        }
+        
+        pub fn add_collateral_mint(ctx: Context<AddCollateralMint>) -> Result<()> {
+            instructions::add_collateral_mint(ctx)
+        }
+        
+        pub fn set_collateral_enabled(ctx: Context<SetCollateralEnabled>, enabled: bool) -> Result<()> {
+            instructions::set_collateral_enabled(ctx, enabled)
+        }
+        
+        pub fn set_default_bet_limits(ctx: Context<SetDefaultBetLimits>, limits: BetLimits) -> Result<()> {
+            instructions::set_default_bet_limits(ctx, limits)
+        }
+        
+        pub fn set_referral_share(ctx: Context<SetReferralShare>, referral_share_bps: u16) -> Result<()> {
+            instructions::set_referral_share(ctx, referral_share_bps)
+        }
+        
+        pub fn set_lp_fee_share(ctx: Context<SetLpFeeShare>, lp_fee_share_bps: u16) -> Result<()> {
+            instructions::set_lp_fee_share(ctx, lp_fee_share_bps)
+        }
+        
+        pub fn set_max_creator_fee(ctx: Context<SetMaxCreatorFee>, max_creator_fee_bps: u16) -> Result<()> {
+            instructions::set_max_creator_fee(ctx, max_creator_fee_bps)
+        }
+        
+        pub fn set_fee_tiers(ctx: Context<SetFeeTiers>, fee_tiers: [FeeTier; PlatformConfig::FEE_TIERS]) -> Result<()> {
+            instructions::set_fee_tiers(ctx, fee_tiers)
+        }
+        
+        // Market management
+        #[allow(clippy::too_many_arguments)]
+        pub fn create_market(
+            ctx: Context<CreateMarket>,
+            name: String,
+            description: String,
+            start_time: i64,
+            end_time: i64,
+            custom_fee_bps: Option<u16>,
+            custom_oracle: Option<Pubkey>,
+            collateral_mode: CollateralMode,
+        ) -> Result<()> {
+            instructions::create_market(ctx, name, description, start_time, end_time, custom_fee_bps, custom_oracle, collateral_mode)
+        }
+        
+        pub fn add_outcome(ctx: Context<AddOutcome>, name: String, outcome_id: u8) -> Result<()> {
+            instructions::add_outcome(ctx, name, outcome_id)
+        }
+        
+        pub fn set_bet_limits(ctx: Context<SetBetLimits>, limits: BetLimits) -> Result<()> {
+            instructions::set_bet_limits(ctx, limits)
+        }
+        
+        pub fn set_access_gate(ctx: Context<SetAccessGate>, access_gate: AccessGate) -> Result<()> {
+            instructions::set_access_gate(ctx, access_gate)
+        }
+        
+        pub fn set_creator_fee(ctx: Context<SetCreatorFee>, creator_fee_bps: u16) -> Result<()> {
+            instructions::set_creator_fee(ctx, creator_fee_bps)
+        }
+        
+        pub fn set_fee_model(ctx: Context<SetFeeModel>, fee_model: FeeModel) -> Result<()> {
+            instructions::set_fee_model(ctx, fee_model)
+        }
+        
+        pub fn create_market_book(ctx: Context<CreateMarketBook>) -> Result<()> {
+            instructions::create_market_book(ctx)
+        }
+        
+        pub fn set_market_condition(ctx: Context<SetMarketCondition>, parent_outcome_id: u8) -> Result<()> {
+            instructions::set_market_condition(ctx, parent_outcome_id)
+        }
+        
+        // Tournaments
+        pub fn create_tournament(
+            ctx: Context<CreateTournament>,
+            name: String,
+            tier: TournamentTier,
+            region: Region,
+            start_date: i64,
+            end_date: i64,
+        ) -> Result<()> {
+            instructions::create_tournament(ctx, name, tier, region, start_date, end_date)
+        }
+        
+        pub fn add_tournament_match(ctx: Context<AddTournamentMatch>, is_grand_final: bool) -> Result<()> {
+            instructions::add_tournament_match(ctx, is_grand_final)
+        }
+        
+        pub fn eliminate_outcome(ctx: Context<EliminateOutcome>) -> Result<()> {
+            instructions::eliminate_outcome(ctx)
+        }
+        
+        // Parlays
+        pub fn create_house(ctx: Context<CreateHouse>, limits: ParlayLimits, leg_limits: LegLimits) -> Result<()> {
+            instructions::create_house(ctx, limits, leg_limits)
+        }
+        
+        pub fn set_parlay_limits(ctx: Context<SetParlayLimits>, limits: ParlayLimits, leg_limits: LegLimits) -> Result<()> {
+            instructions::set_parlay_limits(ctx, limits, leg_limits)
+        }
+        
+        pub fn withdraw_house_liquidity(ctx: Context<WithdrawHouseLiquidity>, amount: u64) -> Result<()> {
+            instructions::withdraw_house_liquidity(ctx, amount)
+        }
+        
+        pub fn place_parlay<'info>(
+            ctx: Context<'_, '_, 'info, 'info, PlaceParlay<'info>>,
+            parlay_id: u64,
+            stake: u64,
+        ) -> Result<()> {
+            instructions::place_parlay(ctx, parlay_id, stake)
+        }
+        
+        pub fn settle_parlay<'info>(ctx: Context<'_, '_, 'info, 'info, SettleParlay<'info>>) -> Result<()> {
+            instructions::settle_parlay(ctx)
+        }
+        
+        // Liquidity provision
+        pub fn add_liquidity<'info>(ctx: Context<'_, '_, 'info, 'info, AddLiquidity<'info>>, amount: u64) -> Result<()> {
+            instructions::add_liquidity(ctx, amount)
+        }
+        
+        pub fn remove_liquidity<'info>(ctx: Context<'_, '_, 'info, 'info, RemoveLiquidity<'info>>, lp_tokens: u64) -> Result<()> {
+            instructions::remove_liquidity(ctx, lp_tokens)
+        }
+        
+        // Fixed odds
+        pub fn set_fixed_odds(ctx: Context<SetFixedOdds>, max_liability: u64) -> Result<()> {
+            instructions::set_fixed_odds(ctx, max_liability)
+        }
+        
+        pub fn set_outcome_odds(ctx: Context<SetOutcomeOdds>, odds: u64) -> Result<()> {
+            instructions::set_outcome_odds(ctx, odds)
+        }
+        
+        pub fn claim_fixed_odds_payout(ctx: Context<ClaimFixedOddsPayout>) -> Result<()> {
+            instructions::claim_fixed_odds_payout(ctx)
+        }
+        
+        // Teams, players and props
+        pub fn create_team(
+            ctx: Context<CreateTeam>,
+            name: String,
+            tag: String,
+            region: Region,
+            logo_uri: String,
+        ) -> Result<()> {
+            instructions::create_team(ctx, name, tag, region, logo_uri)
+        }
+        
+        pub fn seed_odds(ctx: Context<SeedOdds>) -> Result<()> {
+            instructions::seed_odds(ctx)
+        }
+        
+        pub fn set_rates_teams(ctx: Context<SetRatesTeams>, rates_teams: bool) -> Result<()> {
+            instructions::set_rates_teams(ctx, rates_teams)
+        }
+        
+        pub fn create_player(ctx: Context<CreatePlayer>, handle: String) -> Result<()> {
+            instructions::create_player(ctx, handle)
+        }
+        
+        pub fn create_prop_market(ctx: Context<CreatePropMarket>, stat: StatKind, line: u64) -> Result<()> {
+            instructions::create_prop_market(ctx, stat, line)
+        }
+        
+        pub fn resolve_prop_market<'info>(
+            ctx: Context<'_, '_, 'info, 'info, ResolveMarket<'info>>,
+            stat_value: u64,
+        ) -> Result<()> {
+            instructions::resolve_prop_market(ctx, stat_value)
+        }
+        
+        pub fn create_top_player_market(ctx: Context<CreateTopPlayerMarket>, stat: StatKind) -> Result<()> {
+            instructions::create_top_player_market(ctx, stat)
+        }
+        
+        pub fn add_top_player(ctx: Context<AddTopPlayer>, outcome_id: u8) -> Result<()> {
+            instructions::add_top_player(ctx, outcome_id)
+        }
+        
+        pub fn resolve_top_player_market<'info>(
+            ctx: Context<'_, '_, 'info, 'info, ResolveMarket<'info>>,
+            player_id: u64,
+            stat_value: u64,
+        ) -> Result<()> {
+            instructions::resolve_top_player_market(ctx, player_id, stat_value)
+        }
+        
+        // Verification
+        pub fn add_to_allowlist(ctx: Context<AddToAllowlist>, user: Pubkey) -> Result<()> {
+            instructions::add_to_allowlist(ctx, user)
+        }
+        
+        pub fn remove_from_allowlist(ctx: Context<RemoveFromAllowlist>) -> Result<()> {
+            instructions::remove_from_allowlist(ctx)
+        }
+        
+        pub fn issue_credential(ctx: Context<IssueCredential>, user: Pubkey, expires_at: i64) -> Result<()> {
+            instructions::issue_credential(ctx, user, expires_at)
+        }
+        
+        pub fn revoke_credential(ctx: Context<RevokeCredential>) -> Result<()> {
+            instructions::revoke_credential(ctx)
+        }
+        
+        // Betting functions
+        pub fn place_bet(ctx: Context<PlaceBet>, amount: u64) -> Result<()> {
+            instructions::place_bet(ctx, amount)
+        }
+        
+        pub fn cancel_market(ctx: Context<CancelMarket>) -> Result<()> {
+            instructions::cancel_market(ctx)
+        }
+        
+        pub fn resolve_market<'info>(
+            ctx: Context<'_, '_, 'info, 'info, ResolveMarket<'info>>,
+            winning_outcome_id: u8,
+        ) -> Result<()> {
+            instructions::resolve_market(ctx, winning_outcome_id)
+        }
+        
+        pub fn sweep_escrows<'info>(ctx: Context<'_, '_, 'info, 'info, ResolveMarket<'info>>) -> Result<()> {
+            instructions::sweep_escrows(ctx)
+        }
+        
+        pub fn claim_payout(ctx: Context<ClaimPayout>) -> Result<()> {
+            instructions::claim_payout(ctx)
+        }
+        
+        pub fn claim_refund(ctx: Context<ClaimRefund>) -> Result<()> {
+            instructions::claim_refund(ctx)
+        }
+        
+        pub fn claim_referral_rewards(ctx: Context<ClaimReferralRewards>) -> Result<()> {
+            instructions::claim_referral_rewards(ctx)
+        }
+        
+        pub fn refresh_odds<'info>(ctx: Context<'_, '_, 'info, 'info, RefreshOdds<'info>>) -> Result<()> {
+            instructions::refresh_odds(ctx)
+        }
+        
+        // Responsible gaming
+        pub fn set_self_exclusion(ctx: Context<SetSelfExclusion>, until: i64) -> Result<()> {
+            instructions::set_self_exclusion(ctx, until)
+        }
+        
+        pub fn set_deposit_limits(ctx: Context<SetDepositLimits>, daily: u64, weekly: u64) -> Result<()> {
+            instructions::set_deposit_limits(ctx, daily, weekly)
+        }
+        
+        // Admin functions
+        pub fn withdraw_fees(ctx: Context<WithdrawFees>) -> Result<()> {
+            instructions::withdraw_fees(ctx)
+        }
+        
+        // Account migrations
+        pub fn migrate_platform_config(ctx: Context<MigratePlatformConfig>) -> Result<()> {
+            instructions::migrate_platform_config(ctx)
+        }
+        
+        pub fn migrate_market(ctx: Context<MigrateMarket>) -> Result<()> {
+            instructions::migrate_market(ctx)
+        }
+        
+        pub fn migrate_outcome(ctx: Context<MigrateOutcome>) -> Result<()> {
+            instructions::migrate_outcome(ctx)
+        }
     }
 }
+
+pub use interface::*;
\ No newline at end of file
@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+
+/// Marks a user as verified by the platform admin, for allowlist-gated markets
+#[account]
+#[derive(Default, InitSpace)]
+pub struct AllowlistEntry {
+    pub version: u8,                  // Layout version, see `AllowlistEntry::VERSION`
+    pub user: Pubkey,                 // Verified user's wallet address
+    pub added_at: i64,                // When the admin added the user
+    pub bump: u8,                     // PDA bump
+    pub reserved: [u8; 32],           // Zeroed room for fields added by later versions
+}
+
+impl AllowlistEntry {
+    pub const SEED_PREFIX: &'static str = "allowlist";
+    pub const SIZE: usize = 8 + Self::INIT_SPACE; // discriminator + fields
+    
+    /// Layout version written by this program
+    pub const VERSION: u8 = 1;
+}
\ No newline at end of file
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use crate::errors::PredictionMarketError;
+
+/// Betting limits, in collateral base units. A zero maximum means no limit.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub struct BetLimits {
+    pub min_bet: u64,      // Smallest accepted bet
+    pub max_bet: u64,      // Largest bet in a single transaction
+    pub max_position: u64, // Largest stake one user can hold on a market, across its outcomes
+    pub max_pool: u64,     // Largest total pool of the market
+}
+
+impl BetLimits {
+    /// Every maximum that is set must leave room for the minimum bet
+    pub fn is_valid(&self) -> bool {
+        [self.max_bet, self.max_position, self.max_pool]
+            .iter()
+            .all(|&max| max == 0 || max >= self.min_bet)
+    }
+    
+    /// Checks a bet of `amount` that would bring the user's stake on the
+    /// market to `position` and the market's pool to `pool`
+    pub fn check(&self, amount: u64, position: u64, pool: u64) -> Result<()> {
+        require!(
+            amount >= self.min_bet,
+            PredictionMarketError::BetBelowMinimum
+        );
+        require!(
+            self.max_bet == 0 || amount <= self.max_bet,
+            PredictionMarketError::BetAboveMaximum
+        );
+        require!(
+            self.max_position == 0 || position <= self.max_position,
+            PredictionMarketError::PositionLimitExceeded
+        );
+        self.check_pool(pool)
+    }
+    
+    /// Checks a deposit that would bring the market's pool to `pool`
+    pub fn check_pool(&self, pool: u64) -> Result<()> {
+        require!(
+            self.max_pool == 0 || pool <= self.max_pool,
+            PredictionMarketError::PoolLimitExceeded
+        );
+        Ok(())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+/// Allowlist entry for a collateral mint, one per accepted mint
+#[account]
+#[derive(Default, InitSpace)]
+pub struct CollateralConfig {
+    pub version: u8,                  // Layout version, see `CollateralConfig::VERSION`
+    pub mint: Pubkey,                 // Accepted collateral mint (e.g., USDC, wrapped SOL)
+    pub treasury: Pubkey,             // Token account collecting fees in this mint
+    pub enabled: bool,                // Whether new markets may use this mint
+    pub bump: u8,                     // PDA bump
+    pub reserved: [u8; 32],           // Zeroed room for fields added by later versions
+}
+
+impl CollateralConfig {
+    pub const SEED_PREFIX: &'static str = "collateral";
+    pub const SIZE: usize = 8 + Self::INIT_SPACE; // discriminator + fields
+    
+    /// Layout version written by this program
+    pub const VERSION: u8 = 1;
+}
\ No newline at end of file
@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+/// A user's verification issued by an attestation authority, for markets
+/// gated on that authority
+#[account]
+#[derive(Default, InitSpace)]
+pub struct Credential {
+    pub version: u8,                  // Layout version, see `Credential::VERSION`
+    pub authority: Pubkey,            // Attestation authority that issued it
+    pub user: Pubkey,                 // Verified user's wallet address
+    pub expires_at: i64,              // Credential is invalid from this time, 0 if it never expires
+    pub bump: u8,                     // PDA bump
+    pub reserved: [u8; 32],           // Zeroed room for fields added by later versions
+}
+
+impl Credential {
+    pub const SEED_PREFIX: &'static str = "credential";
+    pub const SIZE: usize = 8 + Self::INIT_SPACE; // discriminator + fields
+    
+    /// Layout version written by this program
+    pub const VERSION: u8 = 1;
+    
+    pub fn is_valid(&self, now: i64) -> bool {
+        self.expires_at == 0 || now < self.expires_at
+    }
+}
\ No newline at end of file
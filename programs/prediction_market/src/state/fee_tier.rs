@@ -0,0 +1,16 @@
+use anchor_lang::prelude::*;
+use crate::state::BPS_DENOMINATOR;
+
+/// A fee discount for users whose lifetime betting volume, in collateral
+/// base units, has reached `min_volume`. An all-zero tier is unused.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub struct FeeTier {
+    pub min_volume: u64,   // Lifetime volume needed to qualify
+    pub discount_bps: u16, // Share of the platform fee rebated, in basis points
+}
+
+impl FeeTier {
+    pub fn is_valid(&self) -> bool {
+        self.discount_bps as u64 <= BPS_DENOMINATOR
+    }
+}
\ No newline at end of file
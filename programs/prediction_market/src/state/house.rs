@@ -0,0 +1,112 @@
+use anchor_lang::prelude::*;
+use crate::errors::PredictionMarketError;
+use crate::state::{Market, BPS_DENOMINATOR, MAX_PARLAY_LEGS};
+
+/// Limits on the house's parlay risk, in collateral base units. A zero
+/// maximum means no limit, except on the payout, which must be capped.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub struct ParlayLimits {
+    pub max_legs: u8,       // Most legs in one parlay, up to `MAX_PARLAY_LEGS`
+    pub max_stake: u64,     // Largest stake on one parlay
+    pub max_payout: u64,    // Largest payout one parlay can win
+    pub max_liability: u64, // Largest total payout the house can owe on open parlays
+}
+
+impl ParlayLimits {
+    pub fn is_valid(&self) -> bool {
+        self.max_legs as usize <= MAX_PARLAY_LEGS
+    }
+    
+    /// Whether these limits cap what one parlay can win
+    pub fn caps_payout(&self) -> bool {
+        self.max_payout > 0
+    }
+    
+    /// Most legs a parlay can have
+    pub fn legs(&self) -> usize {
+        match self.max_legs {
+            0 => MAX_PARLAY_LEGS,
+            max_legs => max_legs as usize,
+        }
+    }
+    
+    /// Checks a parlay of `legs` legs staking `stake` to win `payout`, which
+    /// would bring the house's liability to `liability`
+    pub fn check(&self, legs: usize, stake: u64, payout: u64, liability: u64) -> Result<()> {
+        require!(
+            (2..=self.legs()).contains(&legs),
+            PredictionMarketError::InvalidParlayLegCount
+        );
+        require!(
+            (self.max_stake == 0 || stake <= self.max_stake)
+                && (self.max_payout == 0 || payout <= self.max_payout)
+                && (self.max_liability == 0 || liability <= self.max_liability),
+            PredictionMarketError::ParlayLimitExceeded
+        );
+        Ok(())
+    }
+}
+
+/// What the house prices a parlay leg at. A parimutuel leg's odds move with
+/// every bet on its market, so a dust bet on a thin market could otherwise
+/// set them; its market must hold at least `min_pool`, and every leg is
+/// taken at no more than `max_odds`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub struct LegLimits {
+    pub min_pool: u64,      // Smallest pool a parimutuel leg's market can have, 0 for any
+    pub max_odds: u64,      // Highest odds any leg is taken at, scaled by 10000
+}
+
+impl LegLimits {
+    pub fn is_valid(&self) -> bool {
+        self.max_odds > BPS_DENOMINATOR
+    }
+    
+    /// Odds a leg on `market` quoted at `odds` is taken at
+    pub fn leg_odds(&self, market: &Market, odds: u64) -> Result<u64> {
+        require!(
+            market.has_fixed_odds || market.total_pool >= self.min_pool,
+            PredictionMarketError::InvalidParlayLeg
+        );
+        Ok(odds.min(self.max_odds))
+    }
+}
+
+/// Liquidity parlays and fixed-odds bets are paid out of, one per
+/// collateral mint
+#[account]
+#[derive(Default, InitSpace)]
+pub struct House {
+    pub version: u8,                  // Layout version, see `House::VERSION`
+    pub mint: Pubkey,                 // Collateral mint parlays are staked and paid in
+    pub vault: Pubkey,                // Token account holding the house's liquidity and stakes
+    pub liability: u64,               // Total payout owed if every open parlay wins, plus fixed-odds reserves
+    pub limits: ParlayLimits,         // Risk limits on new parlays
+    pub bump: u8,                     // PDA bump
+    pub leg_limits: LegLimits,        // What parlay legs are priced at
+    pub reserved: [u8; 16],           // Zeroed room for fields added by later versions
+}
+
+impl House {
+    pub const SEED_PREFIX: &'static str = "house";
+    pub const SIZE: usize = 8 + Self::INIT_SPACE; // discriminator + fields
+    
+    /// Layout version written by this program
+    pub const VERSION: u8 = 1;
+    
+    /// Part of `vault_balance` not owed to open parlays
+    pub fn free_liquidity(&self, vault_balance: u64) -> u64 {
+        vault_balance.saturating_sub(self.liability)
+    }
+    
+    /// Changes what the house reserves for `market`'s fixed-odds bets to
+    /// `reserve`, moving its liability by the difference
+    pub fn reserve_for(&mut self, market: &mut Market, reserve: u64) -> Result<()> {
+        self.liability = self.liability
+            .checked_sub(market.house_reserve)
+            .and_then(|liability| liability.checked_add(reserve))
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        market.house_reserve = reserve;
+        Ok(())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,164 @@
+//! Account layouts written by the first deployed version of the program,
+//! before accounts carried a version byte. The migration instructions decode
+//! accounts still in one of these layouts and rewrite them in the current one.
+//!
+//! That version sized markets and outcomes to their strings, charged fees in
+//! whole percents and kept a single treasury in the platform config. Its
+//! `place_bet` looked for each escrow at the escrow authority's own address
+//! rather than the associated token account `add_outcome` created, so it
+//! never took a bet: outcomes were never staked on and no `UserPosition` was
+//! ever written. Only the platform config, markets and outcomes need migrating.
+
+use anchor_lang::prelude::*;
+use crate::state::{
+    AccessGate, BetLimits, CollateralMode, FeeModel, Market, MarketStatus, Outcome, PlatformConfig,
+};
+
+/// Whole percents become basis points
+fn percent_to_bps(percentage: u8) -> u16 {
+    percentage as u16 * 100
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct LegacyPlatformConfig {
+    pub admin: Pubkey,
+    pub oracle_authority: Pubkey,
+    pub treasury: Pubkey,
+    pub default_fee_percentage: u8,
+    pub markets_count: u64,
+    pub total_volume: u64,
+    pub paused: bool,
+    pub bump: u8,
+}
+
+impl LegacyPlatformConfig {
+    pub const SIZE: usize = 8 + Self::INIT_SPACE;
+    
+    /// Fees now go to a treasury per collateral mint, so the old treasury is
+    /// dropped; everything added since starts at its default
+    pub fn migrate(self) -> PlatformConfig {
+        PlatformConfig {
+            version: PlatformConfig::VERSION,
+            admin: self.admin,
+            oracle_authority: self.oracle_authority,
+            default_fee_bps: percent_to_bps(self.default_fee_percentage),
+            markets_count: self.markets_count,
+            total_volume: self.total_volume,
+            paused: self.paused,
+            bump: self.bump,
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyMarket {
+    pub id: u64,
+    pub name: String,
+    pub description: String,
+    pub creator: Pubkey,
+    pub outcomes: Vec<u8>,
+    pub total_pool: u64,
+    pub resolved: bool,
+    pub winner: Option<u8>,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub fee_percentage: u8,
+    pub oracle: Pubkey,
+    pub status: MarketStatus,
+    pub bump: u8,
+}
+
+impl LegacyMarket {
+    /// Space the first version allocated, sized to the market's strings
+    pub fn space(&self) -> usize {
+        8 +  // discriminator
+        8 +  // id
+        4 + self.name.len() + // name (String)
+        4 + self.description.len() + // description (String)
+        32 + // creator
+        4 + 32 * 10 + // outcomes (Vec<u8> with estimated capacity)
+        8 +  // total_pool
+        1 +  // resolved
+        1 + 1 + // winner (Option<u8>)
+        8 +  // start_time
+        8 +  // end_time
+        1 +  // fee_percentage
+        32 + // oracle
+        1 +  // status
+        1    // bump
+    }
+    
+    /// The first version had no collateral of its own: escrows were opened
+    /// for whichever mint `add_outcome` was given, so the caller names it
+    pub fn migrate(self, collateral_mint: Pubkey, bet_limits: BetLimits, referral_share_bps: u16) -> Market {
+        Market {
+            version: Market::VERSION,
+            id: self.id,
+            name: self.name,
+            description: self.description,
+            creator: self.creator,
+            outcomes: self.outcomes,
+            total_pool: self.total_pool,
+            resolved: self.resolved,
+            winner: self.winner,
+            start_time: self.start_time,
+            end_time: self.end_time,
+            fee_bps: percent_to_bps(self.fee_percentage),
+            fee_model: FeeModel::TotalPool,
+            oracle: self.oracle,
+            collateral_mint,
+            collateral_mode: CollateralMode::Token,
+            bet_limits,
+            access_gate: AccessGate::Open,
+            referral_share_bps,
+            status: self.status,
+            bump: self.bump,
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyOutcome {
+    pub id: u8,
+    pub market: Pubkey,
+    pub name: String,
+    pub escrow_pubkey: Pubkey,
+    pub total_staked: u64,
+    pub odds: u64,
+    pub bump: u8,
+}
+
+impl LegacyOutcome {
+    /// Space the first version allocated, sized to the outcome's name
+    pub fn space(&self) -> usize {
+        8 +  // discriminator
+        1 +  // id
+        32 + // market
+        4 + self.name.len() + // name (String)
+        32 + // escrow_pubkey
+        8 +  // total_staked
+        8 +  // odds (u64)
+        1    // bump
+    }
+    
+    /// Nothing was ever staked on or paid out of a legacy outcome, so it
+    /// starts with no claims recorded
+    pub fn migrate(self) -> Outcome {
+        Outcome {
+            version: Outcome::VERSION,
+            id: self.id,
+            market: self.market,
+            name: self.name,
+            escrow_pubkey: self.escrow_pubkey,
+            total_staked: self.total_staked,
+            total_claimed: 0,
+            claimants_remaining: 0,
+            discounted_stake: 0,
+            odds: self.odds,
+            bump: self.bump,
+            ..Default::default()
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,101 @@
+use anchor_lang::prelude::*;
+use crate::errors::PredictionMarketError;
+use crate::state::{BPS_DENOMINATOR, MAX_OUTCOMES};
+
+/// Liquidity market makers have spread across one market's outcomes, owned
+/// pro rata by holders of the pool's LP tokens
+#[account]
+#[derive(Default, InitSpace)]
+pub struct LiquidityPool {
+    pub version: u8,                  // Layout version, see `LiquidityPool::VERSION`
+    pub market: Pubkey,               // The market the liquidity is staked on
+    pub lp_mint: Pubkey,              // Mint of the pool's LP tokens, at `["lp-mint", market]`
+    pub lp_supply: u64,               // LP tokens outstanding
+    #[max_len(MAX_OUTCOMES)]
+    pub stakes: Vec<u64>,             // Stake on each outcome, in `Market::outcomes` order
+    pub fee_share_bps: u16,           // Share of the market's fee LPs earn, weighted by their share of the pool
+    pub fees: u64,                    // LPs' part of the fee, set at settlement
+    pub settled: bool,                // Whether `redeemable` has been worked out since the market resolved
+    pub redeemable: u64,              // What is left for LPs to redeem once settled
+    pub bump: u8,                     // PDA bump
+    pub reserved: [u8; 32],           // Zeroed room for fields added by later versions
+}
+
+impl LiquidityPool {
+    pub const SEED_PREFIX: &'static str = "liquidity";
+    pub const LP_MINT_SEED_PREFIX: &'static str = "lp-mint";
+    pub const SIZE: usize = 8 + Self::INIT_SPACE; // discriminator + fields
+    
+    /// Layout version written by this program
+    pub const VERSION: u8 = 1;
+    
+    /// Total staked across every outcome
+    pub fn total_stake(&self) -> u64 {
+        self.stakes.iter().sum()
+    }
+    
+    /// Splits `amount` by `weights`, rounding each part down and adding
+    /// what rounding left to the last weighted part. All zero weights split
+    /// nothing.
+    pub fn split(amount: u64, weights: &[u64]) -> Result<Vec<u64>> {
+        let total_weight: u128 = weights.iter().map(|weight| *weight as u128).sum();
+        let mut parts = vec![0; weights.len()];
+        let Some(last) = weights.iter().rposition(|weight| *weight > 0) else {
+            return Ok(parts);
+        };
+    
+        for (part, weight) in parts.iter_mut().zip(weights) {
+            *part = (amount as u128 * *weight as u128 / total_weight) as u64;
+        }
+        let split: u64 = parts.iter().sum();
+        parts[last] = parts[last].checked_add(amount - split)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        Ok(parts)
+    }
+    
+    /// LP tokens minted for `stake` just added to the pool: the stake itself
+    /// into an empty pool, otherwise its share of the pool's stake before it
+    pub fn lp_tokens_for(&self, stake: u64) -> Result<u64> {
+        let stake_before = self.total_stake()
+            .checked_sub(stake)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        if self.lp_supply == 0 || stake_before == 0 {
+            return Ok(stake);
+        }
+    
+        let lp_tokens = (stake as u128)
+            .checked_mul(self.lp_supply as u128)
+            .ok_or(PredictionMarketError::MathOverflow)?
+            / stake_before as u128;
+        u64::try_from(lp_tokens).map_err(|_| PredictionMarketError::MathOverflow.into())
+    }
+    
+    /// Part of `amount` owed for burning `lp_tokens`. Rounds down, so burning
+    /// the whole supply takes exactly what is left.
+    pub fn share_of(&self, amount: u64, lp_tokens: u64) -> Result<u64> {
+        require!(
+            lp_tokens > 0 && lp_tokens <= self.lp_supply,
+            PredictionMarketError::InvalidLiquidityAmount
+        );
+    
+        Ok((amount as u128 * lp_tokens as u128 / self.lp_supply as u128) as u64)
+    }
+    
+    /// LPs' part of `fee_amount` on a pool of `total_pool`: the fee share of
+    /// the fee attributable to their stake
+    pub fn fee_amount(&self, fee_amount: u64, total_pool: u64) -> Result<u64> {
+        if total_pool == 0 {
+            return Ok(0);
+        }
+    
+        // Transfer-fee mints can settle on less than was credited
+        let stake = self.total_stake().min(total_pool);
+        let fees = (fee_amount as u128)
+            .checked_mul(self.fee_share_bps as u128)
+            .ok_or(PredictionMarketError::MathOverflow)?
+            .checked_mul(stake as u128)
+            .ok_or(PredictionMarketError::MathOverflow)?
+            / (BPS_DENOMINATOR as u128 * total_pool as u128);
+        Ok(fees as u64)
+    }
+}
\ No newline at end of file
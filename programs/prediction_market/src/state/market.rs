@@ -1,34 +1,321 @@
 use anchor_lang::prelude::*;
+use crate::errors::PredictionMarketError;
+use crate::state::{AllowlistEntry, BetLimits, Credential, Outcome, UserPosition};
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+/// Denominator of every basis-point rate
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+/// Longest name and description a market can hold, in bytes
+pub const MAX_MARKET_NAME_LEN: usize = 100;
+pub const MAX_MARKET_DESCRIPTION_LEN: usize = 500;
+
+/// Most outcomes a market can have, since resolving it sweeps every losing
+/// escrow in one transaction
+pub const MAX_OUTCOMES: usize = 16;
+
+/// Most outcomes a market with a `MarketBook` can have, whose losing escrows
+/// can be swept in batches
+pub const MAX_BOOK_OUTCOMES: usize = 64;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
 pub enum MarketStatus {
+    #[default]
     Active,    // Market is open for betting
     Locked,    // Betting period has ended, awaiting resolution
     Resolved,  // Market has been resolved with a winner
     Cancelled, // Market was cancelled (e.g., match postponed)
 }
 
-impl Default for MarketStatus {
-    fn default() -> Self {
-        MarketStatus::Active
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub enum CollateralMode {
+    #[default]
+    Token,     // Users bet and are paid from their token accounts
+    NativeSol, // Users bet lamports, wrapped into wSOL escrows and unwrapped on claim
+}
+
+/// How a market charges its platform fee
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub enum FeeModel {
+    #[default]
+    TotalPool,   // Fee on the whole pool at settlement, winners' own stakes included
+    NetWinnings, // Fee on the losing stakes, i.e. on winners' net winnings, at settlement
+    AtBet,       // Fee on each bet as it is placed, paid straight to the treasury
+}
+
+/// Who may bet on a market
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub enum AccessGate {
+    #[default]
+    Open,                             // Anyone can bet
+    Allowlist,                        // Only users on the admin's allowlist
+    Attestation { authority: Pubkey }, // Only users holding a credential from `authority`
+}
+
+impl AccessGate {
+    /// Checks that `user` may bet, given whichever proof accounts the
+    /// caller passed. Open markets ignore them.
+    pub fn check(
+        &self,
+        user: Pubkey,
+        allowlist_entry: Option<&AllowlistEntry>,
+        credential: Option<&Credential>,
+        now: i64,
+    ) -> Result<()> {
+        match self {
+            AccessGate::Open => Ok(()),
+            AccessGate::Allowlist => {
+                require!(
+                    allowlist_entry.is_some_and(|entry| entry.user == user),
+                    PredictionMarketError::NotAllowlisted
+                );
+                Ok(())
+            }
+            AccessGate::Attestation { authority } => {
+                require!(
+                    credential.is_some_and(|credential| credential.authority == *authority
+                        && credential.user == user
+                        && credential.is_valid(now)),
+                    PredictionMarketError::InvalidCredential
+                );
+                Ok(())
+            }
+        }
     }
 }
 
 #[account]
-#[derive(Default)]
+#[derive(Default, InitSpace)]
 pub struct Market {
+    pub version: u8,                 // Layout version, see `Market::VERSION`
     pub id: u64,                     // Unique identifier
+    #[max_len(MAX_MARKET_NAME_LEN)]
     pub name: String,                // Market name (e.g., "TSM vs Cloud9 - Valorant Champions 2025")
+    #[max_len(MAX_MARKET_DESCRIPTION_LEN)]
     pub description: String,         // Market description
     pub creator: Pubkey,             // Creator's public key
-    pub outcomes: Vec<u8>,           // List of outcome IDs
+    #[max_len(MAX_OUTCOMES)]
+    pub outcomes: Vec<u8>,           // List of outcome IDs, unique
     pub total_pool: u64,             // Total amount staked on this market
     pub resolved: bool,              // Whether the market has been resolved
     pub winner: Option<u8>,          // Winner outcome ID (if resolved)
     pub start_time: i64,             // Match start time (unix timestamp)
     pub end_time: i64,               // Market end time (betting closes)
-    pub fee_percentage: u8,          // Platform fee percentage (e.g., 2 for 2%)
+    pub fee_bps: u16,                // Platform fee in basis points (e.g., 200 for 2%)
+    pub fee_model: FeeModel,         // What the platform fee is charged on, and when
     pub oracle: Pubkey,              // Oracle authority that can resolve this market
+    pub collateral_mint: Pubkey,     // Token mint staked and paid out on this market
+    pub collateral_mode: CollateralMode, // How users move collateral in and out
+    pub bet_limits: BetLimits,       // Limits on bets, positions and the pool
+    pub access_gate: AccessGate,     // Who may bet
+    pub referral_share_bps: u16,     // Share of the fee owed to referrers, in basis points
+    pub referred_volume: u64,        // Stake placed through any referrer
+    pub referral_fees: u64,          // Referrers' part of the fee, set at settlement
+    pub referral_fees_claimed: u64,  // Referral fees paid out so far
+    pub referrers_remaining: u32,    // Referrers that have not claimed yet
+    pub creator_fee_bps: u16,        // Creator's fee on the pool, in basis points, on top of the platform fee
+    pub fee_rebates: u64,            // Platform fee rebated to winners' fee tiers, set at settlement
     pub status: MarketStatus,        // Current market status
     pub bump: u8,                    // PDA bump
+    pub has_book: bool,              // Whether a `MarketBook` prices this market's outcomes
+    pub tournament: Option<u64>,     // ID of the tournament this market is the outright or a match of
+    pub has_condition: bool,         // Whether a `MarketCondition` ties this market to a parent's result
+    pub has_prop: bool,              // Whether this is a `Prop` market, resolved from a player statistic
+    pub has_liquidity: bool,         // Whether a `LiquidityPool` has staked on this market's outcomes
+    pub has_fixed_odds: bool,        // Whether bets are taken by the house at each outcome's posted odds
+    pub max_liability: u64,          // Most the house may owe on any one outcome of a fixed-odds market, 0 for no limit
+    pub house_reserve: u64,          // What the house has reserved to pay this fixed-odds market's bets
+    pub unswept_escrows: u8,         // Losing escrows of a resolved market still to sweep, claims wait for none
+    pub rates_teams: bool,           // Whether resolving this head-to-head moves its teams' ratings
+    pub reserved: [u8; 0],           // Zeroed room for fields added by later versions
+}
+
+impl Market {
+    pub const SIZE: usize = 8 + Self::INIT_SPACE; // discriminator + fields
+    
+    /// Layout version written by this program
+    pub const VERSION: u8 = 1;
+    
+    /// Size of a market with `outcome_count` outcomes. Book markets grow
+    /// by a byte for each outcome past `MAX_OUTCOMES`.
+    pub fn space_for(outcome_count: usize) -> usize {
+        Self::SIZE + outcome_count.saturating_sub(MAX_OUTCOMES)
+    }
+    
+    /// Most outcomes this market can have
+    pub fn max_outcomes(&self) -> usize {
+        if self.has_book { MAX_BOOK_OUTCOMES } else { MAX_OUTCOMES }
+    }
+    
+    /// Platform fee taken at settlement when `winning_stake` is staked on
+    /// the winning outcome, before any fee tier rebates. Markets charging
+    /// at bet time have nothing left to take, and fixed-odds markets have
+    /// their margin in the odds.
+    pub fn fee_amount(&self, winning_stake: u64) -> Result<u64> {
+        if self.has_fixed_odds {
+            return Ok(0);
+        }
+    
+        let fee_base = match self.fee_model {
+            FeeModel::TotalPool => self.total_pool,
+            FeeModel::NetWinnings => self.total_pool.saturating_sub(winning_stake),
+            FeeModel::AtBet => return Ok(0),
+        };
+        let fee = (fee_base as u128)
+            .checked_mul(self.fee_bps as u128)
+            .ok_or(PredictionMarketError::MathOverflow)?
+            / BPS_DENOMINATOR as u128;
+        Ok(fee as u64)
+    }
+    
+    /// Platform fee on a bet of `amount` placed on a market charging at bet
+    /// time, less the bettor's fee tier discount
+    pub fn bet_fee_amount(&self, amount: u64, fee_discount_bps: u16) -> Result<u64> {
+        if self.fee_model != FeeModel::AtBet {
+            return Ok(0);
+        }
+    
+        let fee = (amount as u128)
+            .checked_mul(self.fee_bps as u128)
+            .ok_or(PredictionMarketError::MathOverflow)?
+            / BPS_DENOMINATOR as u128;
+        let discount = fee * fee_discount_bps as u128 / BPS_DENOMINATOR as u128;
+        Ok((fee - discount) as u64)
+    }
+    
+    /// Part of the platform fee rebated on `discounted_stake` of the
+    /// `winning_stake`. Rounds down.
+    pub fn fee_rebate(&self, discounted_stake: u64, winning_stake: u64) -> Result<u64> {
+        if winning_stake == 0 {
+            return Ok(0);
+        }
+    
+        let rebate = (self.fee_amount(winning_stake)? as u128)
+            .checked_mul(discounted_stake as u128)
+            .ok_or(PredictionMarketError::MathOverflow)?
+            / winning_stake as u128;
+        u64::try_from(rebate).map_err(|_| PredictionMarketError::MathOverflow.into())
+    }
+    
+    /// Creator's fee taken from the whole pool at settlement. Fixed-odds
+    /// markets have no pool to take it from.
+    pub fn creator_fee_amount(&self) -> Result<u64> {
+        if self.has_fixed_odds {
+            return Ok(0);
+        }
+    
+        let fee = (self.total_pool as u128)
+            .checked_mul(self.creator_fee_bps as u128)
+            .ok_or(PredictionMarketError::MathOverflow)?
+            / BPS_DENOMINATOR as u128;
+        Ok(fee as u64)
+    }
+    
+    /// What is left of the pool for winners once both fees are taken,
+    /// before fee tier rebates
+    pub fn distributable_pool(&self, winning_stake: u64) -> Result<u64> {
+        let fees = self.fee_amount(winning_stake)?
+            .checked_add(self.creator_fee_amount()?)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        self.total_pool
+            .checked_sub(fees)
+            .ok_or(PredictionMarketError::MathOverflow.into())
+    }
+    
+    /// Payout for `amount` staked on the winning outcome, proportional to
+    /// its share of `winning_stake`. Rounds down.
+    pub fn payout(&self, amount: u64, winning_stake: u64) -> Result<u64> {
+        require!(
+            winning_stake > 0,
+            PredictionMarketError::InsufficientLiquidity
+        );
+    
+        let payout = (amount as u128)
+            .checked_mul(self.distributable_pool(winning_stake)? as u128)
+            .ok_or(PredictionMarketError::MathOverflow)?
+            .checked_div(winning_stake as u128)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+    
+        u64::try_from(payout).map_err(|_| PredictionMarketError::MathOverflow.into())
+    }
+    
+    /// Referrers' part of `fee_amount`: the referral share of the fee
+    /// attributable to referred volume
+    pub fn referral_fee_amount(&self, fee_amount: u64) -> Result<u64> {
+        if self.total_pool == 0 {
+            return Ok(0);
+        }
+    
+        // Transfer-fee mints can settle on less than was credited
+        let referred_volume = self.referred_volume.min(self.total_pool);
+        let referral_fees = (fee_amount as u128)
+            .checked_mul(self.referral_share_bps as u128)
+            .ok_or(PredictionMarketError::MathOverflow)?
+            .checked_mul(referred_volume as u128)
+            .ok_or(PredictionMarketError::MathOverflow)?
+            / (BPS_DENOMINATOR as u128 * self.total_pool as u128);
+        Ok(referral_fees as u64)
+    }
+    
+    /// Referral fees owed to the next referrer to claim, proportional to
+    /// their `volume`. The last referrer receives the exact remainder.
+    pub fn referral_claim_amount(&self, volume: u64) -> Result<u64> {
+        if self.referrers_remaining == 1 {
+            return self.referral_fees
+                .checked_sub(self.referral_fees_claimed)
+                .ok_or(PredictionMarketError::MathOverflow.into());
+        }
+    
+        let amount = (volume as u128)
+            .checked_mul(self.referral_fees as u128)
+            .ok_or(PredictionMarketError::MathOverflow)?
+            .checked_div(self.referred_volume as u128)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        u64::try_from(amount).map_err(|_| PredictionMarketError::MathOverflow.into())
+    }
+    
+    /// Amount owed for `position` on `winning_outcome`: its payout plus its
+    /// fee tier rebate. Both round down, so the last claimer receives the
+    /// exact remainder of the distributable pool and rebates, and the
+    /// escrow ends empty.
+    pub fn claim_amount(&self, position: &UserPosition, winning_outcome: &Outcome) -> Result<u64> {
+        self.stake_claim_amount(position.amount, position.discounted_amount, winning_outcome)
+    }
+    
+    /// Amount owed for `amount` staked on `winning_outcome`, of which
+    /// `discounted_amount` earns a fee tier rebate, as `claim_amount`
+    pub fn stake_claim_amount(&self, amount: u64, discounted_amount: u64, winning_outcome: &Outcome) -> Result<u64> {
+        if winning_outcome.claimants_remaining == 1 {
+            return self.distributable_pool(winning_outcome.total_staked)?
+                .checked_add(self.fee_rebates)
+                .ok_or(PredictionMarketError::MathOverflow)?
+                .checked_sub(winning_outcome.total_claimed)
+                .ok_or(PredictionMarketError::MathOverflow.into());
+        }
+    
+        let payout = self.payout(amount, winning_outcome.total_staked)?;
+        let rebate = self.fee_rebate(discounted_amount, winning_outcome.total_staked)?;
+        payout
+            .checked_add(rebate)
+            .ok_or(PredictionMarketError::MathOverflow.into())
+    }
+    
+    /// Decimal odds, scaled by 10000 like `Outcome::odds`, of an outcome
+    /// with `outcome_stake` staked on it: what each unit a bettor spends
+    /// returns if the outcome wins and the pool stays as it is, net of the
+    /// fees of this market's fee model. Zero while the outcome has no stake.
+    pub fn odds(&self, outcome_stake: u64) -> Result<u64> {
+        if outcome_stake == 0 {
+            return Ok(0);
+        }
+    
+        let mut odds = (self.distributable_pool(outcome_stake)? as u128)
+            .checked_mul(BPS_DENOMINATOR as u128)
+            .ok_or(PredictionMarketError::MathOverflow)?
+            / outcome_stake as u128;
+        // Bets charged at bet time only stake what is left after the fee
+        if self.fee_model == FeeModel::AtBet {
+            odds = odds * BPS_DENOMINATOR.saturating_sub(self.fee_bps as u64) as u128 / BPS_DENOMINATOR as u128;
+        }
+        u64::try_from(odds).map_err(|_| PredictionMarketError::MathOverflow.into())
+    }
 }
\ No newline at end of file
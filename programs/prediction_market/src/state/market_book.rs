@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+use crate::errors::PredictionMarketError;
+use crate::state::{Market, BPS_DENOMINATOR, MAX_BOOK_OUTCOMES};
+
+/// Stake and price of every outcome of one market in a single zero-copy
+/// account, so large markets can be priced without loading each `Outcome`.
+/// Entries follow the order outcomes were added in.
+#[account(zero_copy)]
+pub struct MarketBook {
+    pub version: u8,                          // Layout version, see `MarketBook::VERSION`
+    pub bump: u8,                             // PDA bump
+    pub outcome_count: u8,                    // Entries in use
+    pub padding: [u8; 5],                     // Aligns the fields below
+    pub market: Pubkey,                       // The market this book prices
+    pub outcome_ids: [u8; MAX_BOOK_OUTCOMES], // Outcome ID of each entry
+    pub stakes: [u64; MAX_BOOK_OUTCOMES],     // Total staked on each entry's outcome
+    pub prices: [u64; MAX_BOOK_OUTCOMES],     // Decimal odds of each entry, scaled by 10000
+    pub eliminated: [u8; MAX_BOOK_OUTCOMES],  // Nonzero once the entry's outcome is eliminated
+    pub reserved: [u8; 16],                   // Zeroed room for fields added by later versions
+}
+
+impl MarketBook {
+    pub const SEED_PREFIX: &'static str = "market-book";
+    pub const SIZE: usize = 8 + std::mem::size_of::<MarketBook>(); // discriminator + fields
+    
+    /// Layout version written by this program
+    pub const VERSION: u8 = 1;
+    
+    fn index_of(&self, outcome_id: u8) -> Result<usize> {
+        self.outcome_ids[..self.outcome_count as usize]
+            .iter()
+            .position(|id| *id == outcome_id)
+            .ok_or(PredictionMarketError::OutcomeNotFound.into())
+    }
+    
+    pub fn add_outcome(&mut self, outcome_id: u8) -> Result<()> {
+        let index = self.outcome_count as usize;
+        require!(
+            index < MAX_BOOK_OUTCOMES,
+            PredictionMarketError::TooManyOutcomes
+        );
+    
+        self.outcome_ids[index] = outcome_id;
+        self.outcome_count += 1;
+        Ok(())
+    }
+    
+    /// Records `amount` staked on `outcome_id` and reprices every outcome
+    /// against `market`'s pool, which must already include the bet
+    pub fn record_bet(&mut self, market: &Market, outcome_id: u8, amount: u64) -> Result<()> {
+        let index = self.index_of(outcome_id)?;
+        self.stakes[index] = self.stakes[index].checked_add(amount)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        self.reprice(market)
+    }
+    
+    /// Records `amount` of stake withdrawn from `outcome_id` and reprices
+    /// every outcome against `market`'s pool, which must already exclude it
+    pub fn record_withdrawal(&mut self, market: &Market, outcome_id: u8, amount: u64) -> Result<()> {
+        let index = self.index_of(outcome_id)?;
+        self.stakes[index] = self.stakes[index].checked_sub(amount)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        self.reprice(market)
+    }
+    
+    fn reprice(&mut self, market: &Market) -> Result<()> {
+        for index in 0..self.outcome_count as usize {
+            self.prices[index] = if self.eliminated[index] != 0 {
+                0
+            } else {
+                market.odds(self.stakes[index])?
+            };
+        }
+        Ok(())
+    }
+    
+    /// Prices `outcome_id` at `odds` before any stake has set its price
+    pub fn seed_price(&mut self, outcome_id: u8, odds: u64) -> Result<()> {
+        let index = self.index_of(outcome_id)?;
+        self.prices[index] = odds;
+        Ok(())
+    }
+    
+    /// Prices an eliminated outcome at zero for good
+    pub fn eliminate(&mut self, outcome_id: u8) -> Result<()> {
+        let index = self.index_of(outcome_id)?;
+        self.eliminated[index] = 1;
+        self.prices[index] = 0;
+        Ok(())
+    }
+    
+    /// Prices the winning outcome at what each unit staked on it is paid,
+    /// and every other outcome at zero
+    pub fn settle(&mut self, market: &Market, winning_outcome_id: u8) -> Result<()> {
+        let winner = self.index_of(winning_outcome_id)?;
+        let winning_price = market.odds(self.stakes[winner])?;
+    
+        for index in 0..self.outcome_count as usize {
+            self.prices[index] = if index == winner { winning_price } else { 0 };
+        }
+        Ok(())
+    }
+    
+    /// Prices every staked outcome at a full refund
+    pub fn cancel(&mut self) {
+        for index in 0..self.outcome_count as usize {
+            self.prices[index] = if self.stakes[index] > 0 { BPS_DENOMINATOR } else { 0 };
+        }
+    }
+}
\ No newline at end of file
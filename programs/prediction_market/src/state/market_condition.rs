@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+use crate::state::{Market, MarketStatus};
+
+/// The parent result a conditional market depends on, such as "Team A wins
+/// Map 1" for "If Team A wins Map 1, who wins the series?"
+#[account]
+#[derive(Default, InitSpace)]
+pub struct MarketCondition {
+    pub version: u8,                  // Layout version, see `MarketCondition::VERSION`
+    pub market: Pubkey,               // The conditional market
+    pub parent_market: Pubkey,        // Market whose result decides whether it stands
+    pub parent_outcome_id: u8,        // Outcome the parent must resolve to
+    pub bump: u8,                     // PDA bump
+    pub reserved: [u8; 32],           // Zeroed room for fields added by later versions
+}
+
+impl MarketCondition {
+    pub const SEED_PREFIX: &'static str = "condition";
+    pub const SIZE: usize = 8 + Self::INIT_SPACE; // discriminator + fields
+    
+    /// Layout version written by this program
+    pub const VERSION: u8 = 1;
+    
+    /// Whether `parent` has finished and met the condition, or `None` while
+    /// it is still open
+    pub fn is_met(&self, parent: &Market) -> Option<bool> {
+        match parent.status {
+            MarketStatus::Resolved => Some(parent.winner == Some(self.parent_outcome_id)),
+            MarketStatus::Cancelled => Some(false),
+            _ => None,
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+use crate::errors::PredictionMarketError;
+
+/// Everything one user has staked on one market, across all of its
+/// outcomes, which the market's position limit applies to
+#[account]
+#[derive(Default, InitSpace)]
+pub struct MarketStake {
+    pub version: u8,                  // Layout version, see `MarketStake::VERSION`
+    pub market: Pubkey,               // Market the stakes are on
+    pub user: Pubkey,                 // Wallet that placed them
+    pub amount: u64,                  // Total sent in bets on the market
+    pub bump: u8,                     // PDA bump
+    pub reserved: [u8; 32],           // Zeroed room for fields added by later versions
+}
+
+impl MarketStake {
+    pub const SEED_PREFIX: &'static str = "market-stake";
+    pub const SIZE: usize = 8 + Self::INIT_SPACE; // discriminator + fields
+    
+    /// Layout version written by this program
+    pub const VERSION: u8 = 1;
+    
+    pub fn record_bet(&mut self, amount: u64) -> Result<()> {
+        self.amount = self.amount.checked_add(amount)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        Ok(())
+    }
+}
\ No newline at end of file
@@ -2,8 +2,43 @@ pub mod platform_config;
 pub mod market;
 pub mod outcome;
 pub mod user_position;
+pub mod market_stake;
+pub mod collateral_config;
+pub mod bet_limits;
+pub mod user_profile;
+pub mod allowlist_entry;
+pub mod credential;
+pub mod referrer;
+pub mod fee_tier;
+pub mod market_book;
+pub mod tournament;
+pub mod parlay;
+pub mod house;
+pub mod market_condition;
+pub mod team;
+pub mod player;
+pub mod prop;
+pub mod liquidity_pool;
+pub mod legacy;
 
 pub use platform_config::*;
 pub use market::*;
 pub use outcome::*;
-pub use user_position::*;
\ No newline at end of file
+pub use user_position::*;
+pub use market_stake::*;
+pub use collateral_config::*;
+pub use bet_limits::*;
+pub use user_profile::*;
+pub use allowlist_entry::*;
+pub use credential::*;
+pub use referrer::*;
+pub use fee_tier::*;
+pub use market_book::*;
+pub use tournament::*;
+pub use parlay::*;
+pub use house::*;
+pub use market_condition::*;
+pub use team::*;
+pub use player::*;
+pub use prop::*;
+pub use liquidity_pool::*;
\ No newline at end of file
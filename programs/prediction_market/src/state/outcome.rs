@@ -1,13 +1,43 @@
 use anchor_lang::prelude::*;
+use crate::errors::PredictionMarketError;
+
+/// Longest name an outcome can hold, in bytes
+pub const MAX_OUTCOME_NAME_LEN: usize = 50;
 
 #[account]
-#[derive(Default)]
+#[derive(Default, InitSpace)]
 pub struct Outcome {
+    pub version: u8,            // Layout version, see `Outcome::VERSION`
     pub id: u8,                 // Unique identifier within a market
     pub market: Pubkey,         // The market this outcome belongs to
+    #[max_len(MAX_OUTCOME_NAME_LEN)]
     pub name: String,           // Name of the outcome (e.g., "TSM wins")
     pub escrow_pubkey: Pubkey,  // PDA that holds staked tokens for this outcome
     pub total_staked: u64,      // Total tokens staked on this outcome
+    pub total_claimed: u64,     // Tokens paid out of the escrow so far
+    pub claimants_remaining: u32, // Positions on this outcome that have not claimed yet
+    pub discounted_stake: u64,  // Stake weighted by its bettors' fee tier discounts
     pub odds: u64,              // Current odds (represented as integer, actual odds = odds/10000)
     pub bump: u8,               // PDA bump
+    pub eliminated: bool,       // Knocked out of the tournament, so worthless and closed to bets
+    pub team: Option<u64>,      // ID of the `Team` this outcome backs, if it is a team's win
+    pub liability: u64,         // Payouts the house owes if this outcome wins, on fixed-odds markets
+    pub player: Option<u64>,    // ID of the `Player` this outcome backs, on a top player prop
+    pub reserved: [u8; 5],      // Zeroed room for fields added by later versions
+}
+
+impl Outcome {
+    pub const SIZE: usize = 8 + Self::INIT_SPACE; // discriminator + fields
+    
+    /// Layout version written by this program
+    pub const VERSION: u8 = 1;
+    
+    /// Records a payout or refund leaving this outcome's escrow
+    pub fn record_claim(&mut self, amount: u64) -> Result<()> {
+        self.total_claimed = self.total_claimed.checked_add(amount)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        self.claimants_remaining = self.claimants_remaining.checked_sub(1)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        Ok(())
+    }
 }
\ No newline at end of file
@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+use crate::errors::PredictionMarketError;
+use crate::state::BPS_DENOMINATOR;
+
+/// Most legs a parlay can hold
+pub const MAX_PARLAY_LEGS: usize = 8;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub enum LegResult {
+    #[default]
+    Pending,  // The leg's market has not finished
+    Won,      // The leg's outcome won
+    Lost,     // Another outcome won
+    Void,     // The market was cancelled, so the leg counts at even odds
+}
+
+/// One (market, outcome) pick of a parlay
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub struct ParlayLeg {
+    pub market: Pubkey,     // Market the pick is on
+    pub outcome_id: u8,     // Outcome picked to win
+    pub odds: u64,          // The outcome's odds when the parlay was placed, scaled by 10000
+    pub result: LegResult,  // Result once the market finishes
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub enum ParlayStatus {
+    #[default]
+    Open,     // Waiting on its legs
+    Won,      // Every leg won or was void, and the payout was sent
+    Lost,     // A leg lost, so the house keeps the stake
+    Void,     // Every leg was void, and the stake was refunded
+}
+
+/// A bet that several outcomes all win, at odds fixed when it was placed
+/// and paid by the house
+#[account]
+#[derive(Default, InitSpace)]
+pub struct Parlay {
+    pub version: u8,                  // Layout version, see `Parlay::VERSION`
+    pub id: u64,                      // Identifier chosen by the user, unique among their parlays
+    pub user: Pubkey,                 // Bettor, who receives the payout
+    pub house: Pubkey,                // House that took the bet
+    pub stake: u64,                   // Stake held in the house vault
+    pub payout: u64,                  // Payout if every leg wins, reserved in the house's liability
+    #[max_len(MAX_PARLAY_LEGS)]
+    pub legs: Vec<ParlayLeg>,         // Picks, in the order they were placed
+    pub status: ParlayStatus,         // Current status
+    pub paid: u64,                    // Amount sent to the user at settlement
+    pub bump: u8,                     // PDA bump
+    pub reserved: [u8; 32],           // Zeroed room for fields added by later versions
+}
+
+impl Parlay {
+    pub const SEED_PREFIX: &'static str = "parlay";
+    pub const SIZE: usize = 8 + Self::INIT_SPACE; // discriminator + fields
+    
+    /// Layout version written by this program
+    pub const VERSION: u8 = 1;
+    
+    /// What `stake` returns if legs at each of `odds` all win, rounding down
+    /// after every leg
+    pub fn payout_at(stake: u64, odds: impl IntoIterator<Item = u64>) -> Result<u64> {
+        let mut payout = stake as u128;
+        for odds in odds {
+            payout = payout
+                .checked_mul(odds as u128)
+                .ok_or(PredictionMarketError::MathOverflow)?
+                / BPS_DENOMINATOR as u128;
+        }
+        u64::try_from(payout).map_err(|_| PredictionMarketError::MathOverflow.into())
+    }
+    
+    /// Status and amount owed once every leg has a result: nothing if one
+    /// lost, otherwise the stake at the odds of the legs that won, with void
+    /// legs at even odds
+    pub fn settlement(&self) -> Result<(ParlayStatus, u64)> {
+        if self.legs.iter().any(|leg| leg.result == LegResult::Lost) {
+            return Ok((ParlayStatus::Lost, 0));
+        }
+        require!(
+            self.legs.iter().all(|leg| leg.result != LegResult::Pending),
+            PredictionMarketError::ParlayNotSettled
+        );
+    
+        let won = self.legs.iter().filter(|leg| leg.result == LegResult::Won);
+        let paid = Self::payout_at(self.stake, won.map(|leg| leg.odds))?;
+        if self.legs.iter().all(|leg| leg.result == LegResult::Void) {
+            Ok((ParlayStatus::Void, paid))
+        } else {
+            Ok((ParlayStatus::Won, paid))
+        }
+    }
+}
\ No newline at end of file
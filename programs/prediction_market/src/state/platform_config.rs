@@ -1,27 +1,49 @@
 use anchor_lang::prelude::*;
+use crate::state::{BetLimits, FeeTier};
 
 #[account]
-#[derive(Default)]
+#[derive(Default, InitSpace)]
 pub struct PlatformConfig {
+    pub version: u8,                  // Layout version, see `PlatformConfig::VERSION`
     pub admin: Pubkey,                // Platform administrator
     pub oracle_authority: Pubkey,     // Authority that can resolve markets
-    pub treasury: Pubkey,             // Treasury account to collect fees
-    pub default_fee_percentage: u8,   // Default platform fee (e.g., 2 for 2%)
+    pub default_fee_bps: u16,         // Default platform fee in basis points (e.g., 200 for 2%)
     pub markets_count: u64,           // Total number of markets created
     pub total_volume: u64,            // Total volume across all markets
     pub paused: bool,                 // Whether the platform is paused
+    pub default_bet_limits: BetLimits, // Limits copied to new markets
+    pub referral_share_bps: u16,      // Share of market fees paid to referrers, copied to new markets
+    pub max_creator_fee_bps: u16,     // Highest creator fee a market can charge
+    pub fee_tiers: [FeeTier; PlatformConfig::FEE_TIERS], // Volume-based fee discounts
     pub bump: u8,                     // PDA bump
+    pub tournaments_count: u64,       // Total number of tournaments created
+    pub teams_count: u64,             // Total number of teams registered
+    pub players_count: u64,           // Total number of players registered
+    pub lp_fee_share_bps: u16,        // Share of market fees paid to liquidity providers, copied to new pools
+    pub reserved: [u8; 6],            // Zeroed room for fields added by later versions
 }
 
 impl PlatformConfig {
     pub const SEED_PREFIX: &'static str = "platform-config";
-    pub const SIZE: usize = 8 + // discriminator
-                           32 + // admin
-                           32 + // oracle_authority
-                           32 + // treasury
-                           1 +  // default_fee_percentage
-                           8 +  // markets_count
-                           8 +  // total_volume
-                           1 +  // paused
-                           1;   // bump
+    pub const SIZE: usize = 8 + Self::INIT_SPACE; // discriminator + fields
+    
+    /// Layout version written by this program
+    pub const VERSION: u8 = 1;
+    
+    /// Highest platform fee a market can charge, in basis points
+    pub const MAX_FEE_BPS: u16 = 1_000;
+    
+    /// Number of fee tier slots
+    pub const FEE_TIERS: usize = 4;
+    
+    /// Discount on the platform fee for a user with lifetime `volume`: the
+    /// best tier they qualify for
+    pub fn fee_discount_bps(&self, volume: u64) -> u16 {
+        self.fee_tiers
+            .iter()
+            .filter(|tier| volume >= tier.min_volume)
+            .map(|tier| tier.discount_bps)
+            .max()
+            .unwrap_or(0)
+    }
 }
\ No newline at end of file
@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+/// Longest handle a player can hold, in bytes
+pub const MAX_PLAYER_HANDLE_LEN: usize = 32;
+
+/// A player in the on-chain registry, which prop markets refer to
+#[account]
+#[derive(Default, InitSpace)]
+pub struct Player {
+    pub version: u8,                  // Layout version, see `Player::VERSION`
+    pub id: u64,                      // Unique identifier
+    pub team: Pubkey,                 // `Team` the player plays for
+    #[max_len(MAX_PLAYER_HANDLE_LEN)]
+    pub handle: String,               // In-game name of the player (e.g., "TenZ")
+    pub bump: u8,                     // PDA bump
+    pub reserved: [u8; 32],           // Zeroed room for fields added by later versions
+}
+
+impl Player {
+    pub const SEED_PREFIX: &'static str = "player";
+    pub const SIZE: usize = 8 + Self::INIT_SPACE; // discriminator + fields
+    
+    /// Layout version written by this program
+    pub const VERSION: u8 = 1;
+}
\ No newline at end of file
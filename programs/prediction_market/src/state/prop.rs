@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use std::cmp::Ordering;
+
+/// Player statistic a prop is on
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub enum StatKind {
+    #[default]
+    Kills,        // Kills
+    Acs,          // Average combat score
+    FirstBloods,  // First kills of a round
+    Clutches,     // Rounds won as the last player alive
+}
+
+impl StatKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            StatKind::Kills => "kills",
+            StatKind::Acs => "ACS",
+            StatKind::FirstBloods => "first bloods",
+            StatKind::Clutches => "clutches",
+        }
+    }
+}
+
+/// What a prop market is on
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub enum PropKind {
+    #[default]
+    Line,         // One player's statistic over or under a line
+    TopPlayer,    // Which of several players posts the highest statistic
+}
+
+/// A prop market on a player statistic. A line prop, such as "TenZ over
+/// 20.5 kills on Ascent", has exactly two outcomes, over and under. A top
+/// player prop, such as "Top ACS of the match", has one outcome per player.
+#[account]
+#[derive(Default, InitSpace)]
+pub struct Prop {
+    pub version: u8,                  // Layout version, see `Prop::VERSION`
+    pub market: Pubkey,               // The prop market
+    pub player: Pubkey,               // `Player` a line prop is on
+    pub stat: StatKind,               // Statistic the prop is on
+    pub line: u64,                    // Line in tenths (e.g., 205 for 20.5), on a line prop
+    pub stat_value: Option<u64>,      // Statistic the oracle reported, once resolved
+    pub bump: u8,                     // PDA bump
+    pub kind: PropKind,               // Line or top player
+    pub reserved: [u8; 31],           // Zeroed room for fields added by later versions
+}
+
+impl Prop {
+    pub const SEED_PREFIX: &'static str = "prop";
+    pub const SIZE: usize = 8 + Self::INIT_SPACE; // discriminator + fields
+    
+    /// Layout version written by this program
+    pub const VERSION: u8 = 1;
+    
+    /// Outcome ID of the statistic ending above the line
+    pub const OVER: u8 = 0;
+    
+    /// Outcome ID of the statistic ending below the line
+    pub const UNDER: u8 = 1;
+    
+    /// Names of the over and under outcomes, e.g. "Over 20.5 kills"
+    pub fn outcome_names(&self) -> [String; 2] {
+        let line = format!("{}.{} {}", self.line / 10, self.line % 10, self.stat.label());
+        [format!("Over {}", line), format!("Under {}", line)]
+    }
+    
+    /// Name of a top player prop's outcome for `handle`, e.g. "TenZ top ACS"
+    pub fn player_outcome_name(&self, handle: &str) -> String {
+        format!("{} top {}", handle, self.stat.label())
+    }
+    
+    /// The outcome a final `stat_value` wins, or `None` if it lands exactly
+    /// on the line
+    pub fn winning_outcome(&self, stat_value: u64) -> Option<u8> {
+        match (stat_value as u128 * 10).cmp(&(self.line as u128)) {
+            Ordering::Greater => Some(Self::OVER),
+            Ordering::Less => Some(Self::UNDER),
+            Ordering::Equal => None,
+        }
+    }
+}
\ No newline at end of file
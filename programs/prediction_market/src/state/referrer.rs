@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+use crate::errors::PredictionMarketError;
+
+/// Volume a referrer brought to one market, and their claim on its
+/// referral fees
+#[account]
+#[derive(Default, InitSpace)]
+pub struct Referrer {
+    pub version: u8,                  // Layout version, see `Referrer::VERSION`
+    pub market: Pubkey,               // Market the volume was placed on
+    pub referrer: Pubkey,             // Wallet that earns the rewards
+    pub volume: u64,                  // Stake placed through this referrer
+    pub bump: u8,                     // PDA bump
+    pub reserved: [u8; 32],           // Zeroed room for fields added by later versions
+}
+
+impl Referrer {
+    pub const SEED_PREFIX: &'static str = "referrer";
+    pub const SIZE: usize = 8 + Self::INIT_SPACE; // discriminator + fields
+    
+    /// Layout version written by this program
+    pub const VERSION: u8 = 1;
+    
+    pub fn record_volume(&mut self, amount: u64) -> Result<()> {
+        self.volume = self.volume.checked_add(amount)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        Ok(())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,113 @@
+use anchor_lang::prelude::*;
+use crate::state::BPS_DENOMINATOR;
+
+/// Longest name, tag and logo URI a team can hold, in bytes
+pub const MAX_TEAM_NAME_LEN: usize = 32;
+pub const MAX_TEAM_TAG_LEN: usize = 8;
+pub const MAX_LOGO_URI_LEN: usize = 200;
+
+/// Competitive region a team or tournament belongs to
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub enum Region {
+    #[default]
+    International, // Not tied to one region (e.g., Masters, Champions)
+    Americas,      // North and South America
+    Emea,          // Europe, Middle East and Africa
+    Pacific,       // Asia-Pacific
+    China,         // China
+}
+
+/// A team in the on-chain registry, which players, outcomes and markets
+/// refer to
+#[account]
+#[derive(Default, InitSpace)]
+pub struct Team {
+    pub version: u8,                  // Layout version, see `Team::VERSION`
+    pub id: u64,                      // Unique identifier
+    #[max_len(MAX_TEAM_NAME_LEN)]
+    pub name: String,                 // Name of the team (e.g., "Sentinels")
+    #[max_len(MAX_TEAM_TAG_LEN)]
+    pub tag: String,                  // Short tag shown on scoreboards (e.g., "SEN")
+    pub region: Region,               // Region the team competes in
+    #[max_len(MAX_LOGO_URI_LEN)]
+    pub logo_uri: String,             // URI of the team's logo
+    pub rating: u32,                  // Elo rating, updated as head-to-head markets resolve
+    pub bump: u8,                     // PDA bump
+    pub reserved: [u8; 32],           // Zeroed room for fields added by later versions
+}
+
+impl Team {
+    pub const SEED_PREFIX: &'static str = "team";
+    pub const SIZE: usize = 8 + Self::INIT_SPACE; // discriminator + fields
+    
+    /// Layout version written by this program
+    pub const VERSION: u8 = 1;
+    
+    /// Rating every team starts from
+    pub const INITIAL_RATING: u32 = 1500;
+    
+    /// Most rating points a single result can move
+    pub const RATING_K: u32 = 32;
+    
+    /// Rating differences beyond this count as this, as in FIDE's rules
+    pub const MAX_RATING_DIFFERENCE: u32 = 800;
+    
+    /// Expected score `1 / (1 + 10^(-d / 400))` in basis points for rating
+    /// differences `d` of 0, 25, ..., 800
+    const EXPECTED_SCORES: [u64; 33] = [
+        5000, 5359, 5715, 6063, 6401, 6725, 7034, 7325, 7597, 7850, 8083, 8296, 8490, 8666, 8823, 8965,
+        9091, 9203, 9302, 9390, 9468, 9536, 9595, 9648, 9693, 9733, 9768, 9799, 9825, 9848, 9868, 9886,
+        9901,
+    ];
+    
+    /// Chance, in basis points, of a team rated `rating` beating one rated
+    /// `opponent`, interpolated between `EXPECTED_SCORES`. The two teams'
+    /// chances always add up to 10000.
+    pub fn expected_score_bps(rating: u32, opponent: u32) -> u64 {
+        let difference = rating.abs_diff(opponent).min(Self::MAX_RATING_DIFFERENCE) as u64;
+        let index = (difference / 25) as usize;
+        let low = Self::EXPECTED_SCORES[index];
+        let high = Self::EXPECTED_SCORES[(index + 1).min(Self::EXPECTED_SCORES.len() - 1)];
+        let favourite = low + (high - low) * (difference % 25) / 25;
+        if rating >= opponent { favourite } else { BPS_DENOMINATOR - favourite }
+    }
+    
+    /// Points the winner of a head-to-head result takes from the loser:
+    /// `K * (1 - E)`, with `E` the winner's expected score, rounded to the
+    /// nearest point
+    pub fn rating_change(winner_rating: u32, loser_rating: u32) -> u32 {
+        let upset_bps = BPS_DENOMINATOR - Self::expected_score_bps(winner_rating, loser_rating);
+        ((Self::RATING_K as u64 * upset_bps + BPS_DENOMINATOR / 2) / BPS_DENOMINATOR) as u32
+    }
+    
+    /// Fair decimal odds, scaled by 10000 like `Outcome::odds`, of a team
+    /// rated `rating` against one rated `opponent`
+    pub fn seeded_odds(rating: u32, opponent: u32) -> u64 {
+        BPS_DENOMINATOR * BPS_DENOMINATOR / Self::expected_score_bps(rating, opponent)
+    }
+}
+
+/// Index of an outcome that backs a team, one per outcome. Its fields sit
+/// at fixed offsets, so a `memcmp` on `team` finds every outcome, and so
+/// every market, a team plays in.
+#[account]
+#[derive(Default, InitSpace)]
+pub struct TeamOutcome {
+    pub version: u8,                  // Layout version, see `TeamOutcome::VERSION`
+    pub team: u64,                    // ID of the `Team` the outcome backs
+    pub market: Pubkey,               // Market the outcome belongs to
+    pub outcome: Pubkey,              // The outcome
+    pub bump: u8,                     // PDA bump
+    pub reserved: [u8; 16],           // Zeroed room for fields added by later versions
+}
+
+impl TeamOutcome {
+    pub const SEED_PREFIX: &'static str = "team_outcome";
+    pub const SIZE: usize = 8 + Self::INIT_SPACE; // discriminator + fields
+    
+    /// Layout version written by this program
+    pub const VERSION: u8 = 1;
+    
+    /// Offset of `team` in the account data, for `memcmp` filters
+    pub const TEAM_OFFSET: usize = 8 + 1;
+}
\ No newline at end of file
@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+use crate::errors::PredictionMarketError;
+use crate::state::Region;
+
+/// Most match markets a tournament can link
+pub const MAX_TOURNAMENT_MATCHES: usize = 64;
+
+/// Longest name a tournament can hold, in bytes
+pub const MAX_TOURNAMENT_NAME_LEN: usize = 64;
+
+/// Level of play a tournament is at
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub enum TournamentTier {
+    #[default]
+    International, // Masters and Champions
+    League,        // Regional leagues feeding international events
+    Challengers,   // Second-tier regional circuits
+    Other,         // Third-party and showmatch events
+}
+
+/// A tournament's outright market on its winner and the match markets
+/// played along the way
+#[account]
+#[derive(Default, InitSpace)]
+pub struct Tournament {
+    pub version: u8,                  // Layout version, see `Tournament::VERSION`
+    pub id: u64,                      // Unique identifier
+    #[max_len(MAX_TOURNAMENT_NAME_LEN)]
+    pub name: String,                 // Name of the tournament (e.g., "Valorant Champions 2025")
+    pub tier: TournamentTier,         // Level of play
+    pub region: Region,               // Region it is played in, or international
+    pub start_date: i64,              // First day of play (unix timestamp)
+    pub end_date: i64,                // Last day of play (unix timestamp)
+    pub outright_market: Pubkey,      // Market on who wins the tournament
+    #[max_len(MAX_TOURNAMENT_MATCHES)]
+    pub matches: Vec<Pubkey>,         // Match markets linked so far, grand final included
+    pub grand_final: Option<Pubkey>,  // Match market whose result decides the outright
+    pub eliminated_count: u8,         // Outright outcomes knocked out so far
+    pub bump: u8,                     // PDA bump
+    pub reserved: [u8; 32],           // Zeroed room for fields added by later versions
+}
+
+impl Tournament {
+    pub const SEED_PREFIX: &'static str = "tournament";
+    pub const SIZE: usize = 8 + Self::INIT_SPACE; // discriminator + fields
+    
+    /// Layout version written by this program
+    pub const VERSION: u8 = 1;
+    
+    /// Adds `match_market` to the tournament's matches
+    pub fn link_match(&mut self, match_market: Pubkey) -> Result<()> {
+        require!(
+            self.matches.len() < MAX_TOURNAMENT_MATCHES,
+            PredictionMarketError::TooManyTournamentMatches
+        );
+    
+        self.matches.push(match_market);
+        Ok(())
+    }
+}
+
+/// Index of a market linked to a tournament, as its outright or one of its
+/// matches. Its fields sit at fixed offsets, so a `memcmp` on `tournament`
+/// finds every market of an event.
+#[account]
+#[derive(Default, InitSpace)]
+pub struct TournamentMarket {
+    pub version: u8,                  // Layout version, see `TournamentMarket::VERSION`
+    pub tournament: u64,              // ID of the `Tournament`
+    pub market: Pubkey,               // The linked market
+    pub bump: u8,                     // PDA bump
+    pub reserved: [u8; 16],           // Zeroed room for fields added by later versions
+}
+
+impl TournamentMarket {
+    pub const SEED_PREFIX: &'static str = "tournament_market";
+    pub const SIZE: usize = 8 + Self::INIT_SPACE; // discriminator + fields
+    
+    /// Layout version written by this program
+    pub const VERSION: u8 = 1;
+    
+    /// Offset of `tournament` in the account data, for `memcmp` filters
+    pub const TOURNAMENT_OFFSET: usize = 8 + 1;
+}
\ No newline at end of file
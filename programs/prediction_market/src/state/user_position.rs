@@ -1,14 +1,25 @@
 use anchor_lang::prelude::*;
 
 #[account]
-#[derive(Default)]
+#[derive(Default, InitSpace)]
 pub struct UserPosition {
+    pub version: u8,            // Layout version, see `UserPosition::VERSION`
     pub user: Pubkey,           // User's wallet address
     pub market: Pubkey,         // Market PDA address
     pub outcome: u8,            // ID of the outcome they bet on
     pub amount: u64,            // Amount of tokens staked
     pub shares: u64,            // Shares received (for AMM-based odds)
+    pub discounted_amount: u64, // Amount weighted by the fee tier discount at each bet
     pub timestamp: i64,         // When the position was created
     pub claimed: bool,          // Whether winnings have been claimed
     pub bump: u8,               // PDA bump
+    pub payout: u64,            // Payout locked in if the outcome wins, on fixed-odds markets
+    pub reserved: [u8; 24],     // Zeroed room for fields added by later versions
+}
+
+impl UserPosition {
+    pub const SIZE: usize = 8 + Self::INIT_SPACE; // discriminator + fields
+    
+    /// Layout version written by this program
+    pub const VERSION: u8 = 1;
 }
\ No newline at end of file
@@ -0,0 +1,131 @@
+use anchor_lang::prelude::*;
+use crate::errors::PredictionMarketError;
+
+/// Responsible-gaming settings for one user, checked on every bet in every
+/// market. Deposit limits count collateral base units; zero means no limit.
+#[account]
+#[derive(Default, InitSpace)]
+pub struct UserProfile {
+    pub version: u8,                  // Layout version, see `UserProfile::VERSION`
+    pub user: Pubkey,                 // User's wallet address
+    pub self_excluded_until: i64,     // No bets are accepted before this time
+    pub exclusion_set_at: i64,        // When the exclusion was last changed
+    pub daily_deposit_limit: u64,     // Most the user can bet per UTC day
+    pub weekly_deposit_limit: u64,    // Most the user can bet per week, from Monday UTC
+    pub limits_set_at: i64,           // When the deposit limits were last changed
+    pub current_day: i64,             // Day index `deposited_today` counts
+    pub deposited_today: u64,         // Amount bet during `current_day`
+    pub current_week: i64,            // Week index `deposited_this_week` counts
+    pub deposited_this_week: u64,     // Amount bet during `current_week`
+    pub total_volume: u64,            // Lifetime amount bet, which sets the fee tier
+    pub bump: u8,                     // PDA bump
+    pub reserved: [u8; 32],           // Zeroed room for fields added by later versions
+}
+
+impl UserProfile {
+    pub const SEED_PREFIX: &'static str = "user-profile";
+    pub const SIZE: usize = 8 + Self::INIT_SPACE; // discriminator + fields
+    
+    /// Layout version written by this program
+    pub const VERSION: u8 = 1;
+    
+    /// How long an exclusion or a set of limits must stand before it can be
+    /// loosened. Tightening always applies immediately.
+    pub const COOLDOWN: i64 = 7 * DAY;
+    
+    pub fn is_excluded(&self, now: i64) -> bool {
+        now < self.self_excluded_until
+    }
+    
+    /// Sets the exclusion to end at `until`. Extending it always works;
+    /// shortening or lifting an active exclusion waits out the cooldown.
+    pub fn set_self_exclusion(&mut self, until: i64, now: i64) -> Result<()> {
+        if until < self.self_excluded_until && self.is_excluded(now) {
+            require!(
+                now >= self.exclusion_set_at.saturating_add(Self::COOLDOWN),
+                PredictionMarketError::ProfileCooldownActive
+            );
+        }
+    
+        self.self_excluded_until = until;
+        self.exclusion_set_at = now;
+        Ok(())
+    }
+    
+    /// Replaces the deposit limits. Raising or removing a limit waits out
+    /// the cooldown since the limits were last changed.
+    pub fn set_deposit_limits(&mut self, daily: u64, weekly: u64, now: i64) -> Result<()> {
+        if loosens(self.daily_deposit_limit, daily) || loosens(self.weekly_deposit_limit, weekly) {
+            require!(
+                now >= self.limits_set_at.saturating_add(Self::COOLDOWN),
+                PredictionMarketError::ProfileCooldownActive
+            );
+        }
+    
+        self.daily_deposit_limit = daily;
+        self.weekly_deposit_limit = weekly;
+        self.limits_set_at = now;
+        Ok(())
+    }
+    
+    /// Counts a deposit of `amount`, a bet or liquidity, against the user's
+    /// exclusion and limits
+    pub fn record_deposit(&mut self, amount: u64, now: i64) -> Result<()> {
+        require!(
+            !self.is_excluded(now),
+            PredictionMarketError::SelfExcluded
+        );
+    
+        let day = now.div_euclid(DAY);
+        if day != self.current_day {
+            self.current_day = day;
+            self.deposited_today = 0;
+        }
+        let week = week_index(now);
+        if week != self.current_week {
+            self.current_week = week;
+            self.deposited_this_week = 0;
+        }
+    
+        let deposited_today = self.deposited_today.checked_add(amount)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        let deposited_this_week = self.deposited_this_week.checked_add(amount)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        require!(
+            self.daily_deposit_limit == 0 || deposited_today <= self.daily_deposit_limit,
+            PredictionMarketError::DailyDepositLimitExceeded
+        );
+        require!(
+            self.weekly_deposit_limit == 0 || deposited_this_week <= self.weekly_deposit_limit,
+            PredictionMarketError::WeeklyDepositLimitExceeded
+        );
+    
+        self.deposited_today = deposited_today;
+        self.deposited_this_week = deposited_this_week;
+        Ok(())
+    }
+    
+    /// Records a bet of `amount` as a deposit and towards the fee tier.
+    /// Liquidity can be withdrawn before the market closes, so only bets
+    /// count as volume.
+    pub fn record_bet(&mut self, amount: u64, now: i64) -> Result<()> {
+        self.record_deposit(amount, now)?;
+        self.total_volume = self.total_volume.checked_add(amount)
+            .ok_or(PredictionMarketError::MathOverflow)?;
+        Ok(())
+    }
+}
+
+const DAY: i64 = 24 * 60 * 60;
+const WEEK: i64 = 7 * DAY;
+
+/// The unix epoch fell on a Thursday, so shift by three days to start weeks
+/// on Monday
+fn week_index(now: i64) -> i64 {
+    now.saturating_add(3 * DAY).div_euclid(WEEK)
+}
+
+/// Whether replacing limit `old` with `new` allows more, zero being no limit
+fn loosens(old: u64, new: u64) -> bool {
+    old != 0 && (new == 0 || new > old)
+}
\ No newline at end of file
@@ -0,0 +1,153 @@
+//! Gated markets: only users on the admin's allowlist, or holding a
+//! credential from the market's attestation authority, can bet.
+
+mod common;
+
+use common::*;
+use prediction_market::errors::PredictionMarketError;
+use prediction_market::instruction;
+use prediction_market::state::{AccessGate, Market};
+use solana_program_test::BanksClientError;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+async fn place_gated_bet(
+    env: &mut TestEnv,
+    user: &User,
+    market: Pubkey,
+    allowlist_entry: Option<Pubkey>,
+    credential: Option<Pubkey>,
+) -> Result<(), BanksClientError> {
+    let mut accounts = env.place_bet_accounts(user, market, 0);
+    accounts.allowlist_entry = allowlist_entry;
+    accounts.credential = credential;
+    env.send(accounts, instruction::PlaceBet { amount: 100 }, &[&user.keypair]).await
+}
+
+#[tokio::test]
+async fn open_markets_need_no_extra_accounts() {
+    let mut env = TestEnv::new().await;
+    let market = env.create_market_with_outcomes(2).await;
+    assert_eq!(env.account::<Market>(market).await.access_gate, AccessGate::Open);
+
+    let user = env.create_user(100).await;
+    env.place_bet(&user, market, 0, 100).await.unwrap();
+}
+
+#[tokio::test]
+async fn allowlist_gate() {
+    let mut env = TestEnv::new().await;
+    let market = env.create_market_with_outcomes(2).await;
+    env.set_access_gate(market, AccessGate::Allowlist).await.unwrap();
+
+    let verified = env.create_user(1_000).await;
+    let unverified = env.create_user(1_000).await;
+    env.add_to_allowlist(verified.pubkey()).await.unwrap();
+
+    let entry = allowlist_entry_address(verified.pubkey());
+    place_gated_bet(&mut env, &verified, market, Some(entry), None).await.unwrap();
+
+    let result = env.place_bet(&unverified, market, 0, 100).await;
+    assert_program_error(result, PredictionMarketError::NotAllowlisted);
+
+    // Someone else's entry does not verify the signer
+    let result = place_gated_bet(&mut env, &unverified, market, Some(entry), None).await;
+    assert_program_error(result, PredictionMarketError::NotAllowlisted);
+
+    // Removed users can no longer bet
+    env.send_as_admin(
+        prediction_market::accounts::RemoveFromAllowlist {
+            platform_config: platform_config(),
+            admin: env.admin.pubkey(),
+            allowlist_entry: entry,
+        },
+        instruction::RemoveFromAllowlist {},
+    )
+    .await
+    .unwrap();
+    assert!(!env.account_exists(entry).await);
+    let result = env.place_bet(&verified, market, 0, 100).await;
+    assert_program_error(result, PredictionMarketError::NotAllowlisted);
+}
+
+#[tokio::test]
+async fn attestation_gate() {
+    let mut env = TestEnv::new().await;
+    let market = env.create_market_with_outcomes(2).await;
+    let attestor = Keypair::new();
+    let impostor = Keypair::new();
+    env.airdrop(&attestor.pubkey(), 1_000_000_000).await;
+    env.airdrop(&impostor.pubkey(), 1_000_000_000).await;
+    env.set_access_gate(market, AccessGate::Attestation { authority: attestor.pubkey() })
+        .await
+        .unwrap();
+
+    let user = env.create_user(1_000).await;
+    let now = env.now().await;
+
+    // A credential from another authority is not accepted
+    env.issue_credential(&impostor, user.pubkey(), 0).await.unwrap();
+    let forged = credential_address(impostor.pubkey(), user.pubkey());
+    let result = place_gated_bet(&mut env, &user, market, None, Some(forged)).await;
+    assert_program_error(result, PredictionMarketError::InvalidCredential);
+
+    env.issue_credential(&attestor, user.pubkey(), now + 60).await.unwrap();
+    let credential = credential_address(attestor.pubkey(), user.pubkey());
+    place_gated_bet(&mut env, &user, market, None, Some(credential)).await.unwrap();
+
+    // Expired credentials stop working until renewed
+    env.warp_to(now + 60).await;
+    let result = place_gated_bet(&mut env, &user, market, None, Some(credential)).await;
+    assert_program_error(result, PredictionMarketError::InvalidCredential);
+
+    env.issue_credential(&attestor, user.pubkey(), 0).await.unwrap();
+    place_gated_bet(&mut env, &user, market, None, Some(credential)).await.unwrap();
+
+    // Revoked credentials are gone
+    env.send(
+        prediction_market::accounts::RevokeCredential {
+            authority: attestor.pubkey(),
+            credential,
+        },
+        instruction::RevokeCredential {},
+        &[&attestor],
+    )
+    .await
+    .unwrap();
+    let result = env.place_bet(&user, market, 0, 100).await;
+    assert_program_error(result, PredictionMarketError::InvalidCredential);
+}
+
+#[tokio::test]
+async fn gates_are_admin_only() {
+    let mut env = TestEnv::new().await;
+    let market = env.create_market().await;
+    let stranger = env.create_user(0).await;
+
+    let result = env
+        .send(
+            prediction_market::accounts::SetAccessGate {
+                market,
+                platform_config: platform_config(),
+                admin: stranger.pubkey(),
+            },
+            instruction::SetAccessGate { access_gate: AccessGate::Open },
+            &[&stranger.keypair],
+        )
+        .await;
+    assert_program_error(result, PredictionMarketError::UnauthorizedAdmin);
+
+    let result = env
+        .send(
+            prediction_market::accounts::AddToAllowlist {
+                platform_config: platform_config(),
+                admin: stranger.pubkey(),
+                allowlist_entry: allowlist_entry_address(stranger.pubkey()),
+                system_program: solana_system_interface::program::ID,
+            },
+            instruction::AddToAllowlist { user: stranger.pubkey() },
+            &[&stranger.keypair],
+        )
+        .await;
+    assert_program_error(result, PredictionMarketError::UnauthorizedAdmin);
+}
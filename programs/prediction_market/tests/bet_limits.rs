@@ -0,0 +1,102 @@
+//! Betting limits: platform defaults copied to new markets, per-market
+//! overrides, and the checks `place_bet` applies against them.
+
+mod common;
+
+use common::*;
+use prediction_market::errors::PredictionMarketError;
+use prediction_market::state::{BetLimits, Market, MarketStake, PlatformConfig};
+
+const LIMITS: BetLimits = BetLimits {
+    min_bet: 10,
+    max_bet: 100,
+    max_position: 150,
+    max_pool: 250,
+};
+
+#[tokio::test]
+async fn new_markets_copy_platform_defaults() {
+    let mut env = TestEnv::new().await;
+    let before = env.create_market().await;
+
+    env.set_default_bet_limits(LIMITS).await.unwrap();
+    let config: PlatformConfig = env.platform_config().await;
+    assert_eq!(config.default_bet_limits, LIMITS);
+
+    let after = env.create_market().await;
+    assert_eq!(env.account::<Market>(before).await.bet_limits, BetLimits::default());
+    assert_eq!(env.account::<Market>(after).await.bet_limits, LIMITS);
+}
+
+#[tokio::test]
+async fn bets_within_limits_are_accepted() {
+    let mut env = TestEnv::new().await;
+    env.set_default_bet_limits(LIMITS).await.unwrap();
+    let market = env.create_market_with_outcomes(2).await;
+
+    let alice = env.create_user(1_000).await;
+    let bob = env.create_user(1_000).await;
+    env.place_bet(&alice, market, 0, 10).await.unwrap();
+    env.place_bet(&alice, market, 0, 100).await.unwrap();
+    env.place_bet(&alice, market, 0, 40).await.unwrap();
+    env.place_bet(&bob, market, 1, 100).await.unwrap();
+
+    let stored: Market = env.account(market).await;
+    assert_eq!(stored.total_pool, 250);
+}
+
+#[tokio::test]
+async fn position_limit_spans_outcomes_and_pool_limit_is_per_market() {
+    let mut env = TestEnv::new().await;
+    env.set_default_bet_limits(LIMITS).await.unwrap();
+    let market = env.create_market_with_outcomes(2).await;
+    let alice = env.create_user(1_000).await;
+    let bob = env.create_user(1_000).await;
+
+    // Spreading bets across outcomes doesn't get round the position limit
+    env.place_bet(&alice, market, 0, 100).await.unwrap();
+    env.place_bet(&alice, market, 1, 50).await.unwrap();
+    let result = env.place_bet(&alice, market, 1, 10).await;
+    assert_program_error(result, PredictionMarketError::PositionLimitExceeded);
+    let stake: MarketStake = env.account(market_stake_address(alice.pubkey(), market)).await;
+    assert_eq!(stake.amount, 150);
+
+    env.place_bet(&bob, market, 1, 100).await.unwrap();
+    let result = env.place_bet(&bob, market, 0, 10).await;
+    assert_program_error(result, PredictionMarketError::PoolLimitExceeded);
+}
+
+#[tokio::test]
+async fn market_override_replaces_defaults() {
+    let mut env = TestEnv::new().await;
+    env.set_default_bet_limits(LIMITS).await.unwrap();
+    let market = env.create_market_with_outcomes(2).await;
+    let user = env.create_user(1_000).await;
+
+    env.set_bet_limits(market, BetLimits { min_bet: 1, ..BetLimits::default() }).await.unwrap();
+    env.place_bet(&user, market, 0, 500).await.unwrap();
+
+    // Defaults are untouched by the override
+    let config: PlatformConfig = env.platform_config().await;
+    assert_eq!(config.default_bet_limits, LIMITS);
+}
+
+#[tokio::test]
+async fn limits_are_admin_only() {
+    let mut env = TestEnv::new().await;
+    let market = env.create_market().await;
+    let stranger = env.create_user(0).await;
+
+    let result = env
+        .send(
+            prediction_market::accounts::SetBetLimits {
+                market,
+                platform_config: platform_config(),
+                admin: stranger.pubkey(),
+            },
+            prediction_market::instruction::SetBetLimits { limits: LIMITS },
+            &[&stranger.keypair],
+        )
+        .await;
+    assert_program_error(result, PredictionMarketError::UnauthorizedAdmin);
+}
@@ -0,0 +1,104 @@
+//! Markets in different collateral mints stay apart: each is bound to its
+//! mint and pays fees into that mint's treasury.
+
+mod common;
+
+use anchor_lang::error::ErrorCode;
+use common::*;
+use prediction_market::errors::PredictionMarketError;
+use prediction_market::instruction;
+use prediction_market::state::{CollateralMode, Market};
+use solana_sdk::signature::Signer;
+
+#[tokio::test]
+async fn fees_go_to_the_market_collateral_treasury() {
+    let mut env = TestEnv::new().await;
+    let first_mint = env.mint;
+    let first_market = env.create_market_with_outcomes(2).await;
+
+    let second_mint = env.create_other_mint().await;
+    env.add_collateral_mint(second_mint).await.unwrap();
+    env.use_collateral(second_mint).await;
+    let second_market = env.create_market_with_outcomes(2).await;
+    assert_eq!(env.account::<Market>(second_market).await.collateral_mint, second_mint);
+
+    let alice = env.create_user(1_000).await;
+    let bob = env.create_user(1_000).await;
+    env.place_bet(&alice, second_market, 0, 600).await.unwrap();
+    env.place_bet(&bob, second_market, 1, 400).await.unwrap();
+    env.end_and_resolve(second_market, 0).await;
+
+    // 2% of the 1,000 pool lands in the second mint's treasury only
+    assert_eq!(env.token_balance(treasury_token_address(&second_mint)).await, 20);
+    assert_eq!(env.token_balance(treasury_token_address(&first_mint)).await, 0);
+
+    env.claim_payout(&alice, second_market, 0).await.unwrap();
+    assert_eq!(env.token_balance(alice.token_account).await, 400 + 980);
+
+    // The first market still only takes its own collateral
+    let mut accounts = env.place_bet_accounts(&alice, first_market, 0);
+    accounts.escrow_token_account = escrow_token_address(first_market, 0, &first_mint);
+    let result = env
+        .send(accounts, instruction::PlaceBet { amount: 100 }, &[&alice.keypair])
+        .await;
+    assert_program_error(result, PredictionMarketError::InvalidMint);
+}
+
+#[tokio::test]
+async fn outcomes_must_use_the_market_collateral() {
+    let mut env = TestEnv::new().await;
+    let market = env.create_market().await;
+
+    let other_mint = env.create_other_mint().await;
+    env.add_collateral_mint(other_mint).await.unwrap();
+    env.use_collateral(other_mint).await;
+
+    let result = env.add_outcome(market, 0).await;
+    assert_program_error(result, PredictionMarketError::InvalidMint);
+}
+
+#[tokio::test]
+async fn markets_need_an_allowlisted_mint() {
+    let mut env = TestEnv::new().await;
+    let unlisted_mint = env.create_other_mint().await;
+    env.use_collateral(unlisted_mint).await;
+
+    let now = env.now().await;
+    let accounts = env.create_market_accounts(0);
+    let result = env
+        .send_as_admin(
+            accounts,
+            instruction::CreateMarket {
+                name: "Unlisted".to_string(),
+                description: "Unlisted".to_string(),
+                start_time: now + MARKET_START_OFFSET,
+                end_time: now + MARKET_END_OFFSET,
+                custom_fee_bps: None,
+                custom_oracle: None,
+                collateral_mode: CollateralMode::Token,
+            },
+        )
+        .await;
+    assert_anchor_error(result, ErrorCode::AccountNotInitialized);
+}
+
+#[tokio::test]
+async fn withdraw_fees_per_collateral() {
+    let mut env = TestEnv::new().await;
+    let first_treasury = env.treasury_token_account;
+    env.mint_to(first_treasury, 300).await;
+
+    let second_mint = env.create_other_mint().await;
+    env.add_collateral_mint(second_mint).await.unwrap();
+    env.use_collateral(second_mint).await;
+    let second_treasury = env.treasury_token_account;
+    env.mint_to(second_treasury, 500).await;
+
+    let admin = env.admin.insecure_clone();
+    let admin_token_account = env.create_token_account(&admin.pubkey()).await;
+    env.withdraw_fees(admin_token_account).await.unwrap();
+
+    assert_eq!(env.token_balance(admin_token_account).await, 500);
+    assert_eq!(env.token_balance(second_treasury).await, 0);
+    assert_eq!(env.token_balance(first_treasury).await, 300);
+}
@@ -0,0 +1,1942 @@
+//! In-process test harness for the prediction market program.
+//!
+//! Runs the program natively inside `solana-program-test` alongside the real
+//! SPL Token and Associated Token programs, so the full market lifecycle can be
+//! exercised with `cargo test` and no validator.
+
+#![allow(dead_code)]
+
+use anchor_lang::{AccountDeserialize, AccountSerialize, InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::{
+    get_associated_token_address, get_associated_token_address_with_program_id,
+    spl_associated_token_account,
+};
+use anchor_spl::token::spl_token;
+use anchor_spl::token_2022::spl_token_2022;
+use spl_token_2022::extension::{transfer_fee, ExtensionType, StateWithExtensions};
+use prediction_market::errors::PredictionMarketError;
+use prediction_market::state::{AccessGate, AllowlistEntry, BetLimits, CollateralConfig, CollateralMode, Credential, FeeModel, FeeTier, House, LegLimits, LiquidityPool, Market, MarketBook, MarketCondition, MarketStake, Outcome, Parlay, ParlayLimits, PlatformConfig, Player, Prop, Referrer, Region, StatKind, Team, TeamOutcome, Tournament, TournamentMarket, TournamentTier, UserProfile};
+use prediction_market::{accounts, instruction};
+use solana_program_test::{find_file, processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_rpc_client_types::filter::RpcFilterType;
+use solana_sdk::account::{Account, AccountSharedData, ReadableAccount};
+use solana_sdk::account_info::AccountInfo;
+use solana_sdk::clock::Clock;
+use solana_sdk::entrypoint::ProgramResult;
+use solana_sdk::instruction::{AccountMeta, Instruction, InstructionError};
+use solana_sdk::message::Message;
+use solana_sdk::program_pack::Pack;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_system_interface::instruction as system_instruction;
+use solana_system_interface::program as system_program;
+use solana_sdk::transaction::{Transaction, TransactionError};
+
+pub const MARKET_START_OFFSET: i64 = 3_600;
+pub const MARKET_END_OFFSET: i64 = 7_200;
+/// SOL every test wallet starts with for rent
+pub const USER_LAMPORTS: u64 = 1_000_000_000;
+/// Parlay limits that only cap the payout, which the house requires
+pub const PARLAY_LIMITS: ParlayLimits = ParlayLimits { max_legs: 0, max_stake: 0, max_payout: u64::MAX, max_liability: 0 };
+/// Leg limits that take parimutuel legs on any pool, at up to 100x
+pub const LEG_LIMITS: LegLimits = LegLimits { min_pool: 0, max_odds: 1_000_000 };
+
+/// Anchor's generated `entry` ties the accounts slice to its elements'
+/// lifetime, which `processor!` can't express; leaking the copy is fine here.
+fn process_instruction(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    prediction_market::entry(program_id, accounts, data)
+}
+
+/// Token-2022 mint extensions the harness knows how to initialize.
+#[derive(Debug, Clone, Copy)]
+pub enum MintExtension {
+    TransferFee { basis_points: u16, maximum_fee: u64 },
+    PermanentDelegate,
+    NonTransferable,
+}
+
+impl MintExtension {
+    fn extension_type(&self) -> ExtensionType {
+        match self {
+            MintExtension::TransferFee { .. } => ExtensionType::TransferFeeConfig,
+            MintExtension::PermanentDelegate => ExtensionType::PermanentDelegate,
+            MintExtension::NonTransferable => ExtensionType::NonTransferable,
+        }
+    }
+}
+
+pub struct User {
+    pub keypair: Keypair,
+    pub token_account: Pubkey,
+}
+
+impl User {
+    pub fn pubkey(&self) -> Pubkey {
+        self.keypair.pubkey()
+    }
+}
+
+pub struct TestEnv {
+    pub ctx: ProgramTestContext,
+    pub admin: Keypair,
+    pub mint: Pubkey,
+    pub mint_authority: Keypair,
+    /// Token program that owns `mint`
+    pub token_program: Pubkey,
+    /// Mode new markets are created in
+    pub collateral_mode: CollateralMode,
+    pub treasury_token_account: Pubkey,
+}
+
+impl TestEnv {
+    /// Starts a fresh bank with the platform initialized and one allowlisted
+    /// collateral mint.
+    pub async fn new() -> Self {
+        let mut program_test = ProgramTest::new(
+            "prediction_market",
+            prediction_market::ID,
+            processor!(process_instruction),
+        );
+        program_test.prefer_bpf(false);
+        Self::start(program_test).await
+    }
+
+    /// Like `new`, but running the program built for SBF, so that compute
+    /// units are metered as they are on chain. Panics unless `cargo
+    /// build-sbf` has put `prediction_market.so` somewhere
+    /// `solana-program-test` looks, such as `SBF_OUT_DIR`.
+    pub async fn new_sbf() -> Self {
+        find_file("prediction_market.so")
+            .expect("prediction_market.so not found, build it with `cargo build-sbf` and set `SBF_OUT_DIR`");
+        let mut program_test = ProgramTest::new("prediction_market", prediction_market::ID, None);
+        program_test.prefer_bpf(true);
+        Self::start(program_test).await
+    }
+
+    async fn start(program_test: ProgramTest) -> Self {
+        let ctx = program_test.start_with_context().await;
+        let admin = Keypair::new();
+        let mint_authority = Keypair::new();
+        let mint = Keypair::new();
+
+        let mut env = Self {
+            ctx,
+            admin,
+            mint: mint.pubkey(),
+            mint_authority,
+            token_program: spl_token::ID,
+            collateral_mode: CollateralMode::Token,
+            treasury_token_account: Pubkey::default(),
+        };
+
+        env.airdrop(&env.admin.pubkey(), 10_000_000_000).await;
+        env.create_mint(&mint).await;
+
+        env.send_as_admin(
+            accounts::InitializePlatform {
+                admin: env.admin.pubkey(),
+                platform_config: platform_config(),
+                system_program: system_program::ID,
+            },
+            instruction::InitializePlatform {},
+        )
+        .await
+        .unwrap();
+
+        env.add_collateral_mint(env.mint).await.unwrap();
+        env.treasury_token_account = treasury_token_address(&env.mint);
+
+        env
+    }
+
+    /// Points the helpers at another collateral mint, under whichever token
+    /// program owns it.
+    pub async fn use_collateral(&mut self, mint: Pubkey) {
+        self.token_program = self.owner(mint).await;
+        self.mint = mint;
+        self.treasury_token_account = self.associated_token_address(&treasury_authority(), &mint);
+    }
+
+    /// Switches new markets to native SOL betting over wrapped SOL escrows.
+    /// Users then bet from their wallets and have no token account.
+    pub async fn use_native_sol(&mut self) {
+        let mint = spl_token::native_mint::ID;
+        self.add_collateral_mint(mint).await.unwrap();
+        self.use_collateral(mint).await;
+        self.collateral_mode = CollateralMode::NativeSol;
+    }
+
+    /// Associated token account of `owner` under the collateral's token program.
+    pub fn associated_token_address(&self, owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+        get_associated_token_address_with_program_id(owner, mint, &self.token_program)
+    }
+
+    pub fn escrow_token_account(&self, market: Pubkey, outcome_id: u8) -> Pubkey {
+        self.associated_token_address(&escrow_address(market, outcome_id), &self.mint)
+    }
+
+    pub fn payer(&self) -> Keypair {
+        self.ctx.payer.insecure_clone()
+    }
+
+    /// Sends one program instruction signed by `signers`.
+    pub async fn send(
+        &mut self,
+        accounts: impl ToAccountMetas,
+        args: impl InstructionData,
+        signers: &[&Keypair],
+    ) -> Result<(), BanksClientError> {
+        self.process(&[program_instruction(accounts, args)], signers).await
+    }
+
+    /// Sends one program instruction signed by the admin.
+    pub async fn send_as_admin(
+        &mut self,
+        accounts: impl ToAccountMetas,
+        args: impl InstructionData,
+    ) -> Result<(), BanksClientError> {
+        let admin = self.admin.insecure_clone();
+        self.send(accounts, args, &[&admin]).await
+    }
+
+    /// Sends raw instructions; the context payer always pays the fee.
+    pub async fn process(
+        &mut self,
+        ixs: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Result<(), BanksClientError> {
+        let payer = self.payer();
+        let mut all_signers = vec![&payer];
+        all_signers.extend_from_slice(signers);
+
+        let blockhash = self.ctx.get_new_latest_blockhash().await.unwrap();
+
+        let tx = Transaction::new_signed_with_payer(ixs, Some(&payer.pubkey()), &all_signers, blockhash);
+        self.ctx.banks_client.process_transaction(tx).await
+    }
+
+    /// Serialized size of a transaction of `ixs` and the account data it
+    /// loads, two costs that grow with the accounts an instruction needs.
+    pub async fn transaction_footprint(&mut self, ixs: &[Instruction], signers: usize) -> (usize, usize) {
+        let message = Message::new(ixs, Some(&self.payer().pubkey()));
+        let transaction_size = 1 + 64 * (1 + signers) + message.serialize().len();
+        let mut loaded = 0;
+        for address in &message.account_keys {
+            let account = self.ctx.banks_client.get_account(*address).await.unwrap();
+            // Programs are cached by the runtime rather than loaded per transaction
+            loaded += account.filter(|account| !account.executable).map_or(0, |account| account.data.len());
+        }
+        (transaction_size, loaded)
+    }
+
+    /// Compute units a transaction of `ixs` consumes, simulated without
+    /// being committed. Only meaningful under `new_sbf`, since natively
+    /// run programs are only charged for their CPIs.
+    pub async fn compute_units(&mut self, ixs: &[Instruction], signers: &[&Keypair]) -> u64 {
+        let payer = self.payer();
+        let mut all_signers = vec![&payer];
+        all_signers.extend_from_slice(signers);
+
+        let blockhash = self.ctx.get_new_latest_blockhash().await.unwrap();
+        let tx = Transaction::new_signed_with_payer(ixs, Some(&payer.pubkey()), &all_signers, blockhash);
+        let simulation = self.ctx.banks_client.simulate_transaction(tx).await.unwrap();
+        simulation.result.unwrap().unwrap();
+        simulation.simulation_details.unwrap().units_consumed
+    }
+
+    pub async fn airdrop(&mut self, to: &Pubkey, lamports: u64) {
+        let payer = self.payer();
+        let ix = system_instruction::transfer(&payer.pubkey(), to, lamports);
+        self.process(&[ix], &[]).await.unwrap();
+    }
+
+    // ---------------------------------------------------------------------
+    // Clock
+    // ---------------------------------------------------------------------
+
+    pub async fn now(&mut self) -> i64 {
+        self.clock().await.unix_timestamp
+    }
+
+    async fn clock(&mut self) -> Clock {
+        self.ctx.banks_client.get_sysvar::<Clock>().await.unwrap()
+    }
+
+    /// Moves the bank clock to `unix_timestamp` (forwards or backwards).
+    pub async fn warp_to(&mut self, unix_timestamp: i64) {
+        let mut clock = self.clock().await;
+        clock.unix_timestamp = unix_timestamp;
+        self.ctx.set_sysvar(&clock);
+    }
+
+    // ---------------------------------------------------------------------
+    // Accounts
+    // ---------------------------------------------------------------------
+
+    pub async fn account<T: AccountDeserialize>(&mut self, address: Pubkey) -> T {
+        let account = self
+            .ctx
+            .banks_client
+            .get_account(address)
+            .await
+            .unwrap()
+            .unwrap_or_else(|| panic!("account {} does not exist", address));
+        T::try_deserialize(&mut account.data.as_slice()).unwrap()
+    }
+
+    /// Which of `candidates` a `getProgramAccounts` call with `filters`
+    /// would return. A bank can't list accounts by owner, so the test names
+    /// every account it may have created.
+    pub async fn program_accounts(&mut self, candidates: &[Pubkey], filters: &[RpcFilterType]) -> Vec<Pubkey> {
+        let mut matches = Vec::new();
+        for address in candidates {
+            let Some(account) = self.ctx.banks_client.get_account(*address).await.unwrap() else {
+                continue;
+            };
+            let allows = |filter: &RpcFilterType| match filter {
+                RpcFilterType::DataSize(size) => account.data.len() as u64 == *size,
+                RpcFilterType::Memcmp(memcmp) => memcmp.bytes_match(&account.data),
+                RpcFilterType::TokenAccountState => false,
+            };
+            if account.owner == prediction_market::ID && filters.iter().all(allows) {
+                matches.push(*address);
+            }
+        }
+        matches
+    }
+
+    pub async fn account_exists(&mut self, address: Pubkey) -> bool {
+        self.ctx.banks_client.get_account(address).await.unwrap().is_some()
+    }
+
+    /// Overwrites a program account in place, for states no instruction reaches.
+    pub async fn set_account<T: AccountSerialize>(&mut self, address: Pubkey, value: &T) {
+        let account = self.ctx.banks_client.get_account(address).await.unwrap().unwrap();
+        let mut data = Vec::with_capacity(account.data.len());
+        value.try_serialize(&mut data).unwrap();
+        data.resize(account.data.len(), 0);
+
+        let mut shared = AccountSharedData::from(account);
+        shared.set_data_from_slice(&data);
+        self.ctx.set_account(&address, &shared);
+    }
+
+    /// Replaces an account's raw data, resizing it, e.g. to put it back in
+    /// a legacy layout.
+    pub async fn set_account_data(&mut self, address: Pubkey, data: &[u8]) {
+        let account = self.ctx.banks_client.get_account(address).await.unwrap().unwrap();
+        let mut shared = AccountSharedData::from(account);
+        shared.set_data_from_slice(data);
+        self.ctx.set_account(&address, &shared);
+    }
+
+    pub async fn account_data(&mut self, address: Pubkey) -> Vec<u8> {
+        self.ctx.banks_client.get_account(address).await.unwrap().unwrap().data
+    }
+
+    pub async fn account_data_len(&mut self, address: Pubkey) -> usize {
+        self.account_data(address).await.len()
+    }
+
+    /// The whole account at `address`, e.g. to copy into another bank.
+    pub async fn raw_account(&mut self, address: Pubkey) -> Account {
+        self.ctx.banks_client.get_account(address).await.unwrap().unwrap()
+    }
+
+    /// Creates or replaces the account at `address` wholesale.
+    pub fn put_account(&mut self, address: Pubkey, account: Account) {
+        self.ctx.set_account(&address, &AccountSharedData::from(account));
+    }
+
+    pub async fn lamports(&mut self, address: Pubkey) -> u64 {
+        self.ctx.banks_client.get_balance(address).await.unwrap()
+    }
+
+    pub async fn owner(&mut self, address: Pubkey) -> Pubkey {
+        self.ctx.banks_client.get_account(address).await.unwrap().unwrap().owner
+    }
+
+    /// Balance of an SPL Token or Token-2022 account.
+    pub async fn token_balance(&mut self, address: Pubkey) -> u64 {
+        let account = self.ctx.banks_client.get_account(address).await.unwrap().unwrap();
+        StateWithExtensions::<spl_token_2022::state::Account>::unpack(account.data())
+            .unwrap()
+            .base
+            .amount
+    }
+
+    /// Creates a second mint, unrelated to any market.
+    pub async fn create_other_mint(&mut self) -> Pubkey {
+        let mint = Keypair::new();
+        self.create_mint(&mint).await;
+        mint.pubkey()
+    }
+
+    async fn create_mint(&mut self, mint: &Keypair) {
+        let payer = self.payer();
+        let rent = self.ctx.banks_client.get_rent().await.unwrap();
+        let ixs = [
+            system_instruction::create_account(
+                &payer.pubkey(),
+                &mint.pubkey(),
+                rent.minimum_balance(spl_token::state::Mint::LEN),
+                spl_token::state::Mint::LEN as u64,
+                &spl_token::ID,
+            ),
+            spl_token::instruction::initialize_mint2(
+                &spl_token::ID,
+                &mint.pubkey(),
+                &self.mint_authority.pubkey(),
+                None,
+                6,
+            )
+            .unwrap(),
+        ];
+        self.process(&ixs, &[mint]).await.unwrap();
+    }
+
+    /// Creates a Token-2022 mint with `extensions`, unrelated to any market.
+    pub async fn create_token_2022_mint(&mut self, extensions: &[MintExtension]) -> Pubkey {
+        let mint = Keypair::new();
+        let payer = self.payer();
+        let authority = self.mint_authority.pubkey();
+        let extension_types: Vec<_> = extensions.iter().map(MintExtension::extension_type).collect();
+        let space = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(&extension_types)
+            .unwrap();
+        let rent = self.ctx.banks_client.get_rent().await.unwrap();
+
+        let mut ixs = vec![system_instruction::create_account(
+            &payer.pubkey(),
+            &mint.pubkey(),
+            rent.minimum_balance(space),
+            space as u64,
+            &spl_token_2022::ID,
+        )];
+        for extension in extensions {
+            ixs.push(match *extension {
+                MintExtension::TransferFee { basis_points, maximum_fee } => {
+                    transfer_fee::instruction::initialize_transfer_fee_config(
+                        &spl_token_2022::ID,
+                        &mint.pubkey(),
+                        Some(&authority),
+                        Some(&authority),
+                        basis_points,
+                        maximum_fee,
+                    )
+                    .unwrap()
+                }
+                MintExtension::PermanentDelegate => spl_token_2022::instruction::initialize_permanent_delegate(
+                    &spl_token_2022::ID,
+                    &mint.pubkey(),
+                    &authority,
+                )
+                .unwrap(),
+                MintExtension::NonTransferable => {
+                    spl_token_2022::instruction::initialize_non_transferable_mint(&spl_token_2022::ID, &mint.pubkey())
+                        .unwrap()
+                }
+            });
+        }
+        ixs.push(
+            spl_token_2022::instruction::initialize_mint2(&spl_token_2022::ID, &mint.pubkey(), &authority, None, 6)
+                .unwrap(),
+        );
+        self.process(&ixs, &[&mint]).await.unwrap();
+        mint.pubkey()
+    }
+
+    /// Creates the associated token account of `owner` for the collateral mint.
+    pub async fn create_token_account(&mut self, owner: &Pubkey) -> Pubkey {
+        let payer = self.payer();
+        let ix = spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+            &payer.pubkey(),
+            owner,
+            &self.mint,
+            &self.token_program,
+        );
+        self.process(&[ix], &[]).await.unwrap();
+        self.associated_token_address(owner, &self.mint)
+    }
+
+    pub async fn mint_to(&mut self, token_account: Pubkey, amount: u64) {
+        let authority = self.mint_authority.insecure_clone();
+        let ix = spl_token_2022::instruction::mint_to(
+            &self.token_program,
+            &self.mint,
+            &token_account,
+            &authority.pubkey(),
+            &[],
+            amount,
+        )
+        .unwrap();
+        self.process(&[ix], &[&authority]).await.unwrap();
+    }
+
+    /// Funds a new wallet with SOL for rent and `tokens` of collateral. In
+    /// native SOL mode the collateral is extra lamports in the wallet.
+    pub async fn create_user(&mut self, tokens: u64) -> User {
+        let keypair = Keypair::new();
+        if self.collateral_mode == CollateralMode::NativeSol {
+            self.airdrop(&keypair.pubkey(), USER_LAMPORTS + tokens).await;
+            return User {
+                keypair,
+                token_account: Pubkey::default(),
+            };
+        }
+
+        self.airdrop(&keypair.pubkey(), USER_LAMPORTS).await;
+        let token_account = self.create_token_account(&keypair.pubkey()).await;
+        if tokens > 0 {
+            self.mint_to(token_account, tokens).await;
+        }
+        User {
+            keypair,
+            token_account,
+        }
+    }
+
+    fn user_token_account(&self, user: &User) -> Option<Pubkey> {
+        (self.collateral_mode == CollateralMode::Token).then_some(user.token_account)
+    }
+
+    fn unwrap_account(&self, user: &User) -> Option<Pubkey> {
+        (self.collateral_mode == CollateralMode::NativeSol).then(|| unwrap_address(user.pubkey()))
+    }
+
+    // ---------------------------------------------------------------------
+    // Instructions
+    // ---------------------------------------------------------------------
+
+    pub async fn platform_config(&mut self) -> PlatformConfig {
+        self.account(platform_config()).await
+    }
+
+    pub fn add_collateral_mint_accounts(&self, mint: Pubkey, token_program: Pubkey) -> accounts::AddCollateralMint {
+        accounts::AddCollateralMint {
+            platform_config: platform_config(),
+            admin: self.admin.pubkey(),
+            mint,
+            collateral_config: collateral_config(&mint),
+            treasury_authority: treasury_authority(),
+            treasury_token_account: get_associated_token_address_with_program_id(
+                &treasury_authority(),
+                &mint,
+                &token_program,
+            ),
+            token_program,
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: system_program::ID,
+        }
+    }
+
+    pub async fn add_collateral_mint(&mut self, mint: Pubkey) -> Result<(), BanksClientError> {
+        let token_program = self.owner(mint).await;
+        let accounts = self.add_collateral_mint_accounts(mint, token_program);
+        self.send_as_admin(accounts, instruction::AddCollateralMint {}).await
+    }
+
+    pub async fn set_collateral_enabled(&mut self, mint: Pubkey, enabled: bool) -> Result<(), BanksClientError> {
+        self.send_as_admin(
+            accounts::SetCollateralEnabled {
+                platform_config: platform_config(),
+                admin: self.admin.pubkey(),
+                collateral_config: collateral_config(&mint),
+            },
+            instruction::SetCollateralEnabled { enabled },
+        )
+        .await
+    }
+
+    pub async fn set_default_bet_limits(&mut self, limits: BetLimits) -> Result<(), BanksClientError> {
+        self.send_as_admin(
+            accounts::SetDefaultBetLimits {
+                platform_config: platform_config(),
+                admin: self.admin.pubkey(),
+            },
+            instruction::SetDefaultBetLimits { limits },
+        )
+        .await
+    }
+
+    pub async fn set_bet_limits(&mut self, market: Pubkey, limits: BetLimits) -> Result<(), BanksClientError> {
+        self.send_as_admin(
+            accounts::SetBetLimits {
+                market,
+                platform_config: platform_config(),
+                admin: self.admin.pubkey(),
+            },
+            instruction::SetBetLimits { limits },
+        )
+        .await
+    }
+
+    pub async fn set_access_gate(&mut self, market: Pubkey, access_gate: AccessGate) -> Result<(), BanksClientError> {
+        self.send_as_admin(
+            accounts::SetAccessGate {
+                market,
+                platform_config: platform_config(),
+                admin: self.admin.pubkey(),
+            },
+            instruction::SetAccessGate { access_gate },
+        )
+        .await
+    }
+
+    pub async fn add_to_allowlist(&mut self, user: Pubkey) -> Result<(), BanksClientError> {
+        self.send_as_admin(
+            accounts::AddToAllowlist {
+                platform_config: platform_config(),
+                admin: self.admin.pubkey(),
+                allowlist_entry: allowlist_entry_address(user),
+                system_program: system_program::ID,
+            },
+            instruction::AddToAllowlist { user },
+        )
+        .await
+    }
+
+    pub async fn issue_credential(
+        &mut self,
+        authority: &Keypair,
+        user: Pubkey,
+        expires_at: i64,
+    ) -> Result<(), BanksClientError> {
+        self.send(
+            accounts::IssueCredential {
+                authority: authority.pubkey(),
+                credential: credential_address(authority.pubkey(), user),
+                system_program: system_program::ID,
+            },
+            instruction::IssueCredential { user, expires_at },
+            &[authority],
+        )
+        .await
+    }
+
+    pub fn create_market_accounts(&self, markets_count: u64) -> accounts::CreateMarket {
+        accounts::CreateMarket {
+            creator: self.admin.pubkey(),
+            platform_config: platform_config(),
+            market: market_address(markets_count),
+            mint: self.mint,
+            collateral_config: collateral_config(&self.mint),
+            tournament: None,
+            tournament_market: None,
+            system_program: system_program::ID,
+        }
+    }
+
+    /// Creates a market opening `MARKET_START_OFFSET` from now.
+    pub async fn create_market(&mut self) -> Pubkey {
+        let now = self.now().await;
+        self.create_market_with_times(now + MARKET_START_OFFSET, now + MARKET_END_OFFSET)
+            .await
+            .unwrap()
+    }
+
+    pub async fn create_market_with_times(
+        &mut self,
+        start_time: i64,
+        end_time: i64,
+    ) -> Result<Pubkey, BanksClientError> {
+        self.create_market_with(start_time, end_time, None).await
+    }
+
+    /// Creates a market opening `MARKET_START_OFFSET` from now with its own fee.
+    pub async fn create_market_with_fee(&mut self, custom_fee_bps: u16) -> Result<Pubkey, BanksClientError> {
+        let now = self.now().await;
+        self.create_market_with(now + MARKET_START_OFFSET, now + MARKET_END_OFFSET, Some(custom_fee_bps))
+            .await
+    }
+
+    /// Creates a market opening `MARKET_START_OFFSET` from now as a match of `tournament`.
+    pub async fn create_tournament_match(&mut self, tournament: Pubkey) -> Result<Pubkey, BanksClientError> {
+        let now = self.now().await;
+        self.create_market_in(now + MARKET_START_OFFSET, now + MARKET_END_OFFSET, None, Some(tournament))
+            .await
+    }
+
+    async fn create_market_with(
+        &mut self,
+        start_time: i64,
+        end_time: i64,
+        custom_fee_bps: Option<u16>,
+    ) -> Result<Pubkey, BanksClientError> {
+        self.create_market_in(start_time, end_time, custom_fee_bps, None).await
+    }
+
+    async fn create_market_in(
+        &mut self,
+        start_time: i64,
+        end_time: i64,
+        custom_fee_bps: Option<u16>,
+        tournament: Option<Pubkey>,
+    ) -> Result<Pubkey, BanksClientError> {
+        let markets_count = self.platform_config().await.markets_count;
+        let mut accounts = self.create_market_accounts(markets_count);
+        accounts.tournament = tournament;
+        accounts.tournament_market = tournament.map(|_| tournament_market_address(accounts.market));
+        let market = accounts.market;
+        self.send_as_admin(
+            accounts,
+            instruction::CreateMarket {
+                name: "TSM vs Cloud9 - Valorant Champions 2025".to_string(),
+                description: "Grand Final".to_string(),
+                start_time,
+                end_time,
+                custom_fee_bps,
+                custom_oracle: None,
+                collateral_mode: self.collateral_mode,
+            },
+        )
+        .await?;
+        Ok(market)
+    }
+
+    pub fn add_outcome_accounts(&self, market: Pubkey, outcome_id: u8) -> accounts::AddOutcome {
+        let escrow_authority = escrow_address(market, outcome_id);
+        accounts::AddOutcome {
+            market,
+            platform_config: platform_config(),
+            authority: self.admin.pubkey(),
+            outcome: outcome_address(market, outcome_id),
+            escrow_authority,
+            mint: self.mint,
+            escrow_token_account: self.associated_token_address(&escrow_authority, &self.mint),
+            market_book: None,
+            team: None,
+            team_outcome: None,
+            opponent_outcome: None,
+            opponent_team: None,
+            token_program: self.token_program,
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: system_program::ID,
+        }
+    }
+
+    pub async fn add_outcome(&mut self, market: Pubkey, outcome_id: u8) -> Result<(), BanksClientError> {
+        self.add_outcome_for(market, outcome_id, None).await
+    }
+
+    /// Adds an outcome backing `team`, if given, passing the market's other
+    /// outcome and its team when it makes the market a head-to-head.
+    pub async fn add_outcome_for(
+        &mut self,
+        market: Pubkey,
+        outcome_id: u8,
+        team: Option<Pubkey>,
+    ) -> Result<(), BanksClientError> {
+        let mut accounts = self.add_outcome_accounts(market, outcome_id);
+        accounts.market_book = self.market_book(market).await;
+        accounts.team = team;
+        accounts.team_outcome = team.map(|_| team_outcome_address(outcome_address(market, outcome_id)));
+        let outcomes = self.account::<Market>(market).await.outcomes;
+        if let (Some(_), [opponent_id]) = (team, outcomes.as_slice()) {
+            let opponent = outcome_address(market, *opponent_id);
+            accounts.opponent_outcome = Some(opponent);
+            accounts.opponent_team = self.account::<Outcome>(opponent).await.team.map(team_address);
+        }
+        self.send_as_admin(
+            accounts,
+            instruction::AddOutcome {
+                name: format!("Team {} wins", outcome_id),
+                outcome_id,
+            },
+        )
+        .await
+    }
+
+    /// Creates a market with an outcome backing each of `teams`, in order.
+    pub async fn create_market_with_teams(&mut self, teams: &[Pubkey]) -> Pubkey {
+        let market = self.create_market().await;
+        for (outcome_id, team) in teams.iter().enumerate() {
+            self.add_outcome_for(market, outcome_id as u8, Some(*team)).await.unwrap();
+        }
+        market
+    }
+
+    /// Creates a market with outcomes `0..outcomes`.
+    pub async fn create_market_with_outcomes(&mut self, outcomes: u8) -> Pubkey {
+        let market = self.create_market().await;
+        for outcome_id in 0..outcomes {
+            self.add_outcome(market, outcome_id).await.unwrap();
+        }
+        market
+    }
+
+    pub fn place_bet_accounts(&self, user: &User, market: Pubkey, outcome_id: u8) -> accounts::PlaceBet {
+        accounts::PlaceBet {
+            user: user.pubkey(),
+            market,
+            platform_config: platform_config(),
+            outcome: outcome_address(market, outcome_id),
+            user_position: user_position_address(user.pubkey(), market, outcome_id),
+            market_stake: market_stake_address(user.pubkey(), market),
+            user_profile: user_profile_address(user.pubkey()),
+            allowlist_entry: None,
+            credential: None,
+            referrer: None,
+            referrer_account: None,
+            mint: self.mint,
+            user_token_account: self.user_token_account(user),
+            collateral_config: None,
+            treasury_token_account: None,
+            market_book: None,
+            house: None,
+            house_vault: None,
+            escrow_token_account: self.escrow_token_account(market, outcome_id),
+            token_program: self.token_program,
+            system_program: system_program::ID,
+        }
+    }
+
+    pub async fn place_bet(
+        &mut self,
+        user: &User,
+        market: Pubkey,
+        outcome_id: u8,
+        amount: u64,
+    ) -> Result<(), BanksClientError> {
+        let mut accounts = self.place_bet_accounts(user, market, outcome_id);
+        accounts.market_book = self.market_book(market).await;
+        let stored: Market = self.account(market).await;
+        // Markets charging at bet time need the treasury
+        if stored.fee_model == FeeModel::AtBet {
+            accounts.collateral_config = Some(collateral_config(&self.mint));
+            accounts.treasury_token_account = Some(self.treasury_token_account);
+        }
+        // Fixed-odds markets stake into the house
+        if stored.has_fixed_odds {
+            accounts.house = Some(house_address(&self.mint));
+            accounts.house_vault = Some(self.house_vault());
+        }
+        self.send(accounts, instruction::PlaceBet { amount }, &[&user.keypair])
+            .await
+    }
+
+    /// Places a bet attributed to `referrer`.
+    pub async fn place_referred_bet(
+        &mut self,
+        user: &User,
+        market: Pubkey,
+        outcome_id: u8,
+        amount: u64,
+        referrer: Pubkey,
+    ) -> Result<(), BanksClientError> {
+        let mut accounts = self.place_bet_accounts(user, market, outcome_id);
+        accounts.market_book = self.market_book(market).await;
+        accounts.referrer = Some(referrer);
+        accounts.referrer_account = Some(referrer_address(market, referrer));
+        self.send(accounts, instruction::PlaceBet { amount }, &[&user.keypair])
+            .await
+    }
+
+    pub async fn set_referral_share(&mut self, referral_share_bps: u16) -> Result<(), BanksClientError> {
+        self.send_as_admin(
+            accounts::SetReferralShare {
+                platform_config: platform_config(),
+                admin: self.admin.pubkey(),
+            },
+            instruction::SetReferralShare { referral_share_bps },
+        )
+        .await
+    }
+
+    pub async fn claim_referral_rewards(&mut self, referrer: &User, market: Pubkey) -> Result<(), BanksClientError> {
+        let winner = self.account::<Market>(market).await.winner.unwrap_or_default();
+        let accounts = accounts::ClaimReferralRewards {
+            referrer: referrer.pubkey(),
+            market,
+            referrer_account: referrer_address(market, referrer.pubkey()),
+            mint: self.mint,
+            referrer_token_account: self.user_token_account(referrer),
+            unwrap_account: self.unwrap_account(referrer),
+            escrow_authority: escrow_address(market, winner),
+            escrow_token_account: self.escrow_token_account(market, winner),
+            token_program: self.token_program,
+            system_program: system_program::ID,
+        };
+        self.send(accounts, instruction::ClaimReferralRewards {}, &[&referrer.keypair])
+            .await
+    }
+
+    pub async fn set_max_creator_fee(&mut self, max_creator_fee_bps: u16) -> Result<(), BanksClientError> {
+        self.send_as_admin(
+            accounts::SetMaxCreatorFee {
+                platform_config: platform_config(),
+                admin: self.admin.pubkey(),
+            },
+            instruction::SetMaxCreatorFee { max_creator_fee_bps },
+        )
+        .await
+    }
+
+    pub async fn set_fee_tiers(&mut self, fee_tiers: [FeeTier; PlatformConfig::FEE_TIERS]) -> Result<(), BanksClientError> {
+        self.send_as_admin(
+            accounts::SetFeeTiers {
+                platform_config: platform_config(),
+                admin: self.admin.pubkey(),
+            },
+            instruction::SetFeeTiers { fee_tiers },
+        )
+        .await
+    }
+
+    pub async fn set_fee_model(&mut self, market: Pubkey, fee_model: FeeModel) -> Result<(), BanksClientError> {
+        self.send_as_admin(
+            accounts::SetFeeModel {
+                market,
+                platform_config: platform_config(),
+                admin: self.admin.pubkey(),
+            },
+            instruction::SetFeeModel { fee_model },
+        )
+        .await
+    }
+
+    pub async fn set_rates_teams(&mut self, market: Pubkey, rates_teams: bool) -> Result<(), BanksClientError> {
+        self.send_as_admin(
+            accounts::SetRatesTeams {
+                market,
+                platform_config: platform_config(),
+                admin: self.admin.pubkey(),
+            },
+            instruction::SetRatesTeams { rates_teams },
+        )
+        .await
+    }
+
+    pub async fn create_market_book(&mut self, market: Pubkey) -> Result<(), BanksClientError> {
+        self.send_as_admin(
+            accounts::CreateMarketBook {
+                market,
+                market_book: market_book_address(market),
+                platform_config: platform_config(),
+                admin: self.admin.pubkey(),
+                system_program: system_program::ID,
+            },
+            instruction::CreateMarketBook {},
+        )
+        .await
+    }
+
+    /// Opens a tournament around `outright_market` and returns its address.
+    pub async fn create_tournament(&mut self, outright_market: Pubkey) -> Result<Pubkey, BanksClientError> {
+        let tournament = tournament_address(self.platform_config().await.tournaments_count);
+        self.send_as_admin(
+            accounts::CreateTournament {
+                platform_config: platform_config(),
+                tournament,
+                outright_market,
+                tournament_market: tournament_market_address(outright_market),
+                admin: self.admin.pubkey(),
+                system_program: system_program::ID,
+            },
+            instruction::CreateTournament {
+                name: "Valorant Champions 2025".to_string(),
+                tier: TournamentTier::International,
+                region: Region::International,
+                start_date: 0,
+                end_date: 0,
+            },
+        )
+        .await?;
+        Ok(tournament)
+    }
+
+    pub async fn add_tournament_match(
+        &mut self,
+        tournament: Pubkey,
+        match_market: Pubkey,
+        is_grand_final: bool,
+    ) -> Result<(), BanksClientError> {
+        self.send_as_admin(
+            accounts::AddTournamentMatch {
+                tournament,
+                match_market,
+                tournament_market: tournament_market_address(match_market),
+                platform_config: platform_config(),
+                admin: self.admin.pubkey(),
+                system_program: system_program::ID,
+            },
+            instruction::AddTournamentMatch { is_grand_final },
+        )
+        .await
+    }
+
+    pub async fn eliminate_outcome(&mut self, tournament: Pubkey, outcome_id: u8) -> Result<(), BanksClientError> {
+        let market = self.account::<Tournament>(tournament).await.outright_market;
+        let market_book = self.market_book(market).await;
+        self.send_as_admin(
+            accounts::EliminateOutcome {
+                tournament,
+                market,
+                outcome: outcome_address(market, outcome_id),
+                market_book,
+                oracle: self.admin.pubkey(),
+            },
+            instruction::EliminateOutcome {},
+        )
+        .await
+    }
+
+    pub async fn set_market_condition(
+        &mut self,
+        market: Pubkey,
+        parent_market: Pubkey,
+        parent_outcome_id: u8,
+    ) -> Result<(), BanksClientError> {
+        self.send_as_admin(
+            accounts::SetMarketCondition {
+                market,
+                parent_market,
+                condition: condition_address(market),
+                platform_config: platform_config(),
+                admin: self.admin.pubkey(),
+                system_program: system_program::ID,
+            },
+            instruction::SetMarketCondition { parent_outcome_id },
+        )
+        .await
+    }
+
+    /// Registers an Americas team, tagged with the start of its name, and
+    /// returns its address.
+    pub async fn create_team(&mut self, name: &str) -> Result<Pubkey, BanksClientError> {
+        let tag = name.chars().take(3).collect::<String>().to_uppercase();
+        self.create_team_with(name, &tag, "https://example.com/logo.png").await
+    }
+
+    /// Registers `count` teams and returns their addresses.
+    pub async fn create_teams(&mut self, count: usize) -> Vec<Pubkey> {
+        let mut teams = Vec::with_capacity(count);
+        for index in 0..count {
+            teams.push(self.create_team(&format!("Team {}", index)).await.unwrap());
+        }
+        teams
+    }
+
+    pub async fn create_team_with(&mut self, name: &str, tag: &str, logo_uri: &str) -> Result<Pubkey, BanksClientError> {
+        let team = team_address(self.platform_config().await.teams_count);
+        self.send_as_admin(
+            accounts::CreateTeam {
+                platform_config: platform_config(),
+                team,
+                admin: self.admin.pubkey(),
+                system_program: system_program::ID,
+            },
+            instruction::CreateTeam {
+                name: name.to_string(),
+                tag: tag.to_string(),
+                region: Region::Americas,
+                logo_uri: logo_uri.to_string(),
+            },
+        )
+        .await?;
+        Ok(team)
+    }
+
+    /// Seeds a head-to-head market's odds from the ratings of the teams its
+    /// outcomes `0` and `1` back.
+    pub async fn seed_odds(&mut self, market: Pubkey, home_team: Pubkey, away_team: Pubkey) -> Result<(), BanksClientError> {
+        let market_book = self.market_book(market).await;
+        self.send_as_admin(
+            accounts::SeedOdds {
+                market,
+                home_outcome: outcome_address(market, 0),
+                away_outcome: outcome_address(market, 1),
+                home_team,
+                away_team,
+                market_book,
+                platform_config: platform_config(),
+                admin: self.admin.pubkey(),
+            },
+            instruction::SeedOdds {},
+        )
+        .await
+    }
+
+    /// Registers a player on `team` and returns its address.
+    pub async fn create_player(&mut self, team: Pubkey, handle: &str) -> Result<Pubkey, BanksClientError> {
+        let player = player_address(self.platform_config().await.players_count);
+        self.send_as_admin(
+            accounts::CreatePlayer {
+                platform_config: platform_config(),
+                team,
+                player,
+                admin: self.admin.pubkey(),
+                system_program: system_program::ID,
+            },
+            instruction::CreatePlayer { handle: handle.to_string() },
+        )
+        .await?;
+        Ok(player)
+    }
+
+    pub async fn create_prop_market(
+        &mut self,
+        market: Pubkey,
+        player: Pubkey,
+        stat: StatKind,
+        line: u64,
+    ) -> Result<(), BanksClientError> {
+        let market_book = self.market_book(market).await;
+        let over_escrow_authority = escrow_address(market, Prop::OVER);
+        let under_escrow_authority = escrow_address(market, Prop::UNDER);
+        self.send_as_admin(
+            accounts::CreatePropMarket {
+                market,
+                player,
+                prop: prop_address(market),
+                over_outcome: outcome_address(market, Prop::OVER),
+                over_escrow_authority,
+                over_escrow_token_account: self.associated_token_address(&over_escrow_authority, &self.mint),
+                under_outcome: outcome_address(market, Prop::UNDER),
+                under_escrow_authority,
+                under_escrow_token_account: self.associated_token_address(&under_escrow_authority, &self.mint),
+                market_book,
+                mint: self.mint,
+                platform_config: platform_config(),
+                admin: self.admin.pubkey(),
+                token_program: self.token_program,
+                associated_token_program: spl_associated_token_account::ID,
+                system_program: system_program::ID,
+            },
+            instruction::CreatePropMarket { stat, line },
+        )
+        .await
+    }
+
+    /// Turns `market` into a top player prop on `stat` and adds each of
+    /// `players` as outcomes `0..`, in order.
+    pub async fn create_top_player_market(
+        &mut self,
+        market: Pubkey,
+        stat: StatKind,
+        players: &[Pubkey],
+    ) -> Result<(), BanksClientError> {
+        self.send_as_admin(
+            accounts::CreateTopPlayerMarket {
+                market,
+                prop: prop_address(market),
+                platform_config: platform_config(),
+                admin: self.admin.pubkey(),
+                system_program: system_program::ID,
+            },
+            instruction::CreateTopPlayerMarket { stat },
+        )
+        .await?;
+        for (outcome_id, player) in players.iter().enumerate() {
+            self.add_top_player(market, outcome_id as u8, *player).await?;
+        }
+        Ok(())
+    }
+
+    pub async fn add_top_player(&mut self, market: Pubkey, outcome_id: u8, player: Pubkey) -> Result<(), BanksClientError> {
+        let market_book = self.market_book(market).await;
+        let escrow_authority = escrow_address(market, outcome_id);
+        self.send_as_admin(
+            accounts::AddTopPlayer {
+                market,
+                prop: prop_address(market),
+                player,
+                outcome: outcome_address(market, outcome_id),
+                escrow_authority,
+                escrow_token_account: self.associated_token_address(&escrow_authority, &self.mint),
+                market_book,
+                mint: self.mint,
+                platform_config: platform_config(),
+                admin: self.admin.pubkey(),
+                token_program: self.token_program,
+                associated_token_program: spl_associated_token_account::ID,
+                system_program: system_program::ID,
+            },
+            instruction::AddTopPlayer { outcome_id },
+        )
+        .await
+    }
+
+    /// The market's book, if it has one, for instructions that must keep it in step.
+    pub async fn market_book(&mut self, market: Pubkey) -> Option<Pubkey> {
+        self.account::<Market>(market)
+            .await
+            .has_book
+            .then(|| market_book_address(market))
+    }
+
+    /// Refreshes the odds on every outcome account of the market.
+    pub fn refresh_odds_instruction(&self, market: Pubkey, outcomes: &[u8]) -> Instruction {
+        let mut ix = program_instruction(accounts::RefreshOdds { market }, instruction::RefreshOdds {});
+        ix.accounts.extend(
+            outcomes
+                .iter()
+                .map(|id| AccountMeta::new(outcome_address(market, *id), false)),
+        );
+        ix
+    }
+
+    pub async fn set_creator_fee(
+        &mut self,
+        market: Pubkey,
+        creator: Pubkey,
+        creator_fee_bps: u16,
+    ) -> Result<(), BanksClientError> {
+        self.send_as_admin(
+            accounts::SetCreatorFee {
+                market,
+                platform_config: platform_config(),
+                admin: self.admin.pubkey(),
+                creator,
+            },
+            instruction::SetCreatorFee { creator_fee_bps },
+        )
+        .await
+    }
+
+    pub async fn set_self_exclusion(&mut self, user: &User, until: i64) -> Result<(), BanksClientError> {
+        self.send(
+            accounts::SetSelfExclusion {
+                user: user.pubkey(),
+                user_profile: user_profile_address(user.pubkey()),
+                system_program: system_program::ID,
+            },
+            instruction::SetSelfExclusion { until },
+            &[&user.keypair],
+        )
+        .await
+    }
+
+    pub async fn set_deposit_limits(&mut self, user: &User, daily: u64, weekly: u64) -> Result<(), BanksClientError> {
+        self.send(
+            accounts::SetDepositLimits {
+                user: user.pubkey(),
+                user_profile: user_profile_address(user.pubkey()),
+                system_program: system_program::ID,
+            },
+            instruction::SetDepositLimits { daily, weekly },
+            &[&user.keypair],
+        )
+        .await
+    }
+
+    pub fn resolve_market_accounts(&self, market: Pubkey, winning_outcome_id: u8) -> accounts::ResolveMarket {
+        accounts::ResolveMarket {
+            market,
+            oracle: self.admin.pubkey(),
+            platform_config: platform_config(),
+            winning_outcome: outcome_address(market, winning_outcome_id),
+            winning_escrow_authority: escrow_address(market, winning_outcome_id),
+            winning_escrow_token_account: self.escrow_token_account(market, winning_outcome_id),
+            collateral_config: collateral_config(&self.mint),
+            treasury_token_account: self.treasury_token_account,
+            creator_token_account: None,
+            market_book: None,
+            tournament: None,
+            grand_final: None,
+            grand_final_winner: None,
+            condition: None,
+            parent_market: None,
+            prop: None,
+            house: None,
+            liquidity_pool: None,
+            losing_outcome: None,
+            winning_team: None,
+            losing_team: None,
+            mint: self.mint,
+            token_program: self.token_program,
+        }
+    }
+
+    /// Escrow authority and token account of every losing outcome, in market order.
+    pub async fn settlement_accounts(&mut self, market: Pubkey, winning_outcome_id: u8) -> Vec<AccountMeta> {
+        let outcomes = self.account::<Market>(market).await.outcomes;
+        outcomes
+            .into_iter()
+            .filter(|id| *id != winning_outcome_id)
+            .flat_map(|id| {
+                [
+                    AccountMeta::new_readonly(escrow_address(market, id), false),
+                    AccountMeta::new(self.escrow_token_account(market, id), false),
+                ]
+            })
+            .collect()
+    }
+
+    /// Resolve accounts with every optional account the market needs filled in.
+    async fn resolve_accounts(&mut self, market: Pubkey, winning_outcome_id: u8) -> accounts::ResolveMarket {
+        let mut accounts = self.resolve_market_accounts(market, winning_outcome_id);
+        accounts.market_book = self.market_book(market).await;
+        let stored: Market = self.account(market).await;
+        if stored.creator_fee_bps > 0 {
+            accounts.creator_token_account = Some(self.associated_token_address(&stored.creator, &self.mint));
+        }
+        // Tournament markets pass their tournament, and outrights its grand
+        // final and, once it is resolved, its winner
+        if let Some(tournament_id) = stored.tournament {
+            let tournament: Tournament = self.account(tournament_address(tournament_id)).await;
+            accounts.tournament = Some(tournament_address(tournament_id));
+            if let (true, Some(grand_final)) = (tournament.outright_market == market, tournament.grand_final) {
+                accounts.grand_final = Some(grand_final);
+                let winner = self.account::<Market>(grand_final).await.winner;
+                accounts.grand_final_winner = winner.map(|winner| outcome_address(grand_final, winner));
+            }
+        }
+        // Conditional markets pass their condition and its parent
+        if stored.has_condition {
+            let condition: MarketCondition = self.account(condition_address(market)).await;
+            accounts.condition = Some(condition_address(market));
+            accounts.parent_market = Some(condition.parent_market);
+        }
+        if stored.has_prop {
+            accounts.prop = Some(prop_address(market));
+        }
+        if stored.has_liquidity {
+            accounts.liquidity_pool = Some(liquidity_pool_address(market));
+        }
+        if stored.has_fixed_odds {
+            accounts.house = Some(house_address(&self.mint));
+        }
+        // Markets that rate their teams pass both teams
+        if let (true, &[first, second]) = (stored.rates_teams, &stored.outcomes[..]) {
+            let losing_outcome_id = if winning_outcome_id == first { second } else { first };
+            let winner: Outcome = self.account(outcome_address(market, winning_outcome_id)).await;
+            let loser: Outcome = self.account(outcome_address(market, losing_outcome_id)).await;
+            accounts.losing_outcome = Some(outcome_address(market, losing_outcome_id));
+            accounts.winning_team = winner.team.map(team_address);
+            accounts.losing_team = loser.team.map(team_address);
+        }
+        accounts
+    }
+
+    /// Resolves the market, paying any creator fee to the creator's associated token account.
+    pub async fn resolve_market(&mut self, market: Pubkey, winning_outcome_id: u8) -> Result<(), BanksClientError> {
+        let losing_outcomes = self.account::<Market>(market).await.outcomes.len() - 1;
+        self.resolve_market_sweeping(market, winning_outcome_id, losing_outcomes).await
+    }
+
+    /// Resolves the market sweeping only its first `sweep` losing escrows,
+    /// which markets with a book allow.
+    pub async fn resolve_market_sweeping(
+        &mut self,
+        market: Pubkey,
+        winning_outcome_id: u8,
+        sweep: usize,
+    ) -> Result<(), BanksClientError> {
+        let ix = self.resolve_market_instruction(market, winning_outcome_id, sweep).await;
+        let admin = self.admin.insecure_clone();
+        self.process(&[ix], &[&admin]).await
+    }
+
+    pub async fn resolve_market_instruction(&mut self, market: Pubkey, winning_outcome_id: u8, sweep: usize) -> Instruction {
+        let accounts = self.resolve_accounts(market, winning_outcome_id).await;
+        let mut ix = program_instruction(accounts, instruction::ResolveMarket { winning_outcome_id });
+        let escrows = self.settlement_accounts(market, winning_outcome_id).await;
+        ix.accounts.extend(escrows.into_iter().take(sweep * 2));
+        ix
+    }
+
+    /// Sweeps the next `count` losing escrows of a market resolved without
+    /// all of them.
+    pub async fn sweep_escrows(&mut self, market: Pubkey, count: usize) -> Result<(), BanksClientError> {
+        let ix = self.sweep_escrows_instruction(market, count).await;
+        let admin = self.admin.insecure_clone();
+        self.process(&[ix], &[&admin]).await
+    }
+
+    pub async fn sweep_escrows_instruction(&mut self, market: Pubkey, count: usize) -> Instruction {
+        let stored: Market = self.account(market).await;
+        let winning_outcome_id = stored.winner.unwrap();
+        let escrows = self.settlement_accounts(market, winning_outcome_id).await;
+        let first = escrows.len() / 2 - stored.unswept_escrows as usize;
+        let accounts = self.resolve_accounts(market, winning_outcome_id).await;
+        let mut ix = program_instruction(accounts, instruction::SweepEscrows {});
+        ix.accounts.extend(escrows.into_iter().skip(first * 2).take(count * 2));
+        ix
+    }
+
+    /// Resolves a prop market from the player's stat, passing the outcome that
+    /// value wins (or over, on a push).
+    pub async fn resolve_prop_market(&mut self, market: Pubkey, stat_value: u64) -> Result<(), BanksClientError> {
+        let prop: Prop = self.account(prop_address(market)).await;
+        let winning_outcome_id = prop.winning_outcome(stat_value).unwrap_or(Prop::OVER);
+        let accounts = self.resolve_accounts(market, winning_outcome_id).await;
+        let mut ix = program_instruction(accounts, instruction::ResolvePropMarket { stat_value });
+        ix.accounts
+            .extend(self.settlement_accounts(market, winning_outcome_id).await);
+        let admin = self.admin.insecure_clone();
+        self.process(&[ix], &[&admin]).await
+    }
+
+    /// Resolves a top player prop to the player behind `winning_outcome_id`,
+    /// reporting `player_id` as that player.
+    pub async fn resolve_top_player_market(
+        &mut self,
+        market: Pubkey,
+        winning_outcome_id: u8,
+        player_id: u64,
+        stat_value: u64,
+    ) -> Result<(), BanksClientError> {
+        let accounts = self.resolve_accounts(market, winning_outcome_id).await;
+        let mut ix = program_instruction(accounts, instruction::ResolveTopPlayerMarket { player_id, stat_value });
+        ix.accounts
+            .extend(self.settlement_accounts(market, winning_outcome_id).await);
+        let admin = self.admin.insecure_clone();
+        self.process(&[ix], &[&admin]).await
+    }
+
+    /// Warps past the market's end time and resolves it.
+    pub async fn end_and_resolve(&mut self, market: Pubkey, winning_outcome_id: u8) {
+        let end_time = self.account::<Market>(market).await.end_time;
+        self.warp_to(end_time).await;
+        self.resolve_market(market, winning_outcome_id).await.unwrap();
+    }
+
+    pub async fn cancel_market(&mut self, market: Pubkey) -> Result<(), BanksClientError> {
+        let market_book = self.market_book(market).await;
+        let house = self
+            .account::<Market>(market)
+            .await
+            .has_fixed_odds
+            .then(|| house_address(&self.mint));
+        self.send_as_admin(
+            accounts::CancelMarket {
+                market,
+                platform_config: platform_config(),
+                admin: self.admin.pubkey(),
+                market_book,
+                house,
+            },
+            instruction::CancelMarket {},
+        )
+        .await
+    }
+
+    pub fn claim_payout_accounts(&self, user: &User, market: Pubkey, outcome_id: u8) -> accounts::ClaimPayout {
+        accounts::ClaimPayout {
+            user: user.pubkey(),
+            market,
+            outcome: outcome_address(market, outcome_id),
+            user_position: user_position_address(user.pubkey(), market, outcome_id),
+            mint: self.mint,
+            user_token_account: self.user_token_account(user),
+            unwrap_account: self.unwrap_account(user),
+            escrow_token_account: self.escrow_token_account(market, outcome_id),
+            escrow_authority: escrow_address(market, outcome_id),
+            token_program: self.token_program,
+            system_program: system_program::ID,
+        }
+    }
+
+    pub async fn claim_payout(&mut self, user: &User, market: Pubkey, outcome_id: u8) -> Result<(), BanksClientError> {
+        let accounts = self.claim_payout_accounts(user, market, outcome_id);
+        self.send(accounts, instruction::ClaimPayout {}, &[&user.keypair])
+            .await
+    }
+
+    pub fn claim_refund_accounts(&self, user: &User, market: Pubkey, outcome_id: u8) -> accounts::ClaimRefund {
+        accounts::ClaimRefund {
+            user: user.pubkey(),
+            market,
+            outcome: outcome_address(market, outcome_id),
+            user_position: user_position_address(user.pubkey(), market, outcome_id),
+            mint: self.mint,
+            user_token_account: self.user_token_account(user),
+            unwrap_account: self.unwrap_account(user),
+            escrow_token_account: self.escrow_token_account(market, outcome_id),
+            escrow_authority: escrow_address(market, outcome_id),
+            token_program: self.token_program,
+            system_program: system_program::ID,
+        }
+    }
+
+    pub async fn claim_refund(&mut self, user: &User, market: Pubkey, outcome_id: u8) -> Result<(), BanksClientError> {
+        let accounts = self.claim_refund_accounts(user, market, outcome_id);
+        self.send(accounts, instruction::ClaimRefund {}, &[&user.keypair])
+            .await
+    }
+
+    pub fn withdraw_fees_accounts(&self, admin_token_account: Pubkey) -> accounts::WithdrawFees {
+        accounts::WithdrawFees {
+            platform_config: platform_config(),
+            admin: self.admin.pubkey(),
+            mint: self.mint,
+            collateral_config: collateral_config(&self.mint),
+            admin_token_account,
+            treasury_token_account: self.treasury_token_account,
+            treasury_authority: treasury_authority(),
+            token_program: self.token_program,
+            system_program: system_program::ID,
+        }
+    }
+
+    pub async fn withdraw_fees(&mut self, admin_token_account: Pubkey) -> Result<(), BanksClientError> {
+        let accounts = self.withdraw_fees_accounts(admin_token_account);
+        self.send_as_admin(accounts, instruction::WithdrawFees {}).await
+    }
+
+    /// The house vault in the test collateral.
+    pub async fn set_lp_fee_share(&mut self, lp_fee_share_bps: u16) -> Result<(), BanksClientError> {
+        self.send_as_admin(
+            accounts::SetLpFeeShare {
+                platform_config: platform_config(),
+                admin: self.admin.pubkey(),
+            },
+            instruction::SetLpFeeShare { lp_fee_share_bps },
+        )
+        .await
+    }
+
+    pub fn lp_token_account(&self, provider: &User, market: Pubkey) -> Pubkey {
+        self.associated_token_address(&provider.pubkey(), &lp_mint_address(market))
+    }
+
+    pub async fn add_liquidity_accounts(&mut self, provider: &User, market: Pubkey) -> accounts::AddLiquidity {
+        let market_book = self.market_book(market).await;
+        accounts::AddLiquidity {
+            provider: provider.pubkey(),
+            market,
+            platform_config: platform_config(),
+            liquidity_pool: liquidity_pool_address(market),
+            lp_mint: lp_mint_address(market),
+            provider_lp_token_account: self.lp_token_account(provider, market),
+            mint: self.mint,
+            provider_token_account: provider.token_account,
+            market_book,
+            user_profile: user_profile_address(provider.pubkey()),
+            allowlist_entry: None,
+            credential: None,
+            token_program: self.token_program,
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: system_program::ID,
+        }
+    }
+
+    /// Adds `amount` of liquidity to the market from `provider`.
+    pub async fn add_liquidity(&mut self, provider: &User, market: Pubkey, amount: u64) -> Result<(), BanksClientError> {
+        let accounts = self.add_liquidity_accounts(provider, market).await;
+        self.add_liquidity_with(provider, accounts, amount).await
+    }
+
+    /// Adds liquidity with `accounts`, passing every outcome of the market
+    /// and its escrow.
+    pub async fn add_liquidity_with(
+        &mut self,
+        provider: &User,
+        accounts: accounts::AddLiquidity,
+        amount: u64,
+    ) -> Result<(), BanksClientError> {
+        let market = accounts.market;
+        let mut ix = program_instruction(accounts, instruction::AddLiquidity { amount });
+        for id in self.account::<Market>(market).await.outcomes {
+            ix.accounts.push(AccountMeta::new(outcome_address(market, id), false));
+            ix.accounts.push(AccountMeta::new(self.escrow_token_account(market, id), false));
+        }
+        self.process(&[ix], &[&provider.keypair]).await
+    }
+
+    /// Burns `lp_tokens` of `provider`'s LP tokens for their share of the pool.
+    pub async fn remove_liquidity(&mut self, provider: &User, market: Pubkey, lp_tokens: u64) -> Result<(), BanksClientError> {
+        let market_book = self.market_book(market).await;
+        let accounts = accounts::RemoveLiquidity {
+            provider: provider.pubkey(),
+            market,
+            liquidity_pool: liquidity_pool_address(market),
+            lp_mint: lp_mint_address(market),
+            provider_lp_token_account: self.lp_token_account(provider, market),
+            mint: self.mint,
+            provider_token_account: provider.token_account,
+            market_book,
+            token_program: self.token_program,
+        };
+        let mut ix = program_instruction(accounts, instruction::RemoveLiquidity { lp_tokens });
+        for id in self.account::<Market>(market).await.outcomes {
+            ix.accounts.push(AccountMeta::new(outcome_address(market, id), false));
+            ix.accounts.push(AccountMeta::new_readonly(escrow_address(market, id), false));
+            ix.accounts.push(AccountMeta::new(self.escrow_token_account(market, id), false));
+        }
+        self.process(&[ix], &[&provider.keypair]).await
+    }
+
+    pub fn house_vault(&self) -> Pubkey {
+        self.associated_token_address(&house_address(&self.mint), &self.mint)
+    }
+
+    /// Opens the house in the test collateral and funds its vault with `liquidity`.
+    pub async fn create_house(&mut self, limits: ParlayLimits, liquidity: u64) -> Result<(), BanksClientError> {
+        self.send_as_admin(
+            accounts::CreateHouse {
+                platform_config: platform_config(),
+                admin: self.admin.pubkey(),
+                mint: self.mint,
+                collateral_config: collateral_config(&self.mint),
+                house: house_address(&self.mint),
+                vault: self.house_vault(),
+                token_program: self.token_program,
+                associated_token_program: spl_associated_token_account::ID,
+                system_program: system_program::ID,
+            },
+            instruction::CreateHouse { limits, leg_limits: LEG_LIMITS },
+        )
+        .await?;
+        if liquidity > 0 {
+            let vault = self.house_vault();
+            self.mint_to(vault, liquidity).await;
+        }
+        Ok(())
+    }
+
+    pub async fn set_parlay_limits(&mut self, limits: ParlayLimits) -> Result<(), BanksClientError> {
+        self.set_house_limits(limits, LEG_LIMITS).await
+    }
+
+    pub async fn set_house_limits(&mut self, limits: ParlayLimits, leg_limits: LegLimits) -> Result<(), BanksClientError> {
+        self.send_as_admin(
+            accounts::SetParlayLimits {
+                house: house_address(&self.mint),
+                platform_config: platform_config(),
+                admin: self.admin.pubkey(),
+            },
+            instruction::SetParlayLimits { limits, leg_limits },
+        )
+        .await
+    }
+
+    pub async fn withdraw_house_liquidity(
+        &mut self,
+        admin_token_account: Pubkey,
+        amount: u64,
+    ) -> Result<(), BanksClientError> {
+        self.send_as_admin(
+            accounts::WithdrawHouseLiquidity {
+                house: house_address(&self.mint),
+                vault: self.house_vault(),
+                mint: self.mint,
+                admin_token_account,
+                platform_config: platform_config(),
+                admin: self.admin.pubkey(),
+                token_program: self.token_program,
+            },
+            instruction::WithdrawHouseLiquidity { amount },
+        )
+        .await
+    }
+
+    pub async fn set_fixed_odds(&mut self, market: Pubkey, max_liability: u64) -> Result<(), BanksClientError> {
+        self.send_as_admin(
+            accounts::SetFixedOdds {
+                market,
+                house: house_address(&self.mint),
+                platform_config: platform_config(),
+                admin: self.admin.pubkey(),
+            },
+            instruction::SetFixedOdds { max_liability },
+        )
+        .await
+    }
+
+    /// Posts an outcome's fixed odds as the platform admin.
+    pub async fn set_outcome_odds(&mut self, market: Pubkey, outcome_id: u8, odds: u64) -> Result<(), BanksClientError> {
+        self.send_as_admin(
+            accounts::SetOutcomeOdds {
+                market,
+                outcome: outcome_address(market, outcome_id),
+                platform_config: platform_config(),
+                authority: self.admin.pubkey(),
+            },
+            instruction::SetOutcomeOdds { odds },
+        )
+        .await
+    }
+
+    pub async fn claim_fixed_odds_payout(&mut self, user: &User, market: Pubkey, outcome_id: u8) -> Result<(), BanksClientError> {
+        self.send(
+            accounts::ClaimFixedOddsPayout {
+                user: user.pubkey(),
+                market,
+                house: house_address(&self.mint),
+                vault: self.house_vault(),
+                user_position: user_position_address(user.pubkey(), market, outcome_id),
+                mint: self.mint,
+                user_token_account: user.token_account,
+                token_program: self.token_program,
+            },
+            instruction::ClaimFixedOddsPayout {},
+            &[&user.keypair],
+        )
+        .await
+    }
+
+    /// Places a parlay on `legs`, each a market and one of its outcome IDs.
+    pub async fn place_parlay(
+        &mut self,
+        user: &User,
+        parlay_id: u64,
+        stake: u64,
+        legs: &[(Pubkey, u8)],
+    ) -> Result<(), BanksClientError> {
+        let mut ix = program_instruction(
+            accounts::PlaceParlay {
+                user: user.pubkey(),
+                house: house_address(&self.mint),
+                vault: self.house_vault(),
+                parlay: parlay_address(user.pubkey(), parlay_id),
+                user_profile: user_profile_address(user.pubkey()),
+                mint: self.mint,
+                user_token_account: user.token_account,
+                token_program: self.token_program,
+                system_program: system_program::ID,
+            },
+            instruction::PlaceParlay { parlay_id, stake },
+        );
+        for (market, outcome_id) in legs {
+            ix.accounts.push(AccountMeta::new_readonly(*market, false));
+            ix.accounts.push(AccountMeta::new_readonly(outcome_address(*market, *outcome_id), false));
+        }
+        self.process(&[ix], &[&user.keypair]).await
+    }
+
+    /// Cranks a parlay, passing the market of each of its legs.
+    pub async fn settle_parlay(&mut self, user: &User, parlay_id: u64) -> Result<(), BanksClientError> {
+        let parlay = parlay_address(user.pubkey(), parlay_id);
+        let legs = self.account::<Parlay>(parlay).await.legs;
+        let mut ix = program_instruction(
+            accounts::SettleParlay {
+                parlay,
+                house: house_address(&self.mint),
+                vault: self.house_vault(),
+                mint: self.mint,
+                user_token_account: user.token_account,
+                token_program: self.token_program,
+            },
+            instruction::SettleParlay {},
+        );
+        ix.accounts
+            .extend(legs.iter().map(|leg| AccountMeta::new_readonly(leg.market, false)));
+        self.process(&[ix], &[]).await
+    }
+}
+
+pub fn program_instruction(accounts: impl ToAccountMetas, args: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: prediction_market::ID,
+        accounts: accounts.to_account_metas(None),
+        data: args.data(),
+    }
+}
+
+// -------------------------------------------------------------------------
+// PDAs
+// -------------------------------------------------------------------------
+
+pub fn platform_config() -> Pubkey {
+    Pubkey::find_program_address(&[PlatformConfig::SEED_PREFIX.as_bytes()], &prediction_market::ID).0
+}
+
+pub fn treasury_authority() -> Pubkey {
+    Pubkey::find_program_address(
+        &[PlatformConfig::SEED_PREFIX.as_bytes(), b"treasury"],
+        &prediction_market::ID,
+    )
+    .0
+}
+
+/// Treasury of an SPL Token mint; `TestEnv::use_collateral` also covers Token-2022.
+pub fn treasury_token_address(mint: &Pubkey) -> Pubkey {
+    get_associated_token_address(&treasury_authority(), mint)
+}
+
+pub fn collateral_config(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[CollateralConfig::SEED_PREFIX.as_bytes(), mint.as_ref()],
+        &prediction_market::ID,
+    )
+    .0
+}
+
+pub fn market_address(market_id: u64) -> Pubkey {
+    Pubkey::find_program_address(&[b"market", market_id.to_le_bytes().as_ref()], &prediction_market::ID).0
+}
+
+pub fn outcome_address(market: Pubkey, outcome_id: u8) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"outcome", market.as_ref(), &outcome_id.to_le_bytes()],
+        &prediction_market::ID,
+    )
+    .0
+}
+
+pub fn escrow_address(market: Pubkey, outcome_id: u8) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"escrow", market.as_ref(), &outcome_id.to_le_bytes()],
+        &prediction_market::ID,
+    )
+    .0
+}
+
+/// Escrow of an SPL Token mint; see `TestEnv::escrow_token_account` for Token-2022.
+pub fn escrow_token_address(market: Pubkey, outcome_id: u8, mint: &Pubkey) -> Pubkey {
+    get_associated_token_address(&escrow_address(market, outcome_id), mint)
+}
+
+pub fn unwrap_address(user: Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"unwrap", user.as_ref()], &prediction_market::ID).0
+}
+
+pub fn user_profile_address(user: Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[UserProfile::SEED_PREFIX.as_bytes(), user.as_ref()],
+        &prediction_market::ID,
+    )
+    .0
+}
+
+pub fn allowlist_entry_address(user: Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[AllowlistEntry::SEED_PREFIX.as_bytes(), user.as_ref()],
+        &prediction_market::ID,
+    )
+    .0
+}
+
+pub fn credential_address(authority: Pubkey, user: Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[Credential::SEED_PREFIX.as_bytes(), authority.as_ref(), user.as_ref()],
+        &prediction_market::ID,
+    )
+    .0
+}
+
+pub fn referrer_address(market: Pubkey, referrer: Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[Referrer::SEED_PREFIX.as_bytes(), market.as_ref(), referrer.as_ref()],
+        &prediction_market::ID,
+    )
+    .0
+}
+
+pub fn market_book_address(market: Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[MarketBook::SEED_PREFIX.as_bytes(), market.as_ref()],
+        &prediction_market::ID,
+    )
+    .0
+}
+
+pub fn tournament_address(tournament_id: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[Tournament::SEED_PREFIX.as_bytes(), tournament_id.to_le_bytes().as_ref()],
+        &prediction_market::ID,
+    )
+    .0
+}
+
+pub fn tournament_market_address(market: Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[TournamentMarket::SEED_PREFIX.as_bytes(), market.as_ref()],
+        &prediction_market::ID,
+    )
+    .0
+}
+
+pub fn condition_address(market: Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[MarketCondition::SEED_PREFIX.as_bytes(), market.as_ref()],
+        &prediction_market::ID,
+    )
+    .0
+}
+
+pub fn team_address(team_id: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[Team::SEED_PREFIX.as_bytes(), team_id.to_le_bytes().as_ref()],
+        &prediction_market::ID,
+    )
+    .0
+}
+
+pub fn team_outcome_address(outcome: Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[TeamOutcome::SEED_PREFIX.as_bytes(), outcome.as_ref()], &prediction_market::ID).0
+}
+
+pub fn player_address(player_id: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[Player::SEED_PREFIX.as_bytes(), player_id.to_le_bytes().as_ref()],
+        &prediction_market::ID,
+    )
+    .0
+}
+
+pub fn prop_address(market: Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[Prop::SEED_PREFIX.as_bytes(), market.as_ref()], &prediction_market::ID).0
+}
+
+pub fn liquidity_pool_address(market: Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[LiquidityPool::SEED_PREFIX.as_bytes(), market.as_ref()], &prediction_market::ID).0
+}
+
+pub fn lp_mint_address(market: Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[LiquidityPool::LP_MINT_SEED_PREFIX.as_bytes(), market.as_ref()], &prediction_market::ID).0
+}
+
+pub fn house_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[House::SEED_PREFIX.as_bytes(), mint.as_ref()], &prediction_market::ID).0
+}
+
+pub fn parlay_address(user: Pubkey, parlay_id: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[Parlay::SEED_PREFIX.as_bytes(), user.as_ref(), parlay_id.to_le_bytes().as_ref()],
+        &prediction_market::ID,
+    )
+    .0
+}
+
+pub fn user_position_address(user: Pubkey, market: Pubkey, outcome_id: u8) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            b"user_position",
+            user.as_ref(),
+            market.as_ref(),
+            &outcome_id.to_le_bytes(),
+        ],
+        &prediction_market::ID,
+    )
+    .0
+}
+
+pub fn market_stake_address(user: Pubkey, market: Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[MarketStake::SEED_PREFIX.as_bytes(), market.as_ref(), user.as_ref()],
+        &prediction_market::ID,
+    )
+    .0
+}
+
+// -------------------------------------------------------------------------
+// Assertions
+// -------------------------------------------------------------------------
+
+fn custom_error_code<T: std::fmt::Debug>(result: Result<T, BanksClientError>) -> u32 {
+    match result.expect_err("transaction should have failed").unwrap() {
+        TransactionError::InstructionError(_, InstructionError::Custom(code)) => code,
+        other => panic!("expected a custom program error, got {:?}", other),
+    }
+}
+
+pub fn assert_program_error<T: std::fmt::Debug>(result: Result<T, BanksClientError>, expected: PredictionMarketError) {
+    assert_eq!(
+        custom_error_code(result),
+        u32::from(expected),
+        "expected {:?}",
+        expected
+    );
+}
+
+pub fn assert_anchor_error<T: std::fmt::Debug>(result: Result<T, BanksClientError>, expected: anchor_lang::error::ErrorCode) {
+    assert_eq!(
+        custom_error_code(result),
+        u32::from(expected),
+        "expected {:?}",
+        expected
+    );
+}
@@ -0,0 +1,92 @@
+//! Conditional markets: a market that only stands if a parent market
+//! resolves to a given outcome, and is refunded otherwise.
+
+mod common;
+
+use common::*;
+use prediction_market::errors::PredictionMarketError;
+use prediction_market::state::{Market, MarketCondition, MarketStatus};
+use solana_sdk::pubkey::Pubkey;
+
+/// "Map 1" and "If Team 0 wins Map 1, who wins the series?", with a bet on
+/// each outcome of the series.
+async fn conditional_series(env: &mut TestEnv) -> (Pubkey, Pubkey, [User; 2]) {
+    let map_one = env.create_market_with_outcomes(2).await;
+    let series = env.create_market_with_outcomes(2).await;
+    env.set_market_condition(series, map_one, 0).await.unwrap();
+
+    let users = [env.create_user(300).await, env.create_user(300).await];
+    env.place_bet(&users[0], map_one, 0, 100).await.unwrap();
+    env.place_bet(&users[0], series, 0, 200).await.unwrap();
+    env.place_bet(&users[1], series, 1, 300).await.unwrap();
+    (map_one, series, users)
+}
+
+#[tokio::test]
+async fn met_condition_resolves_normally() {
+    let mut env = TestEnv::new().await;
+    let (map_one, series, users) = conditional_series(&mut env).await;
+    let condition: MarketCondition = env.account(condition_address(series)).await;
+    assert_eq!((condition.parent_market, condition.parent_outcome_id), (map_one, 0));
+    assert!(env.account::<Market>(series).await.has_condition);
+
+    // Settlement waits for the parent
+    let end_time = env.account::<Market>(series).await.end_time;
+    env.warp_to(end_time).await;
+    let result = env.resolve_market(series, 0).await;
+    assert_program_error(result, PredictionMarketError::ConditionNotSettled);
+
+    env.resolve_market(map_one, 0).await.unwrap();
+    env.resolve_market(series, 0).await.unwrap();
+    assert_eq!(env.account::<Market>(series).await.status, MarketStatus::Resolved);
+
+    // 2% fee on a pool of 500
+    env.claim_payout(&users[0], series, 0).await.unwrap();
+    assert_eq!(env.token_balance(users[0].token_account).await, 490);
+}
+
+#[tokio::test]
+async fn failed_condition_refunds_every_position() {
+    let mut env = TestEnv::new().await;
+    let (map_one, series, users) = conditional_series(&mut env).await;
+    let bettor = env.create_user(100).await;
+    env.place_bet(&bettor, map_one, 1, 100).await.unwrap();
+
+    env.end_and_resolve(map_one, 1).await;
+    env.resolve_market(series, 0).await.unwrap();
+    assert_eq!(env.account::<Market>(series).await.status, MarketStatus::Cancelled);
+
+    let result = env.claim_payout(&users[0], series, 0).await;
+    assert!(result.is_err());
+    env.claim_refund(&users[0], series, 0).await.unwrap();
+    env.claim_refund(&users[1], series, 1).await.unwrap();
+    assert_eq!(env.token_balance(users[0].token_account).await, 200);
+    assert_eq!(env.token_balance(users[1].token_account).await, 300);
+}
+
+#[tokio::test]
+async fn cancelled_parent_refunds_the_market() {
+    let mut env = TestEnv::new().await;
+    let (map_one, series, users) = conditional_series(&mut env).await;
+    env.cancel_market(map_one).await.unwrap();
+
+    // No need to wait for the market's own end
+    env.resolve_market(series, 1).await.unwrap();
+    env.claim_refund(&users[1], series, 1).await.unwrap();
+    assert_eq!(env.token_balance(users[1].token_account).await, 300);
+}
+
+#[tokio::test]
+async fn condition_is_set_before_betting() {
+    let mut env = TestEnv::new().await;
+    let map_one = env.create_market_with_outcomes(2).await;
+    let series = env.create_market_with_outcomes(2).await;
+
+    let result = env.set_market_condition(series, map_one, 2).await;
+    assert_program_error(result, PredictionMarketError::OutcomeNotFound);
+
+    let user = env.create_user(100).await;
+    env.place_bet(&user, series, 0, 100).await.unwrap();
+    let result = env.set_market_condition(series, map_one, 0).await;
+    assert_program_error(result, PredictionMarketError::MarketHasBets);
+}
@@ -0,0 +1,109 @@
+//! Creator fees: a market's creator earns a basis-point fee on the pool at
+//! settlement, on top of the platform fee and bounded by a platform maximum.
+
+mod common;
+
+use common::*;
+use prediction_market::errors::PredictionMarketError;
+use prediction_market::instruction;
+use prediction_market::state::{Market, PlatformConfig};
+
+#[tokio::test]
+async fn creator_is_paid_at_settlement() {
+    let mut env = TestEnv::new().await;
+    env.set_max_creator_fee(500).await.unwrap();
+    let market = env.create_market_with_outcomes(2).await;
+
+    let organizer = env.create_user(0).await;
+    env.set_creator_fee(market, organizer.pubkey(), 150).await.unwrap();
+    let stored: Market = env.account(market).await;
+    assert_eq!(stored.creator, organizer.pubkey());
+    assert_eq!(stored.creator_fee_bps, 150);
+
+    let alice = env.create_user(600).await;
+    let bob = env.create_user(400).await;
+    env.place_bet(&alice, market, 0, 600).await.unwrap();
+    env.place_bet(&bob, market, 1, 400).await.unwrap();
+
+    // 2% platform fee and 1.5% creator fee on a pool of 1000
+    env.end_and_resolve(market, 0).await;
+    assert_eq!(env.token_balance(env.treasury_token_account).await, 20);
+    assert_eq!(env.token_balance(organizer.token_account).await, 15);
+
+    let stored: Market = env.account(market).await;
+    assert_eq!(stored.distributable_pool(600).unwrap(), 965);
+    env.claim_payout(&alice, market, 0).await.unwrap();
+    assert_eq!(env.token_balance(alice.token_account).await, 965);
+    assert_eq!(env.token_balance(env.escrow_token_account(market, 0)).await, 0);
+}
+
+#[tokio::test]
+async fn resolution_needs_the_creator_account() {
+    let mut env = TestEnv::new().await;
+    env.set_max_creator_fee(500).await.unwrap();
+    let market = env.create_market_with_outcomes(2).await;
+    let organizer = env.create_user(0).await;
+    env.set_creator_fee(market, organizer.pubkey(), 100).await.unwrap();
+
+    let alice = env.create_user(100).await;
+    env.place_bet(&alice, market, 0, 100).await.unwrap();
+    let end_time = env.account::<Market>(market).await.end_time;
+    env.warp_to(end_time).await;
+
+    // The harness passes the creator's account itself, so leave it out here
+    let accounts = env.resolve_market_accounts(market, 0);
+    let mut ix = program_instruction(accounts, instruction::ResolveMarket { winning_outcome_id: 0 });
+    ix.accounts.extend(env.settlement_accounts(market, 0).await);
+    let admin = env.admin.insecure_clone();
+    let result = env.process(&[ix], &[&admin]).await;
+    assert_program_error(result, PredictionMarketError::InvalidSettlementAccounts);
+}
+
+#[tokio::test]
+async fn maximum_is_enforced_and_fees_are_fixed_before_betting() {
+    let mut env = TestEnv::new().await;
+    let market = env.create_market_with_outcomes(2).await;
+    let organizer = env.create_user(0).await;
+
+    // Creator fees are off until the admin allows them
+    let config: PlatformConfig = env.platform_config().await;
+    assert_eq!(config.max_creator_fee_bps, 0);
+    let result = env.set_creator_fee(market, organizer.pubkey(), 1).await;
+    assert_program_error(result, PredictionMarketError::CreatorFeeTooHigh);
+
+    env.set_max_creator_fee(200).await.unwrap();
+    env.set_creator_fee(market, organizer.pubkey(), 200).await.unwrap();
+
+    let alice = env.create_user(100).await;
+    env.place_bet(&alice, market, 0, 100).await.unwrap();
+    let result = env.set_creator_fee(market, organizer.pubkey(), 0).await;
+    assert_program_error(result, PredictionMarketError::MarketHasBets);
+}
+
+#[tokio::test]
+async fn creators_add_outcomes_until_the_first_bet() {
+    let mut env = TestEnv::new().await;
+    let market = env.create_market_with_outcomes(2).await;
+    let organizer = env.create_user(100).await;
+    env.set_creator_fee(market, organizer.pubkey(), 0).await.unwrap();
+
+    // Anyone else is turned away
+    let stranger = env.create_user(0).await;
+    let mut accounts = env.add_outcome_accounts(market, 2);
+    accounts.authority = stranger.pubkey();
+    let args = instruction::AddOutcome { name: "Team 2 wins".to_string(), outcome_id: 2 };
+    let result = env.send(accounts, args, &[&stranger.keypair]).await;
+    assert_program_error(result, PredictionMarketError::UnauthorizedAdmin);
+
+    let mut accounts = env.add_outcome_accounts(market, 2);
+    accounts.authority = organizer.pubkey();
+    let args = instruction::AddOutcome { name: "Team 2 wins".to_string(), outcome_id: 2 };
+    env.send(accounts, args, &[&organizer.keypair]).await.unwrap();
+    assert_eq!(env.account::<Market>(market).await.outcomes, vec![0, 1, 2]);
+
+    // Once there is stake, an outcome added would shift the odds it was
+    // placed at
+    env.place_bet(&organizer, market, 0, 100).await.unwrap();
+    let result = env.add_outcome(market, 3).await;
+    assert_program_error(result, PredictionMarketError::MarketHasBets);
+}
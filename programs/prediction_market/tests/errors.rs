@@ -0,0 +1,1008 @@
+//! One test per `PredictionMarketError` variant.
+
+mod common;
+
+use common::*;
+use prediction_market::errors::PredictionMarketError;
+use prediction_market::{accounts, instruction};
+use prediction_market::state::{
+    AccessGate, BetLimits, CollateralMode, FeeModel, FeeTier, LegLimits, Market, ParlayLimits, PlatformConfig, StatKind, Tournament, MAX_MARKET_DESCRIPTION_LEN,
+    MAX_LOGO_URI_LEN, MAX_MARKET_NAME_LEN, MAX_OUTCOMES, MAX_OUTCOME_NAME_LEN, MAX_TOURNAMENT_MATCHES,
+};
+use solana_program_test::BanksClientError;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_system_interface::program as system_program;
+
+#[tokio::test]
+async fn platform_paused() {
+    let mut env = TestEnv::new().await;
+    let mut config: PlatformConfig = env.platform_config().await;
+    config.paused = true;
+    env.set_account(platform_config(), &config).await;
+
+    let now = env.now().await;
+    let result = env
+        .create_market_with_times(now + MARKET_START_OFFSET, now + MARKET_END_OFFSET)
+        .await;
+    assert_program_error(result, PredictionMarketError::PlatformPaused);
+}
+
+#[tokio::test]
+async fn invalid_time_range() {
+    let mut env = TestEnv::new().await;
+    let now = env.now().await;
+    let result = env
+        .create_market_with_times(now + MARKET_START_OFFSET, now + MARKET_START_OFFSET)
+        .await;
+    assert_program_error(result, PredictionMarketError::InvalidTimeRange);
+}
+
+#[tokio::test]
+async fn invalid_start_time() {
+    let mut env = TestEnv::new().await;
+    let now = env.now().await;
+    let result = env.create_market_with_times(now, now + MARKET_END_OFFSET).await;
+    assert_program_error(result, PredictionMarketError::InvalidStartTime);
+}
+
+#[tokio::test]
+async fn market_not_active() {
+    let mut env = TestEnv::new().await;
+    let market = env.create_market_with_outcomes(1).await;
+    env.cancel_market(market).await.unwrap();
+
+    let result = env.add_outcome(market, 1).await;
+    assert_program_error(result, PredictionMarketError::MarketNotActive);
+}
+
+#[tokio::test]
+async fn market_already_closed() {
+    let mut env = TestEnv::new().await;
+    let market = env.create_market_with_outcomes(2).await;
+    let user = env.create_user(100).await;
+
+    let end_time = env.account::<Market>(market).await.end_time;
+    env.warp_to(end_time).await;
+
+    let result = env.place_bet(&user, market, 0, 100).await;
+    assert_program_error(result, PredictionMarketError::MarketAlreadyClosed);
+}
+
+#[tokio::test]
+async fn market_not_resolved() {
+    let mut env = TestEnv::new().await;
+    let market = env.create_market_with_outcomes(2).await;
+    let user = env.create_user(100).await;
+    env.place_bet(&user, market, 0, 100).await.unwrap();
+
+    let result = env.claim_payout(&user, market, 0).await;
+    assert_program_error(result, PredictionMarketError::MarketNotResolved);
+}
+
+#[tokio::test]
+async fn invalid_bet_amount() {
+    let mut env = TestEnv::new().await;
+    let market = env.create_market_with_outcomes(2).await;
+    let user = env.create_user(100).await;
+
+    let result = env.place_bet(&user, market, 0, 0).await;
+    assert_program_error(result, PredictionMarketError::InvalidBetAmount);
+}
+
+#[tokio::test]
+async fn unauthorized_oracle() {
+    let mut env = TestEnv::new().await;
+    let market = env.create_market_with_outcomes(2).await;
+    let end_time = env.account::<Market>(market).await.end_time;
+    env.warp_to(end_time).await;
+
+    let impostor = Keypair::new();
+    let mut accounts = env.resolve_market_accounts(market, 0);
+    accounts.oracle = impostor.pubkey();
+    let result = env
+        .send(accounts, instruction::ResolveMarket { winning_outcome_id: 0 }, &[&impostor])
+        .await;
+    assert_program_error(result, PredictionMarketError::UnauthorizedOracle);
+}
+
+#[tokio::test]
+async fn already_claimed() {
+    let mut env = TestEnv::new().await;
+    let market = env.create_market_with_outcomes(2).await;
+    let user = env.create_user(100).await;
+    env.place_bet(&user, market, 0, 100).await.unwrap();
+    env.end_and_resolve(market, 0).await;
+    env.claim_payout(&user, market, 0).await.unwrap();
+
+    let result = env.claim_payout(&user, market, 0).await;
+    assert_program_error(result, PredictionMarketError::AlreadyClaimed);
+}
+
+#[tokio::test]
+async fn insufficient_liquidity() {
+    let mut env = TestEnv::new().await;
+    let admin = env.admin.pubkey();
+    let admin_token_account = env.create_token_account(&admin).await;
+
+    let result = env.withdraw_fees(admin_token_account).await;
+    assert_program_error(result, PredictionMarketError::InsufficientLiquidity);
+}
+
+#[tokio::test]
+async fn outcome_not_found() {
+    let mut env = TestEnv::new().await;
+    let market = env.create_market_with_outcomes(2).await;
+    let end_time = env.account::<Market>(market).await.end_time;
+    env.warp_to(end_time).await;
+
+    let accounts = env.resolve_market_accounts(market, 0);
+    let result = env
+        .send_as_admin(accounts, instruction::ResolveMarket { winning_outcome_id: 7 })
+        .await;
+    assert_program_error(result, PredictionMarketError::OutcomeNotFound);
+}
+
+#[tokio::test]
+async fn not_winner() {
+    let mut env = TestEnv::new().await;
+    let market = env.create_market_with_outcomes(2).await;
+    let winner = env.create_user(100).await;
+    let loser = env.create_user(100).await;
+    env.place_bet(&winner, market, 0, 100).await.unwrap();
+    env.place_bet(&loser, market, 1, 100).await.unwrap();
+    env.end_and_resolve(market, 0).await;
+
+    let result = env.claim_payout(&loser, market, 1).await;
+    assert_program_error(result, PredictionMarketError::NotWinner);
+}
+
+#[tokio::test]
+async fn unauthorized_admin() {
+    let mut env = TestEnv::new().await;
+    let impostor = Keypair::new();
+    env.airdrop(&impostor.pubkey(), 1_000_000_000).await;
+
+    let now = env.now().await;
+    let mut accounts = env.create_market_accounts(0);
+    accounts.creator = impostor.pubkey();
+    let result = env
+        .send(
+            accounts,
+            instruction::CreateMarket {
+                name: "Fake".to_string(),
+                description: "Fake".to_string(),
+                start_time: now + MARKET_START_OFFSET,
+                end_time: now + MARKET_END_OFFSET,
+                custom_fee_bps: None,
+                custom_oracle: None,
+                collateral_mode: CollateralMode::Token,
+            },
+            &[&impostor],
+        )
+        .await;
+    assert_program_error(result, PredictionMarketError::UnauthorizedAdmin);
+}
+
+#[tokio::test]
+async fn market_not_ended() {
+    let mut env = TestEnv::new().await;
+    let market = env.create_market_with_outcomes(2).await;
+
+    let result = env.resolve_market(market, 0).await;
+    assert_program_error(result, PredictionMarketError::MarketNotEnded);
+}
+
+#[tokio::test]
+async fn math_overflow() {
+    let mut env = TestEnv::new().await;
+    let market_address = env.create_market_with_outcomes(2).await;
+    let user = env.create_user(100).await;
+
+    let mut market: Market = env.account(market_address).await;
+    market.total_pool = u64::MAX;
+    env.set_account(market_address, &market).await;
+
+    let result = env.place_bet(&user, market_address, 0, 1).await;
+    assert_program_error(result, PredictionMarketError::MathOverflow);
+}
+
+#[tokio::test]
+async fn invalid_mint() {
+    let mut env = TestEnv::new().await;
+    let market = env.create_market_with_outcomes(2).await;
+    let user = env.create_user(100).await;
+
+    // The user's token account is for the collateral mint, not this one
+    let other_mint = env.create_other_mint().await;
+    let mut accounts = env.place_bet_accounts(&user, market, 0);
+    accounts.mint = other_mint;
+    let result = env
+        .send(accounts, instruction::PlaceBet { amount: 100 }, &[&user.keypair])
+        .await;
+    assert_program_error(result, PredictionMarketError::InvalidMint);
+}
+
+#[tokio::test]
+async fn market_already_resolved() {
+    let mut env = TestEnv::new().await;
+    let market = env.create_market_with_outcomes(2).await;
+    let user = env.create_user(100).await;
+    env.place_bet(&user, market, 0, 100).await.unwrap();
+    env.end_and_resolve(market, 0).await;
+
+    let result = env.resolve_market(market, 1).await;
+    assert_program_error(result, PredictionMarketError::MarketAlreadyResolved);
+}
+
+#[tokio::test]
+async fn market_not_cancelled() {
+    let mut env = TestEnv::new().await;
+    let market = env.create_market_with_outcomes(2).await;
+    let user = env.create_user(100).await;
+    env.place_bet(&user, market, 0, 100).await.unwrap();
+
+    let result = env.claim_refund(&user, market, 0).await;
+    assert_program_error(result, PredictionMarketError::MarketNotCancelled);
+}
+
+#[tokio::test]
+async fn no_winning_stake() {
+    let mut env = TestEnv::new().await;
+    let market = env.create_market_with_outcomes(2).await;
+    let user = env.create_user(100).await;
+    env.place_bet(&user, market, 1, 100).await.unwrap();
+    let end_time = env.account::<Market>(market).await.end_time;
+    env.warp_to(end_time).await;
+
+    let result = env.resolve_market(market, 0).await;
+    assert_program_error(result, PredictionMarketError::NoWinningStake);
+}
+
+#[tokio::test]
+async fn invalid_settlement_accounts() {
+    let mut env = TestEnv::new().await;
+    let market = env.create_market_with_outcomes(2).await;
+    let user = env.create_user(100).await;
+    env.place_bet(&user, market, 0, 100).await.unwrap();
+    let end_time = env.account::<Market>(market).await.end_time;
+    env.warp_to(end_time).await;
+
+    // Without the losing escrow the pool can't be settled
+    let accounts = env.resolve_market_accounts(market, 0);
+    let result = env
+        .send_as_admin(accounts, instruction::ResolveMarket { winning_outcome_id: 0 })
+        .await;
+    assert_program_error(result, PredictionMarketError::InvalidSettlementAccounts);
+}
+
+#[tokio::test]
+async fn collateral_not_allowed() {
+    let mut env = TestEnv::new().await;
+    let mint = env.mint;
+    env.set_collateral_enabled(mint, false).await.unwrap();
+
+    let now = env.now().await;
+    let result = env
+        .create_market_with_times(now + MARKET_START_OFFSET, now + MARKET_END_OFFSET)
+        .await;
+    assert_program_error(result, PredictionMarketError::CollateralNotAllowed);
+}
+
+#[tokio::test]
+async fn unsupported_mint_extension() {
+    let mut env = TestEnv::new().await;
+    let mint = env.create_token_2022_mint(&[MintExtension::PermanentDelegate]).await;
+
+    let result = env.add_collateral_mint(mint).await;
+    assert_program_error(result, PredictionMarketError::UnsupportedMintExtension);
+}
+
+#[tokio::test]
+async fn invalid_collateral_mode() {
+    let mut env = TestEnv::new().await;
+
+    // Native SOL betting needs wrapped SOL escrows
+    env.collateral_mode = CollateralMode::NativeSol;
+    let now = env.now().await;
+    let result = env
+        .create_market_with_times(now + MARKET_START_OFFSET, now + MARKET_END_OFFSET)
+        .await;
+    assert_program_error(result, PredictionMarketError::InvalidCollateralMode);
+}
+
+/// Creates a market whose bets are limited to 10..=100, positions to 150 and
+/// the pool to 250.
+async fn limited_market(env: &mut TestEnv) -> Pubkey {
+    let market = env.create_market_with_outcomes(2).await;
+    let limits = BetLimits { min_bet: 10, max_bet: 100, max_position: 150, max_pool: 250 };
+    env.set_bet_limits(market, limits).await.unwrap();
+    market
+}
+
+#[tokio::test]
+async fn bet_below_minimum() {
+    let mut env = TestEnv::new().await;
+    let market = limited_market(&mut env).await;
+    let user = env.create_user(1_000).await;
+
+    let result = env.place_bet(&user, market, 0, 9).await;
+    assert_program_error(result, PredictionMarketError::BetBelowMinimum);
+}
+
+#[tokio::test]
+async fn bet_above_maximum() {
+    let mut env = TestEnv::new().await;
+    let market = limited_market(&mut env).await;
+    let user = env.create_user(1_000).await;
+
+    let result = env.place_bet(&user, market, 0, 101).await;
+    assert_program_error(result, PredictionMarketError::BetAboveMaximum);
+}
+
+#[tokio::test]
+async fn position_limit_exceeded() {
+    let mut env = TestEnv::new().await;
+    let market = limited_market(&mut env).await;
+    let user = env.create_user(1_000).await;
+
+    env.place_bet(&user, market, 0, 100).await.unwrap();
+    let result = env.place_bet(&user, market, 0, 51).await;
+    assert_program_error(result, PredictionMarketError::PositionLimitExceeded);
+}
+
+#[tokio::test]
+async fn pool_limit_exceeded() {
+    let mut env = TestEnv::new().await;
+    let market = limited_market(&mut env).await;
+    let alice = env.create_user(1_000).await;
+    let bob = env.create_user(1_000).await;
+    let carol = env.create_user(1_000).await;
+
+    env.place_bet(&alice, market, 0, 100).await.unwrap();
+    env.place_bet(&bob, market, 1, 100).await.unwrap();
+    let result = env.place_bet(&carol, market, 1, 51).await;
+    assert_program_error(result, PredictionMarketError::PoolLimitExceeded);
+}
+
+#[tokio::test]
+async fn invalid_bet_limits() {
+    let mut env = TestEnv::new().await;
+
+    let result = env
+        .set_default_bet_limits(BetLimits { min_bet: 100, max_bet: 50, ..BetLimits::default() })
+        .await;
+    assert_program_error(result, PredictionMarketError::InvalidBetLimits);
+}
+
+#[tokio::test]
+async fn self_excluded() {
+    let mut env = TestEnv::new().await;
+    let market = env.create_market_with_outcomes(2).await;
+    let user = env.create_user(100).await;
+
+    let now = env.now().await;
+    env.set_self_exclusion(&user, now + MARKET_END_OFFSET).await.unwrap();
+    let result = env.place_bet(&user, market, 0, 100).await;
+    assert_program_error(result, PredictionMarketError::SelfExcluded);
+}
+
+#[tokio::test]
+async fn daily_deposit_limit_exceeded() {
+    let mut env = TestEnv::new().await;
+    let market = env.create_market_with_outcomes(2).await;
+    let user = env.create_user(100).await;
+
+    env.set_deposit_limits(&user, 50, 0).await.unwrap();
+    let result = env.place_bet(&user, market, 0, 51).await;
+    assert_program_error(result, PredictionMarketError::DailyDepositLimitExceeded);
+}
+
+#[tokio::test]
+async fn weekly_deposit_limit_exceeded() {
+    let mut env = TestEnv::new().await;
+    let market = env.create_market_with_outcomes(2).await;
+    let user = env.create_user(100).await;
+
+    env.set_deposit_limits(&user, 0, 50).await.unwrap();
+    let result = env.place_bet(&user, market, 0, 51).await;
+    assert_program_error(result, PredictionMarketError::WeeklyDepositLimitExceeded);
+}
+
+#[tokio::test]
+async fn profile_cooldown_active() {
+    let mut env = TestEnv::new().await;
+    let user = env.create_user(100).await;
+
+    let now = env.now().await;
+    env.set_self_exclusion(&user, now + 30 * 24 * 60 * 60).await.unwrap();
+    let result = env.set_self_exclusion(&user, now).await;
+    assert_program_error(result, PredictionMarketError::ProfileCooldownActive);
+}
+
+#[tokio::test]
+async fn not_allowlisted() {
+    let mut env = TestEnv::new().await;
+    let market = env.create_market_with_outcomes(2).await;
+    env.set_access_gate(market, AccessGate::Allowlist).await.unwrap();
+    let user = env.create_user(100).await;
+
+    let result = env.place_bet(&user, market, 0, 100).await;
+    assert_program_error(result, PredictionMarketError::NotAllowlisted);
+}
+
+#[tokio::test]
+async fn invalid_credential() {
+    let mut env = TestEnv::new().await;
+    let market = env.create_market_with_outcomes(2).await;
+    let authority = Keypair::new().pubkey();
+    env.set_access_gate(market, AccessGate::Attestation { authority }).await.unwrap();
+    let user = env.create_user(100).await;
+
+    let result = env.place_bet(&user, market, 0, 100).await;
+    assert_program_error(result, PredictionMarketError::InvalidCredential);
+}
+
+#[tokio::test]
+async fn invalid_referrer() {
+    let mut env = TestEnv::new().await;
+    let market = env.create_market_with_outcomes(2).await;
+    let user = env.create_user(100).await;
+
+    // Users cannot refer themselves
+    let result = env.place_referred_bet(&user, market, 0, 100, user.pubkey()).await;
+    assert_program_error(result, PredictionMarketError::InvalidReferrer);
+}
+
+#[tokio::test]
+async fn invalid_referral_share() {
+    let mut env = TestEnv::new().await;
+
+    let result = env.set_referral_share(10_001).await;
+    assert_program_error(result, PredictionMarketError::InvalidReferralShare);
+}
+
+#[tokio::test]
+async fn creator_fee_too_high() {
+    let mut env = TestEnv::new().await;
+    env.set_max_creator_fee(100).await.unwrap();
+    let market = env.create_market().await;
+
+    let result = env.set_creator_fee(market, Keypair::new().pubkey(), 101).await;
+    assert_program_error(result, PredictionMarketError::CreatorFeeTooHigh);
+}
+
+#[tokio::test]
+async fn market_has_bets() {
+    let mut env = TestEnv::new().await;
+    env.set_max_creator_fee(100).await.unwrap();
+    let market = env.create_market_with_outcomes(2).await;
+    let user = env.create_user(100).await;
+    env.place_bet(&user, market, 0, 100).await.unwrap();
+
+    let result = env.set_creator_fee(market, Keypair::new().pubkey(), 100).await;
+    assert_program_error(result, PredictionMarketError::MarketHasBets);
+}
+
+#[tokio::test]
+async fn fee_too_high() {
+    let mut env = TestEnv::new().await;
+
+    let result = env.create_market_with_fee(PlatformConfig::MAX_FEE_BPS + 1).await;
+    assert_program_error(result, PredictionMarketError::FeeTooHigh);
+}
+
+#[tokio::test]
+async fn invalid_fee_tiers() {
+    let mut env = TestEnv::new().await;
+
+    let mut tiers = [FeeTier::default(); PlatformConfig::FEE_TIERS];
+    tiers[0] = FeeTier { min_volume: 1_000, discount_bps: 10_001 };
+    let result = env.set_fee_tiers(tiers).await;
+    assert_program_error(result, PredictionMarketError::InvalidFeeTiers);
+}
+
+#[tokio::test]
+async fn invalid_migration() {
+    let mut env = TestEnv::new().await;
+
+    // The platform config is already in the current layout
+    let result = env
+        .send_as_admin(
+            accounts::MigratePlatformConfig {
+                platform_config: platform_config(),
+                payer: env.admin.pubkey(),
+                system_program: system_program::ID,
+            },
+            instruction::MigratePlatformConfig {},
+        )
+        .await;
+    assert_program_error(result, PredictionMarketError::InvalidMigration);
+}
+
+#[tokio::test]
+async fn invalid_treasury() {
+    let mut env = TestEnv::new().await;
+    let market = env.create_market_with_outcomes(2).await;
+    env.set_fee_model(market, FeeModel::AtBet).await.unwrap();
+    let user = env.create_user(100).await;
+
+    // The harness passes the treasury itself, so leave it out here
+    let accounts = env.place_bet_accounts(&user, market, 0);
+    let result = env
+        .send(accounts, instruction::PlaceBet { amount: 100 }, &[&user.keypair])
+        .await;
+    assert_program_error(result, PredictionMarketError::InvalidTreasury);
+}
+
+#[tokio::test]
+async fn too_many_outcomes() {
+    let mut env = TestEnv::new().await;
+    let market = env.create_market_with_outcomes(MAX_OUTCOMES as u8).await;
+
+    let result = env.add_outcome(market, MAX_OUTCOMES as u8).await;
+    assert_program_error(result, PredictionMarketError::TooManyOutcomes);
+}
+
+#[tokio::test]
+async fn duplicate_outcome() {
+    let mut env = TestEnv::new().await;
+    let market = env.create_market_with_outcomes(2).await;
+
+    let result = env.add_outcome(market, 1).await;
+    assert_program_error(result, PredictionMarketError::DuplicateOutcome);
+}
+
+/// Creates a market with the given name and description
+async fn create_named_market(env: &mut TestEnv, name: String, description: String) -> Result<(), BanksClientError> {
+    let now = env.now().await;
+    let markets_count = env.platform_config().await.markets_count;
+    let accounts = env.create_market_accounts(markets_count);
+    env.send_as_admin(
+        accounts,
+        instruction::CreateMarket {
+            name,
+            description,
+            start_time: now + MARKET_START_OFFSET,
+            end_time: now + MARKET_END_OFFSET,
+            custom_fee_bps: None,
+            custom_oracle: None,
+            collateral_mode: CollateralMode::Token,
+        },
+    )
+    .await
+}
+
+#[tokio::test]
+async fn name_too_long() {
+    let mut env = TestEnv::new().await;
+
+    let result = create_named_market(&mut env, "x".repeat(MAX_MARKET_NAME_LEN + 1), String::new()).await;
+    assert_program_error(result, PredictionMarketError::NameTooLong);
+
+    // Outcome names are bounded too
+    let market = env.create_market().await;
+    let accounts = env.add_outcome_accounts(market, 0);
+    let result = env
+        .send_as_admin(
+            accounts,
+            instruction::AddOutcome { name: "x".repeat(MAX_OUTCOME_NAME_LEN + 1), outcome_id: 0 },
+        )
+        .await;
+    assert_program_error(result, PredictionMarketError::NameTooLong);
+}
+
+#[tokio::test]
+async fn description_too_long() {
+    let mut env = TestEnv::new().await;
+
+    let result = create_named_market(&mut env, String::new(), "x".repeat(MAX_MARKET_DESCRIPTION_LEN + 1)).await;
+    assert_program_error(result, PredictionMarketError::DescriptionTooLong);
+
+    // Names and descriptions at the limit fit the account
+    create_named_market(&mut env, "x".repeat(MAX_MARKET_NAME_LEN), "x".repeat(MAX_MARKET_DESCRIPTION_LEN))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn invalid_market_book() {
+    let mut env = TestEnv::new().await;
+    let market = env.create_market_with_outcomes(2).await;
+    env.create_market_book(market).await.unwrap();
+
+    let user = env.create_user(100).await;
+    let accounts = env.place_bet_accounts(&user, market, 0);
+    let result = env.send(accounts, instruction::PlaceBet { amount: 100 }, &[&user.keypair]).await;
+    assert_program_error(result, PredictionMarketError::InvalidMarketBook);
+}
+
+#[tokio::test]
+async fn market_already_in_tournament() {
+    let mut env = TestEnv::new().await;
+    let outright = env.create_market().await;
+    let tournament = env.create_tournament(outright).await.unwrap();
+
+    let result = env.create_tournament(outright).await;
+    assert_program_error(result, PredictionMarketError::MarketAlreadyInTournament);
+    let result = env.add_tournament_match(tournament, outright, false).await;
+    assert_program_error(result, PredictionMarketError::MarketAlreadyInTournament);
+}
+
+#[tokio::test]
+async fn too_many_tournament_matches() {
+    let mut env = TestEnv::new().await;
+    let outright = env.create_market().await;
+    let tournament = env.create_tournament(outright).await.unwrap();
+    let mut stored: Tournament = env.account(tournament).await;
+    stored.matches = vec![Pubkey::new_unique(); MAX_TOURNAMENT_MATCHES];
+    env.set_account(tournament, &stored).await;
+
+    let match_market = env.create_market().await;
+    let result = env.add_tournament_match(tournament, match_market, false).await;
+    assert_program_error(result, PredictionMarketError::TooManyTournamentMatches);
+}
+
+#[tokio::test]
+async fn grand_final_already_set() {
+    let mut env = TestEnv::new().await;
+    let outright = env.create_market().await;
+    let tournament = env.create_tournament(outright).await.unwrap();
+    let grand_final = env.create_market().await;
+    env.add_tournament_match(tournament, grand_final, true).await.unwrap();
+
+    let other = env.create_market().await;
+    let result = env.add_tournament_match(tournament, other, true).await;
+    assert_program_error(result, PredictionMarketError::GrandFinalAlreadySet);
+}
+
+#[tokio::test]
+async fn invalid_tournament() {
+    let mut env = TestEnv::new().await;
+    let outright = env.create_market_with_outcomes(2).await;
+    env.create_tournament(outright).await.unwrap();
+    let user = env.create_user(100).await;
+    env.place_bet(&user, outright, 0, 100).await.unwrap();
+    let end_time = env.account::<Market>(outright).await.end_time;
+    env.warp_to(end_time).await;
+
+    // The harness passes the tournament itself, so leave it out here
+    let accounts = env.resolve_market_accounts(outright, 0);
+    let mut ix = program_instruction(accounts, instruction::ResolveMarket { winning_outcome_id: 0 });
+    ix.accounts.extend(env.settlement_accounts(outright, 0).await);
+    let admin = env.admin.insecure_clone();
+    let result = env.process(&[ix], &[&admin]).await;
+    assert_program_error(result, PredictionMarketError::InvalidTournament);
+}
+
+#[tokio::test]
+async fn outcome_eliminated() {
+    let mut env = TestEnv::new().await;
+    let outright = env.create_market_with_outcomes(2).await;
+    let tournament = env.create_tournament(outright).await.unwrap();
+    env.eliminate_outcome(tournament, 1).await.unwrap();
+
+    let result = env.eliminate_outcome(tournament, 1).await;
+    assert_program_error(result, PredictionMarketError::OutcomeEliminated);
+    let user = env.create_user(100).await;
+    let result = env.place_bet(&user, outright, 1, 100).await;
+    assert_program_error(result, PredictionMarketError::OutcomeEliminated);
+}
+
+#[tokio::test]
+async fn grand_final_not_resolved() {
+    let mut env = TestEnv::new().await;
+    let outright = env.create_market_with_outcomes(2).await;
+    let tournament = env.create_tournament(outright).await.unwrap();
+    let user = env.create_user(100).await;
+    env.place_bet(&user, outright, 0, 100).await.unwrap();
+    let end_time = env.account::<Market>(outright).await.end_time;
+    env.warp_to(end_time).await;
+
+    // Even after its end, an outright needs a resolved grand final
+    let result = env.resolve_market(outright, 0).await;
+    assert_program_error(result, PredictionMarketError::GrandFinalNotResolved);
+
+    let grand_final = env.create_market().await;
+    env.add_tournament_match(tournament, grand_final, true).await.unwrap();
+    let result = env.resolve_market(outright, 0).await;
+    assert_program_error(result, PredictionMarketError::GrandFinalNotResolved);
+}
+
+#[tokio::test]
+async fn not_grand_final_winner() {
+    let mut env = TestEnv::new().await;
+    let teams = env.create_teams(2).await;
+    let outright = env.create_market_with_teams(&teams).await;
+    let tournament = env.create_tournament(outright).await.unwrap();
+    let grand_final = env.create_market_with_teams(&teams).await;
+    env.add_tournament_match(tournament, grand_final, true).await.unwrap();
+    let user = env.create_user(200).await;
+    env.place_bet(&user, outright, 1, 100).await.unwrap();
+    env.place_bet(&user, grand_final, 0, 100).await.unwrap();
+    env.end_and_resolve(grand_final, 0).await;
+
+    let result = env.resolve_market(outright, 1).await;
+    assert_program_error(result, PredictionMarketError::NotGrandFinalWinner);
+}
+
+/// A house with 1000 of liquidity and two markets a parlay can be placed on.
+async fn parlay_markets(env: &mut TestEnv) -> (Pubkey, Pubkey) {
+    env.create_house(PARLAY_LIMITS, 1_000).await.unwrap();
+    let first = env.create_market_with_outcomes(2).await;
+    let second = env.create_market_with_outcomes(2).await;
+    let bettor = env.create_user(400).await;
+    for market in [first, second] {
+        env.place_bet(&bettor, market, 0, 100).await.unwrap();
+        env.place_bet(&bettor, market, 1, 100).await.unwrap();
+    }
+    (first, second)
+}
+
+#[tokio::test]
+async fn invalid_parlay_leg() {
+    let mut env = TestEnv::new().await;
+    let (first, _) = parlay_markets(&mut env).await;
+    let user = env.create_user(100).await;
+
+    let result = env.place_parlay(&user, 0, 100, &[(first, 0), (first, 1)]).await;
+    assert_program_error(result, PredictionMarketError::InvalidParlayLeg);
+}
+
+#[tokio::test]
+async fn invalid_parlay_leg_count() {
+    let mut env = TestEnv::new().await;
+    let (first, _) = parlay_markets(&mut env).await;
+    let user = env.create_user(100).await;
+
+    let result = env.place_parlay(&user, 0, 100, &[(first, 0)]).await;
+    assert_program_error(result, PredictionMarketError::InvalidParlayLegCount);
+}
+
+#[tokio::test]
+async fn parlay_limit_exceeded() {
+    let mut env = TestEnv::new().await;
+    let (first, second) = parlay_markets(&mut env).await;
+    env.set_parlay_limits(ParlayLimits { max_payout: 100, ..PARLAY_LIMITS }).await.unwrap();
+    let user = env.create_user(100).await;
+
+    let result = env.place_parlay(&user, 0, 100, &[(first, 0), (second, 0)]).await;
+    assert_program_error(result, PredictionMarketError::ParlayLimitExceeded);
+}
+
+#[tokio::test]
+async fn invalid_parlay_limits() {
+    let mut env = TestEnv::new().await;
+
+    // An uncapped payout would let one thin leg drain the house
+    let result = env.create_house(ParlayLimits { max_payout: 0, ..PARLAY_LIMITS }, 0).await;
+    assert_program_error(result, PredictionMarketError::InvalidParlayLimits);
+
+    env.create_house(PARLAY_LIMITS, 0).await.unwrap();
+    let result = env.set_house_limits(PARLAY_LIMITS, LegLimits { min_pool: 0, max_odds: 10_000 }).await;
+    assert_program_error(result, PredictionMarketError::InvalidParlayLimits);
+}
+
+#[tokio::test]
+async fn parlay_not_settled() {
+    let mut env = TestEnv::new().await;
+    let (first, second) = parlay_markets(&mut env).await;
+    let user = env.create_user(100).await;
+    env.place_parlay(&user, 0, 100, &[(first, 0), (second, 0)]).await.unwrap();
+
+    let result = env.settle_parlay(&user, 0).await;
+    assert_program_error(result, PredictionMarketError::ParlayNotSettled);
+}
+
+#[tokio::test]
+async fn parlay_already_settled() {
+    let mut env = TestEnv::new().await;
+    let (first, second) = parlay_markets(&mut env).await;
+    let user = env.create_user(100).await;
+    env.place_parlay(&user, 0, 100, &[(first, 0), (second, 0)]).await.unwrap();
+    env.cancel_market(first).await.unwrap();
+    env.cancel_market(second).await.unwrap();
+    env.settle_parlay(&user, 0).await.unwrap();
+
+    let result = env.settle_parlay(&user, 0).await;
+    assert_program_error(result, PredictionMarketError::ParlayAlreadySettled);
+}
+
+#[tokio::test]
+async fn invalid_condition() {
+    let mut env = TestEnv::new().await;
+    let market = env.create_market_with_outcomes(2).await;
+
+    let result = env.set_market_condition(market, market, 0).await;
+    assert_program_error(result, PredictionMarketError::InvalidCondition);
+}
+
+#[tokio::test]
+async fn condition_not_settled() {
+    let mut env = TestEnv::new().await;
+    let parent = env.create_market_with_outcomes(2).await;
+    let market = env.create_market_with_outcomes(2).await;
+    env.set_market_condition(market, parent, 0).await.unwrap();
+    let user = env.create_user(100).await;
+    env.place_bet(&user, market, 0, 100).await.unwrap();
+    let end_time = env.account::<Market>(market).await.end_time;
+    env.warp_to(end_time).await;
+
+    let result = env.resolve_market(market, 0).await;
+    assert_program_error(result, PredictionMarketError::ConditionNotSettled);
+}
+
+#[tokio::test]
+async fn invalid_prop() {
+    let mut env = TestEnv::new().await;
+    let market = env.create_market().await;
+    let team = env.create_team("Sentinels").await.unwrap();
+    let player = env.create_player(team, "TenZ").await.unwrap();
+    env.create_prop_market(market, player, StatKind::Kills, 205).await.unwrap();
+
+    let end_time = env.account::<Market>(market).await.end_time;
+    env.warp_to(end_time).await;
+    let result = env.resolve_market(market, 0).await;
+    assert_program_error(result, PredictionMarketError::InvalidProp);
+}
+
+
+#[tokio::test]
+async fn uri_too_long() {
+    let mut env = TestEnv::new().await;
+    let logo_uri = "u".repeat(MAX_LOGO_URI_LEN + 1);
+
+    let result = env.create_team_with("Sentinels", "SEN", &logo_uri).await;
+    assert_program_error(result, PredictionMarketError::UriTooLong);
+}
+
+#[tokio::test]
+async fn invalid_team() {
+    let mut env = TestEnv::new().await;
+    let sentinels = env.create_team("Sentinels").await.unwrap();
+    let cloud9 = env.create_team("Cloud9").await.unwrap();
+    let market = env.create_market().await;
+    env.add_outcome_for(market, 0, Some(sentinels)).await.unwrap();
+    env.add_outcome_for(market, 1, Some(cloud9)).await.unwrap();
+
+    let result = env.seed_odds(market, cloud9, sentinels).await;
+    assert_program_error(result, PredictionMarketError::InvalidTeam);
+}
+
+#[tokio::test]
+async fn invalid_liquidity_pool() {
+    let mut env = TestEnv::new().await;
+    let market = env.create_market_with_outcomes(2).await;
+    let alice = env.create_user(200).await;
+    env.add_liquidity(&alice, market, 200).await.unwrap();
+
+    let end_time = env.account::<Market>(market).await.end_time;
+    env.warp_to(end_time).await;
+    let mut ix = program_instruction(env.resolve_market_accounts(market, 0), instruction::ResolveMarket { winning_outcome_id: 0 });
+    ix.accounts.extend(env.settlement_accounts(market, 0).await);
+    let admin = env.admin.insecure_clone();
+    let result = env.process(&[ix], &[&admin]).await;
+    assert_program_error(result, PredictionMarketError::InvalidLiquidityPool);
+}
+
+#[tokio::test]
+async fn invalid_liquidity_amount() {
+    let mut env = TestEnv::new().await;
+    let market = env.create_market_with_outcomes(2).await;
+    let alice = env.create_user(200).await;
+
+    let result = env.add_liquidity(&alice, market, 0).await;
+    assert_program_error(result, PredictionMarketError::InvalidLiquidityAmount);
+}
+
+#[tokio::test]
+async fn liquidity_locked() {
+    let mut env = TestEnv::new().await;
+    let market = env.create_market_with_outcomes(2).await;
+    let alice = env.create_user(200).await;
+    env.add_liquidity(&alice, market, 200).await.unwrap();
+
+    let end_time = env.account::<Market>(market).await.end_time;
+    env.warp_to(end_time).await;
+    let result = env.remove_liquidity(&alice, market, 200).await;
+    assert_program_error(result, PredictionMarketError::LiquidityLocked);
+}
+
+#[tokio::test]
+async fn invalid_fee_share() {
+    let mut env = TestEnv::new().await;
+
+    let result = env.set_lp_fee_share(10_001).await;
+    assert_program_error(result, PredictionMarketError::InvalidFeeShare);
+}
+
+#[tokio::test]
+async fn invalid_market_mode() {
+    let mut env = TestEnv::new().await;
+    env.create_house(PARLAY_LIMITS, 1_000).await.unwrap();
+    let market = env.create_market_with_outcomes(2).await;
+    env.set_fixed_odds(market, 0).await.unwrap();
+
+    let result = env.create_market_book(market).await;
+    assert_program_error(result, PredictionMarketError::InvalidMarketMode);
+
+    // Nor are posted odds replaced by seeded ones
+    let sentinels = env.create_team("Sentinels").await.unwrap();
+    let cloud9 = env.create_team("Cloud9").await.unwrap();
+    let market = env.create_market().await;
+    env.set_fixed_odds(market, 0).await.unwrap();
+    env.add_outcome_for(market, 0, Some(sentinels)).await.unwrap();
+    env.add_outcome_for(market, 1, Some(cloud9)).await.unwrap();
+    let result = env.seed_odds(market, sentinels, cloud9).await;
+    assert_program_error(result, PredictionMarketError::InvalidMarketMode);
+}
+
+#[tokio::test]
+async fn invalid_odds() {
+    let mut env = TestEnv::new().await;
+    env.create_house(PARLAY_LIMITS, 1_000).await.unwrap();
+    let market = env.create_market_with_outcomes(2).await;
+    env.set_fixed_odds(market, 0).await.unwrap();
+
+    let result = env.set_outcome_odds(market, 0, 10_000).await;
+    assert_program_error(result, PredictionMarketError::InvalidOdds);
+}
+
+#[tokio::test]
+async fn liability_limit_exceeded() {
+    let mut env = TestEnv::new().await;
+    env.create_house(PARLAY_LIMITS, 1_000).await.unwrap();
+    let market = env.create_market_with_outcomes(2).await;
+    env.set_fixed_odds(market, 100).await.unwrap();
+    env.set_outcome_odds(market, 0, 20_000).await.unwrap();
+    let user = env.create_user(100).await;
+
+    let result = env.place_bet(&user, market, 0, 51).await;
+    assert_program_error(result, PredictionMarketError::LiabilityLimitExceeded);
+}
+
+#[tokio::test]
+async fn invalid_house() {
+    let mut env = TestEnv::new().await;
+    env.create_house(PARLAY_LIMITS, 1_000).await.unwrap();
+    let market = env.create_market_with_outcomes(2).await;
+    env.set_fixed_odds(market, 0).await.unwrap();
+    env.set_outcome_odds(market, 0, 20_000).await.unwrap();
+    let user = env.create_user(100).await;
+
+    let accounts = env.place_bet_accounts(&user, market, 0);
+    let result = env.send(accounts, instruction::PlaceBet { amount: 100 }, &[&user.keypair]).await;
+    assert_program_error(result, PredictionMarketError::InvalidHouse);
+}
+
+#[tokio::test]
+async fn settlement_pending() {
+    let mut env = TestEnv::new().await;
+    let market = env.create_market().await;
+    env.create_market_book(market).await.unwrap();
+    for outcome_id in 0..3 {
+        env.add_outcome(market, outcome_id).await.unwrap();
+    }
+    let user = env.create_user(100).await;
+    env.place_bet(&user, market, 0, 100).await.unwrap();
+    let end_time = env.account::<Market>(market).await.end_time;
+    env.warp_to(end_time).await;
+    env.resolve_market_sweeping(market, 0, 1).await.unwrap();
+
+    let result = env.claim_payout(&user, market, 0).await;
+    assert_program_error(result, PredictionMarketError::SettlementPending);
+}
+
+#[tokio::test]
+async fn fee_charged_at_bet() {
+    let mut env = TestEnv::new().await;
+    let market = env.create_market_with_outcomes(2).await;
+    env.set_fee_model(market, FeeModel::AtBet).await.unwrap();
+    let user = env.create_user(100).await;
+    let referrer = env.create_user(0).await;
+
+    let result = env.place_referred_bet(&user, market, 0, 100, referrer.pubkey()).await;
+    assert_program_error(result, PredictionMarketError::FeeChargedAtBet);
+    let result = env.add_liquidity(&user, market, 100).await;
+    assert_program_error(result, PredictionMarketError::FeeChargedAtBet);
+}
@@ -0,0 +1,83 @@
+//! Fee models: a market charges its platform fee on the whole pool, on the
+//! winners' net winnings only, or on each bet as it is placed.
+
+mod common;
+
+use common::*;
+use prediction_market::errors::PredictionMarketError;
+use prediction_market::state::{FeeModel, Market};
+use solana_sdk::pubkey::Pubkey;
+
+async fn market_with_fee_model(env: &mut TestEnv, fee_model: FeeModel) -> Pubkey {
+    let market = env.create_market_with_outcomes(2).await;
+    env.set_fee_model(market, fee_model).await.unwrap();
+    market
+}
+
+#[tokio::test]
+async fn net_winnings_spares_the_winners_stake() {
+    let mut env = TestEnv::new().await;
+    let market = market_with_fee_model(&mut env, FeeModel::NetWinnings).await;
+    let alice = env.create_user(600).await;
+    let bob = env.create_user(400).await;
+    env.place_bet(&alice, market, 0, 600).await.unwrap();
+    env.place_bet(&bob, market, 1, 400).await.unwrap();
+
+    // 2% of the 400 Alice won, not of the pool of 1000
+    env.end_and_resolve(market, 0).await;
+    assert_eq!(env.token_balance(env.treasury_token_account).await, 8);
+    env.claim_payout(&alice, market, 0).await.unwrap();
+    assert_eq!(env.token_balance(alice.token_account).await, 992);
+}
+
+#[tokio::test]
+async fn at_bet_fee_goes_straight_to_the_treasury() {
+    let mut env = TestEnv::new().await;
+    let market = market_with_fee_model(&mut env, FeeModel::AtBet).await;
+    let alice = env.create_user(1_000).await;
+    let bob = env.create_user(500).await;
+    env.place_bet(&alice, market, 0, 1_000).await.unwrap();
+    env.place_bet(&bob, market, 1, 500).await.unwrap();
+
+    // 2% of each bet is charged up front and only the rest is staked
+    assert_eq!(env.token_balance(env.treasury_token_account).await, 30);
+    let stored: Market = env.account(market).await;
+    assert_eq!(stored.total_pool, 980 + 490);
+
+    // Settlement takes no further fee
+    env.end_and_resolve(market, 0).await;
+    assert_eq!(env.token_balance(env.treasury_token_account).await, 30);
+    env.claim_payout(&alice, market, 0).await.unwrap();
+    assert_eq!(env.token_balance(alice.token_account).await, 1_470);
+}
+
+#[tokio::test]
+async fn odds_are_net_of_the_fee_model() {
+    let odds = |fee_model| {
+        let market = Market {
+            total_pool: 1_000,
+            fee_bps: 200,
+            fee_model,
+            ..Market::default()
+        };
+        market.odds(400).unwrap()
+    };
+
+    // 980 / 400 on the pool, 988 / 400 on net winnings, and 1000 / 400 on
+    // the 98% of each bet that is staked
+    assert_eq!(odds(FeeModel::TotalPool), 24_500);
+    assert_eq!(odds(FeeModel::NetWinnings), 24_700);
+    assert_eq!(odds(FeeModel::AtBet), 24_500);
+    assert_eq!(Market::default().odds(0).unwrap(), 0);
+}
+
+#[tokio::test]
+async fn fee_model_is_fixed_once_betting_starts() {
+    let mut env = TestEnv::new().await;
+    let market = env.create_market_with_outcomes(2).await;
+    let alice = env.create_user(100).await;
+    env.place_bet(&alice, market, 0, 100).await.unwrap();
+
+    let result = env.set_fee_model(market, FeeModel::AtBet).await;
+    assert_program_error(result, PredictionMarketError::MarketHasBets);
+}
@@ -0,0 +1,93 @@
+//! Basis-point fees and volume-based fee tiers: a bet placed once the user's
+//! lifetime volume reaches a tier earns a rebate of that tier's share of the
+//! platform fee if it wins.
+
+mod common;
+
+use common::*;
+use prediction_market::state::{FeeTier, Market, Outcome, UserPosition, UserProfile};
+
+fn tiers() -> [FeeTier; 4] {
+    [
+        FeeTier { min_volume: 1_000, discount_bps: 2_500 },
+        FeeTier { min_volume: 1_500, discount_bps: 5_000 },
+        FeeTier::default(),
+        FeeTier::default(),
+    ]
+}
+
+#[tokio::test]
+async fn custom_fee_in_basis_points() {
+    let mut env = TestEnv::new().await;
+    let market = env.create_market_with_fee(250).await.unwrap();
+    for outcome_id in 0..2 {
+        env.add_outcome(market, outcome_id).await.unwrap();
+    }
+    assert_eq!(env.account::<Market>(market).await.fee_bps, 250);
+
+    let alice = env.create_user(600).await;
+    let bob = env.create_user(400).await;
+    env.place_bet(&alice, market, 0, 600).await.unwrap();
+    env.place_bet(&bob, market, 1, 400).await.unwrap();
+
+    // 2.5% of a pool of 1000
+    env.end_and_resolve(market, 0).await;
+    assert_eq!(env.token_balance(env.treasury_token_account).await, 25);
+    env.claim_payout(&alice, market, 0).await.unwrap();
+    assert_eq!(env.token_balance(alice.token_account).await, 975);
+}
+
+#[tokio::test]
+async fn discount_follows_lifetime_volume() {
+    let mut env = TestEnv::new().await;
+    env.set_fee_tiers(tiers()).await.unwrap();
+    let first = env.create_market_with_outcomes(2).await;
+    let second = env.create_market_with_outcomes(2).await;
+    let alice = env.create_user(2_000).await;
+
+    // No volume before the first bet, 1000 before the second and 1600
+    // before the third
+    env.place_bet(&alice, first, 0, 1_000).await.unwrap();
+    env.place_bet(&alice, second, 0, 600).await.unwrap();
+    env.place_bet(&alice, second, 0, 400).await.unwrap();
+
+    let profile: UserProfile = env.account(user_profile_address(alice.pubkey())).await;
+    assert_eq!(profile.total_volume, 2_000);
+    let position: UserPosition = env.account(user_position_address(alice.pubkey(), first, 0)).await;
+    assert_eq!(position.discounted_amount, 0);
+    let position: UserPosition = env.account(user_position_address(alice.pubkey(), second, 0)).await;
+    assert_eq!(position.discounted_amount, 150 + 200);
+    let outcome: Outcome = env.account(outcome_address(second, 0)).await;
+    assert_eq!(outcome.discounted_stake, 350);
+}
+
+#[tokio::test]
+async fn winners_receive_their_rebate() {
+    let mut env = TestEnv::new().await;
+    env.set_fee_tiers(tiers()).await.unwrap();
+    let warm_up = env.create_market_with_outcomes(2).await;
+    let market = env.create_market_with_outcomes(2).await;
+
+    let alice = env.create_user(2_100).await;
+    let bob = env.create_user(500).await;
+    let carol = env.create_user(1_000).await;
+    env.place_bet(&alice, warm_up, 0, 1_600).await.unwrap();
+
+    // Alice bets at a 50% discount, Bob at none
+    env.place_bet(&alice, market, 0, 500).await.unwrap();
+    env.place_bet(&bob, market, 0, 500).await.unwrap();
+    env.place_bet(&carol, market, 1, 1_000).await.unwrap();
+
+    // The fee of 40 on the pool of 2000 is split 30 to the treasury and a
+    // rebate of 10 for Alice's discounted half of the winning stake
+    env.end_and_resolve(market, 0).await;
+    let stored: Market = env.account(market).await;
+    assert_eq!(stored.fee_rebates, 10);
+    assert_eq!(env.token_balance(env.treasury_token_account).await, 30);
+
+    env.claim_payout(&alice, market, 0).await.unwrap();
+    assert_eq!(env.token_balance(alice.token_account).await, 980 + 10);
+    env.claim_payout(&bob, market, 0).await.unwrap();
+    assert_eq!(env.token_balance(bob.token_account).await, 980);
+    assert_eq!(env.token_balance(env.escrow_token_account(market, 0)).await, 0);
+}
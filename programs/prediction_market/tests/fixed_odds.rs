@@ -0,0 +1,114 @@
+//! Fixed-odds markets: bets taken by the house at each outcome's posted
+//! odds, with their payouts locked in and reserved out of the house vault.
+
+mod common;
+
+use common::*;
+use prediction_market::errors::PredictionMarketError;
+use prediction_market::state::{House, Market, Outcome, UserPosition};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signer;
+
+/// A two-outcome fixed-odds market against a house holding `liquidity`.
+async fn fixed_odds_market(env: &mut TestEnv, liquidity: u64, max_liability: u64) -> Pubkey {
+    env.create_house(PARLAY_LIMITS, liquidity).await.unwrap();
+    let market = env.create_market_with_outcomes(2).await;
+    env.set_fixed_odds(market, max_liability).await.unwrap();
+    market
+}
+
+async fn house_liability(env: &mut TestEnv) -> u64 {
+    env.account::<House>(house_address(&env.mint)).await.liability
+}
+
+#[tokio::test]
+async fn bets_lock_in_their_odds() {
+    let mut env = TestEnv::new().await;
+    let market = fixed_odds_market(&mut env, 1_000, 0).await;
+    env.set_outcome_odds(market, 0, 25_000).await.unwrap();
+    env.set_outcome_odds(market, 1, 16_000).await.unwrap();
+
+    let alice = env.create_user(100).await;
+    let bob = env.create_user(100).await;
+    let carol = env.create_user(200).await;
+    env.place_bet(&alice, market, 0, 100).await.unwrap();
+    env.set_outcome_odds(market, 0, 20_000).await.unwrap();
+    env.place_bet(&bob, market, 0, 100).await.unwrap();
+    env.place_bet(&carol, market, 1, 200).await.unwrap();
+
+    let position: UserPosition = env.account(user_position_address(alice.pubkey(), market, 0)).await;
+    assert_eq!(position.payout, 250);
+    assert_eq!(env.account::<Outcome>(outcome_address(market, 0)).await.liability, 450);
+    assert_eq!(env.account::<Outcome>(outcome_address(market, 1)).await.liability, 320);
+
+    // The house only reserves for the outcome it would owe most on
+    assert_eq!(house_liability(&mut env).await, 450);
+    assert_eq!(env.token_balance(env.house_vault()).await, 1_400);
+    assert_eq!(env.token_balance(env.escrow_token_account(market, 0)).await, 0);
+
+    // Resolving leaves no fee and reserves only the winners' payouts
+    env.end_and_resolve(market, 0).await;
+    assert_eq!(env.token_balance(env.treasury_token_account).await, 0);
+    assert_eq!(house_liability(&mut env).await, 450);
+
+    env.claim_fixed_odds_payout(&alice, market, 0).await.unwrap();
+    env.claim_fixed_odds_payout(&bob, market, 0).await.unwrap();
+    assert_eq!(env.token_balance(alice.token_account).await, 250);
+    assert_eq!(env.token_balance(bob.token_account).await, 200);
+    assert_eq!(house_liability(&mut env).await, 0);
+    assert_eq!(env.token_balance(env.house_vault()).await, 950);
+
+    let result = env.claim_fixed_odds_payout(&carol, market, 1).await;
+    assert_program_error(result, PredictionMarketError::NotWinner);
+    let result = env.claim_payout(&carol, market, 1).await;
+    assert_program_error(result, PredictionMarketError::InvalidMarketMode);
+}
+
+#[tokio::test]
+async fn bets_stay_within_the_houses_liability() {
+    let mut env = TestEnv::new().await;
+    let market = fixed_odds_market(&mut env, 1_000, 300).await;
+    env.set_outcome_odds(market, 0, 30_000).await.unwrap();
+
+    let alice = env.create_user(200).await;
+    env.place_bet(&alice, market, 0, 100).await.unwrap();
+    let result = env.place_bet(&alice, market, 0, 1).await;
+    assert_program_error(result, PredictionMarketError::LiabilityLimitExceeded);
+
+    // Without a limit the bet must still be covered by the vault
+    let market = env.create_market_with_outcomes(2).await;
+    env.set_fixed_odds(market, 0).await.unwrap();
+    env.set_outcome_odds(market, 1, 120_000).await.unwrap();
+    let bob = env.create_user(100).await;
+    let result = env.place_bet(&bob, market, 1, 100).await;
+    assert_program_error(result, PredictionMarketError::InsufficientLiquidity);
+
+    // Nothing else can be withdrawn while bets are open
+    let admin_token_account = env.create_token_account(&env.admin.pubkey()).await;
+    let result = env.withdraw_house_liquidity(admin_token_account, 801).await;
+    assert_program_error(result, PredictionMarketError::InsufficientLiquidity);
+}
+
+#[tokio::test]
+async fn cancelled_bets_are_refunded_by_the_house() {
+    let mut env = TestEnv::new().await;
+    let market = fixed_odds_market(&mut env, 1_000, 0).await;
+    env.set_outcome_odds(market, 0, 40_000).await.unwrap();
+    env.set_outcome_odds(market, 1, 13_000).await.unwrap();
+
+    let alice = env.create_user(100).await;
+    let bob = env.create_user(300).await;
+    env.place_bet(&alice, market, 0, 100).await.unwrap();
+    env.place_bet(&bob, market, 1, 300).await.unwrap();
+    assert_eq!(house_liability(&mut env).await, 400);
+
+    env.cancel_market(market).await.unwrap();
+    assert_eq!(env.account::<Market>(market).await.house_reserve, 400);
+
+    env.claim_fixed_odds_payout(&alice, market, 0).await.unwrap();
+    env.claim_fixed_odds_payout(&bob, market, 1).await.unwrap();
+    assert_eq!(env.token_balance(alice.token_account).await, 100);
+    assert_eq!(env.token_balance(bob.token_account).await, 300);
+    assert_eq!(house_liability(&mut env).await, 0);
+    assert_eq!(env.token_balance(env.house_vault()).await, 1_000);
+}
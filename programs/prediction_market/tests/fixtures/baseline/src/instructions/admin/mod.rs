@@ -0,0 +1,13 @@
+pub mod initialize_platform;
+pub mod create_market;
+pub mod add_outcome;
+pub mod resolve_market;
+pub mod withdraw_fees;
+pub mod cancel_market;
+
+pub use initialize_platform::*;
+pub use create_market::*;
+pub use add_outcome::*;
+pub use resolve_market::*;
+pub use withdraw_fees::*;
+pub use cancel_market::*;
\ No newline at end of file
@@ -0,0 +1,5 @@
+pub mod admin;
+pub mod users;
+
+pub use admin::*;
+pub use users::*;
\ No newline at end of file
@@ -0,0 +1,5 @@
+pub mod place_bet;
+pub mod claim_payout;
+
+pub use place_bet::*;
+pub use claim_payout::*;
\ No newline at end of file
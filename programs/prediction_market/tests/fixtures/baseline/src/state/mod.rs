@@ -0,0 +1,9 @@
+pub mod platform_config;
+pub mod market;
+pub mod outcome;
+pub mod user_position;
+
+pub use platform_config::*;
+pub use market::*;
+pub use outcome::*;
+pub use user_position::*;
\ No newline at end of file
@@ -0,0 +1,213 @@
+//! Happy-path coverage for every instruction.
+
+mod common;
+
+use common::*;
+use prediction_market::state::{CollateralConfig, Market, MarketStatus, Outcome, UserPosition};
+use solana_sdk::signature::Signer;
+
+#[tokio::test]
+async fn initialize_platform() {
+    let mut env = TestEnv::new().await;
+    let config = env.platform_config().await;
+
+    assert_eq!(config.admin, env.admin.pubkey());
+    assert_eq!(config.oracle_authority, env.admin.pubkey());
+    assert_eq!(config.default_fee_bps, 200);
+    assert_eq!(config.markets_count, 0);
+    assert_eq!(config.total_volume, 0);
+    assert!(!config.paused);
+}
+
+#[tokio::test]
+async fn add_collateral_mint() {
+    let mut env = TestEnv::new().await;
+    let mint = env.create_other_mint().await;
+    env.add_collateral_mint(mint).await.unwrap();
+
+    let config: CollateralConfig = env.account(collateral_config(&mint)).await;
+    assert_eq!(config.mint, mint);
+    assert_eq!(config.treasury, treasury_token_address(&mint));
+    assert!(config.enabled);
+    assert_eq!(env.token_balance(config.treasury).await, 0);
+}
+
+#[tokio::test]
+async fn set_collateral_enabled() {
+    let mut env = TestEnv::new().await;
+    let mint = env.mint;
+    env.set_collateral_enabled(mint, false).await.unwrap();
+    assert!(!env.account::<CollateralConfig>(collateral_config(&mint)).await.enabled);
+
+    env.set_collateral_enabled(mint, true).await.unwrap();
+    assert!(env.account::<CollateralConfig>(collateral_config(&mint)).await.enabled);
+}
+
+#[tokio::test]
+async fn create_market() {
+    let mut env = TestEnv::new().await;
+    let now = env.now().await;
+    let address = env.create_market().await;
+    assert_eq!(address, market_address(0));
+
+    let market: Market = env.account(address).await;
+    assert_eq!(market.id, 0);
+    assert_eq!(market.creator, env.admin.pubkey());
+    assert_eq!(market.oracle, env.admin.pubkey());
+    assert_eq!(market.collateral_mint, env.mint);
+    assert_eq!(market.start_time, now + MARKET_START_OFFSET);
+    assert_eq!(market.end_time, now + MARKET_END_OFFSET);
+    assert_eq!(market.fee_bps, 200);
+    assert_eq!(market.status, MarketStatus::Active);
+    assert!(market.outcomes.is_empty());
+    assert_eq!(env.platform_config().await.markets_count, 1);
+
+    // Markets are numbered by the platform's running count
+    assert_eq!(env.create_market().await, market_address(1));
+}
+
+#[tokio::test]
+async fn add_outcome() {
+    let mut env = TestEnv::new().await;
+    let market_address = env.create_market_with_outcomes(2).await;
+
+    let market: Market = env.account(market_address).await;
+    assert_eq!(market.outcomes, vec![0, 1]);
+
+    let outcome: Outcome = env.account(outcome_address(market_address, 1)).await;
+    assert_eq!(outcome.id, 1);
+    assert_eq!(outcome.market, market_address);
+    assert_eq!(outcome.name, "Team 1 wins");
+    assert_eq!(outcome.escrow_pubkey, escrow_token_address(market_address, 1, &env.mint));
+    assert_eq!(outcome.total_staked, 0);
+    assert_eq!(outcome.odds, 10_000);
+    assert_eq!(env.token_balance(outcome.escrow_pubkey).await, 0);
+}
+
+#[tokio::test]
+async fn place_bet() {
+    let mut env = TestEnv::new().await;
+    let market_address = env.create_market_with_outcomes(2).await;
+    let user = env.create_user(1_000).await;
+
+    env.place_bet(&user, market_address, 1, 300).await.unwrap();
+    env.place_bet(&user, market_address, 1, 200).await.unwrap();
+
+    let position: UserPosition = env
+        .account(user_position_address(user.pubkey(), market_address, 1))
+        .await;
+    assert_eq!(position.user, user.pubkey());
+    assert_eq!(position.market, market_address);
+    assert_eq!(position.outcome, 1);
+    assert_eq!(position.amount, 500);
+    assert!(!position.claimed);
+
+    let outcome: Outcome = env.account(outcome_address(market_address, 1)).await;
+    assert_eq!(outcome.total_staked, 500);
+    // Topping up a position doesn't add a claimant
+    assert_eq!(outcome.claimants_remaining, 1);
+    let market: Market = env.account(market_address).await;
+    assert_eq!(market.total_pool, 500);
+
+    assert_eq!(env.token_balance(user.token_account).await, 500);
+    assert_eq!(env.token_balance(outcome.escrow_pubkey).await, 500);
+}
+
+#[tokio::test]
+async fn resolve_market() {
+    let mut env = TestEnv::new().await;
+    let market_address = env.create_market_with_outcomes(2).await;
+    let alice = env.create_user(1_000).await;
+    let bob = env.create_user(1_000).await;
+    env.place_bet(&alice, market_address, 0, 300).await.unwrap();
+    env.place_bet(&bob, market_address, 1, 100).await.unwrap();
+
+    env.end_and_resolve(market_address, 0).await;
+
+    let market: Market = env.account(market_address).await;
+    assert_eq!(market.status, MarketStatus::Resolved);
+    assert!(market.resolved);
+    assert_eq!(market.winner, Some(0));
+
+    // The losing escrow is swept into the winning one and the 2% fee goes to the treasury
+    let winning_escrow = escrow_token_address(market_address, 0, &env.mint);
+    let losing_escrow = escrow_token_address(market_address, 1, &env.mint);
+    let treasury = env.treasury_token_account;
+    assert_eq!(env.token_balance(losing_escrow).await, 0);
+    assert_eq!(env.token_balance(winning_escrow).await, 392);
+    assert_eq!(env.token_balance(treasury).await, 8);
+}
+
+#[tokio::test]
+async fn claim_payout() {
+    let mut env = TestEnv::new().await;
+    let market_address = env.create_market_with_outcomes(2).await;
+    let alice = env.create_user(1_000).await;
+    let bob = env.create_user(1_000).await;
+    let carol = env.create_user(1_000).await;
+
+    env.place_bet(&alice, market_address, 0, 300).await.unwrap();
+    env.place_bet(&bob, market_address, 0, 100).await.unwrap();
+    env.place_bet(&carol, market_address, 1, 100).await.unwrap();
+    env.end_and_resolve(market_address, 0).await;
+
+    // Pool of 500 less the 2% fee leaves 490 to split 3:1, and bob as the
+    // last claimer also takes the unit lost to rounding
+    env.claim_payout(&alice, market_address, 0).await.unwrap();
+    env.claim_payout(&bob, market_address, 0).await.unwrap();
+
+    assert_eq!(env.token_balance(alice.token_account).await, 700 + 367);
+    assert_eq!(env.token_balance(bob.token_account).await, 900 + 123);
+    let winning_escrow = escrow_token_address(market_address, 0, &env.mint);
+    assert_eq!(env.token_balance(winning_escrow).await, 0);
+
+    let outcome: Outcome = env.account(outcome_address(market_address, 0)).await;
+    assert_eq!(outcome.total_claimed, 490);
+    assert_eq!(outcome.claimants_remaining, 0);
+
+    let position: UserPosition = env
+        .account(user_position_address(alice.pubkey(), market_address, 0))
+        .await;
+    assert!(position.claimed);
+}
+
+#[tokio::test]
+async fn cancel_market() {
+    let mut env = TestEnv::new().await;
+    let market_address = env.create_market_with_outcomes(2).await;
+    env.cancel_market(market_address).await.unwrap();
+
+    let market: Market = env.account(market_address).await;
+    assert_eq!(market.status, MarketStatus::Cancelled);
+}
+
+#[tokio::test]
+async fn claim_refund() {
+    let mut env = TestEnv::new().await;
+    let market_address = env.create_market_with_outcomes(2).await;
+    let user = env.create_user(1_000).await;
+    env.place_bet(&user, market_address, 1, 400).await.unwrap();
+    env.cancel_market(market_address).await.unwrap();
+
+    env.claim_refund(&user, market_address, 1).await.unwrap();
+
+    assert_eq!(env.token_balance(user.token_account).await, 1_000);
+    let position: UserPosition = env
+        .account(user_position_address(user.pubkey(), market_address, 1))
+        .await;
+    assert!(position.claimed);
+}
+
+#[tokio::test]
+async fn withdraw_fees() {
+    let mut env = TestEnv::new().await;
+    let admin = env.admin.pubkey();
+    let admin_token_account = env.create_token_account(&admin).await;
+    let treasury = env.treasury_token_account;
+    env.mint_to(treasury, 500).await;
+
+    env.withdraw_fees(admin_token_account).await.unwrap();
+
+    assert_eq!(env.token_balance(treasury).await, 0);
+    assert_eq!(env.token_balance(admin_token_account).await, 500);
+}
@@ -0,0 +1,233 @@
+//! Liquidity provision: market makers stake across a market's outcomes for
+//! LP tokens, which redeem an exact share of the pool and of the LPs' part
+//! of the fee.
+
+mod common;
+
+use common::*;
+use prediction_market::errors::PredictionMarketError;
+use prediction_market::state::{AccessGate, BetLimits, LiquidityPool, Market, Outcome, UserProfile};
+use solana_program_test::BanksClientError;
+use solana_sdk::pubkey::Pubkey;
+
+/// Adds liquidity to an allowlist-gated market, passing the provider's entry.
+async fn add_allowlisted_liquidity(
+    env: &mut TestEnv,
+    provider: &User,
+    market: Pubkey,
+    amount: u64,
+) -> Result<(), BanksClientError> {
+    let mut accounts = env.add_liquidity_accounts(provider, market).await;
+    accounts.allowlist_entry = Some(allowlist_entry_address(provider.pubkey()));
+    env.add_liquidity_with(provider, accounts, amount).await
+}
+
+#[tokio::test]
+async fn first_deposit_follows_the_market() {
+    let mut env = TestEnv::new().await;
+    let empty = env.create_market_with_outcomes(3).await;
+    let alice = env.create_user(1_300).await;
+
+    // With nothing staked yet the deposit is spread evenly
+    env.add_liquidity(&alice, empty, 300).await.unwrap();
+    let pool: LiquidityPool = env.account(liquidity_pool_address(empty)).await;
+    assert_eq!(pool.stakes, vec![100, 100, 100]);
+    assert_eq!(pool.lp_supply, 300);
+    assert_eq!(env.token_balance(env.lp_token_account(&alice, empty)).await, 300);
+    assert!(env.account::<Market>(empty).await.has_liquidity);
+
+    // Otherwise it follows the market's stakes
+    let market = env.create_market_with_outcomes(2).await;
+    let bettor = env.create_user(400).await;
+    env.place_bet(&bettor, market, 0, 100).await.unwrap();
+    env.place_bet(&bettor, market, 1, 300).await.unwrap();
+    env.add_liquidity(&alice, market, 1_000).await.unwrap();
+    let pool: LiquidityPool = env.account(liquidity_pool_address(market)).await;
+    assert_eq!(pool.stakes, vec![250, 750]);
+    assert_eq!(env.account::<Market>(market).await.total_pool, 1_400);
+
+    let outcome: Outcome = env.account(outcome_address(market, 1)).await;
+    assert_eq!((outcome.total_staked, outcome.claimants_remaining), (1_050, 2));
+}
+
+#[tokio::test]
+async fn outcomes_cannot_be_added_under_a_pool() {
+    let mut env = TestEnv::new().await;
+    let market = env.create_market_with_outcomes(2).await;
+    let alice = env.create_user(200).await;
+    env.add_liquidity(&alice, market, 200).await.unwrap();
+    let lp_tokens = env.token_balance(env.lp_token_account(&alice, market)).await;
+    env.remove_liquidity(&alice, market, lp_tokens).await.unwrap();
+
+    // The pool holds no stake on an outcome added after it, even once
+    // its providers have left
+    let result = env.add_outcome(market, 2).await;
+    assert_program_error(result, PredictionMarketError::MarketHasBets);
+}
+
+#[tokio::test]
+async fn deposits_are_checked_like_bets() {
+    let mut env = TestEnv::new().await;
+    let market = env.create_market_with_outcomes(2).await;
+    env.set_access_gate(market, AccessGate::Allowlist).await.unwrap();
+    env.set_bet_limits(market, BetLimits { max_pool: 1_000, ..BetLimits::default() }).await.unwrap();
+    let alice = env.create_user(2_000).await;
+
+    // Gated markets take liquidity only from those who may bet
+    let result = env.add_liquidity(&alice, market, 400).await;
+    assert_program_error(result, PredictionMarketError::NotAllowlisted);
+    env.add_to_allowlist(alice.pubkey()).await.unwrap();
+    add_allowlisted_liquidity(&mut env, &alice, market, 400).await.unwrap();
+
+    // Deposits count against the provider's limits, but not their volume
+    env.set_deposit_limits(&alice, 500, 500).await.unwrap();
+    let result = add_allowlisted_liquidity(&mut env, &alice, market, 200).await;
+    assert_program_error(result, PredictionMarketError::DailyDepositLimitExceeded);
+    let profile: UserProfile = env.account(user_profile_address(alice.pubkey())).await;
+    assert_eq!((profile.deposited_today, profile.total_volume), (400, 0));
+
+    // And fill the pool like bets
+    let bob = env.create_user(2_000).await;
+    env.add_to_allowlist(bob.pubkey()).await.unwrap();
+    let result = add_allowlisted_liquidity(&mut env, &bob, market, 700).await;
+    assert_program_error(result, PredictionMarketError::PoolLimitExceeded);
+    add_allowlisted_liquidity(&mut env, &bob, market, 600).await.unwrap();
+    assert_eq!(env.account::<Market>(market).await.total_pool, 1_000);
+}
+
+#[tokio::test]
+async fn partial_withdrawals_are_exact() {
+    let mut env = TestEnv::new().await;
+    let market = env.create_market_with_outcomes(2).await;
+    let bettor = env.create_user(400).await;
+    env.place_bet(&bettor, market, 0, 100).await.unwrap();
+    env.place_bet(&bettor, market, 1, 300).await.unwrap();
+
+    let alice = env.create_user(1_000).await;
+    let bob = env.create_user(400).await;
+    env.add_liquidity(&alice, market, 1_000).await.unwrap();
+    env.add_liquidity(&bob, market, 400).await.unwrap();
+    let pool: LiquidityPool = env.account(liquidity_pool_address(market)).await;
+    assert_eq!(pool.stakes, vec![350, 1_050]);
+    assert_eq!(pool.lp_supply, 1_400);
+
+    env.remove_liquidity(&alice, market, 300).await.unwrap();
+    assert_eq!(env.token_balance(alice.token_account).await, 300);
+    env.remove_liquidity(&alice, market, 700).await.unwrap();
+    assert_eq!(env.token_balance(alice.token_account).await, 1_000);
+
+    // The last LP out takes exactly what is left
+    env.remove_liquidity(&bob, market, 400).await.unwrap();
+    assert_eq!(env.token_balance(bob.token_account).await, 400);
+    let pool: LiquidityPool = env.account(liquidity_pool_address(market)).await;
+    assert_eq!((pool.stakes, pool.lp_supply), (vec![0, 0], 0));
+
+    // The market is back to the bettor's stakes alone
+    assert_eq!(env.account::<Market>(market).await.total_pool, 400);
+    let outcome: Outcome = env.account(outcome_address(market, 1)).await;
+    assert_eq!((outcome.total_staked, outcome.claimants_remaining), (300, 1));
+    assert_eq!(env.token_balance(env.escrow_token_account(market, 1)).await, 300);
+}
+
+#[tokio::test]
+async fn lps_redeem_winnings_and_fee_share() {
+    let mut env = TestEnv::new().await;
+    env.set_lp_fee_share(5_000).await.unwrap();
+    let market = env.create_market_with_outcomes(2).await;
+    let bettor = env.create_user(400).await;
+    env.place_bet(&bettor, market, 0, 100).await.unwrap();
+    env.place_bet(&bettor, market, 1, 300).await.unwrap();
+
+    let alice = env.create_user(400).await;
+    env.add_liquidity(&alice, market, 400).await.unwrap();
+
+    // Liquidity is locked between the close and the result
+    let end_time = env.account::<Market>(market).await.end_time;
+    env.warp_to(end_time).await;
+    let result = env.remove_liquidity(&alice, market, 400).await;
+    assert_program_error(result, PredictionMarketError::LiquidityLocked);
+
+    // Fee is 16; LPs staked half the pool, so they get half of half of it
+    env.resolve_market(market, 0).await.unwrap();
+    assert_eq!(env.account::<LiquidityPool>(liquidity_pool_address(market)).await.fees, 4);
+    assert_eq!(env.token_balance(env.treasury_token_account).await, 12);
+
+    // LPs hold half the winning stake of 200, so half of the 784 left
+    env.remove_liquidity(&alice, market, 100).await.unwrap();
+    assert_eq!(env.token_balance(alice.token_account).await, 99);
+    env.remove_liquidity(&alice, market, 300).await.unwrap();
+    assert_eq!(env.token_balance(alice.token_account).await, 396);
+
+    // Bettors are paid as if the pool were any other position
+    env.claim_payout(&bettor, market, 0).await.unwrap();
+    assert_eq!(env.token_balance(bettor.token_account).await, 392);
+    assert_eq!(env.token_balance(env.escrow_token_account(market, 0)).await, 0);
+}
+
+#[tokio::test]
+async fn cancelled_markets_refund_liquidity() {
+    let mut env = TestEnv::new().await;
+    let market = env.create_market_with_outcomes(2).await;
+    let bettor = env.create_user(200).await;
+    env.place_bet(&bettor, market, 0, 100).await.unwrap();
+    env.place_bet(&bettor, market, 1, 100).await.unwrap();
+
+    let alice = env.create_user(200).await;
+    env.add_liquidity(&alice, market, 200).await.unwrap();
+    env.cancel_market(market).await.unwrap();
+
+    env.remove_liquidity(&alice, market, 150).await.unwrap();
+    env.remove_liquidity(&alice, market, 50).await.unwrap();
+    assert_eq!(env.token_balance(alice.token_account).await, 200);
+
+    env.claim_refund(&bettor, market, 0).await.unwrap();
+    env.claim_refund(&bettor, market, 1).await.unwrap();
+    assert_eq!(env.token_balance(env.escrow_token_account(market, 0)).await, 0);
+    assert_eq!(env.token_balance(env.escrow_token_account(market, 1)).await, 0);
+
+    let result = env.remove_liquidity(&alice, market, 1).await;
+    assert_program_error(result, PredictionMarketError::InvalidLiquidityAmount);
+}
+#[tokio::test]
+async fn deposits_after_an_elimination_keep_redemptions_whole() {
+    let mut env = TestEnv::new().await;
+    let teams = env.create_teams(3).await;
+    let outright = env.create_market_with_teams(&teams).await;
+    let tournament = env.create_tournament(outright).await.unwrap();
+    let bettor = env.create_user(400).await;
+    env.place_bet(&bettor, outright, 0, 100).await.unwrap();
+    env.place_bet(&bettor, outright, 1, 200).await.unwrap();
+    env.place_bet(&bettor, outright, 2, 100).await.unwrap();
+
+    let alice = env.create_user(400).await;
+    let bob = env.create_user(400).await;
+    env.add_liquidity(&alice, outright, 400).await.unwrap();
+    env.eliminate_outcome(tournament, 2).await.unwrap();
+
+    // Bob buys the same mix of stakes as Alice holds, the eliminated
+    // outcome's included, at the price she paid
+    env.add_liquidity(&bob, outright, 200).await.unwrap();
+    let pool: LiquidityPool = env.account(liquidity_pool_address(outright)).await;
+    assert_eq!(pool.stakes, vec![150, 300, 150]);
+    assert_eq!(env.token_balance(env.lp_token_account(&bob, outright)).await, 200);
+
+    // Withdrawing straight away returns exactly the deposit
+    env.remove_liquidity(&bob, outright, 100).await.unwrap();
+    assert_eq!(env.token_balance(bob.token_account).await, 300);
+
+    let grand_final = env.create_market_with_teams(&teams[..2]).await;
+    env.add_tournament_match(tournament, grand_final, true).await.unwrap();
+    env.place_bet(&bettor, grand_final, 0, 0).await.ok();
+    let finalist = env.create_user(100).await;
+    env.place_bet(&finalist, grand_final, 1, 100).await.unwrap();
+    env.end_and_resolve(grand_final, 1).await;
+    env.resolve_market(outright, 1).await.unwrap();
+
+    // The pool's 250 of the 450 on the winner claims 250 * 882 / 450 = 490,
+    // split 400:100 between Alice and Bob. Without Bob, Alice's 200 of 400
+    // would have claimed 200 * 784 / 400, the same 392.
+    env.remove_liquidity(&alice, outright, 400).await.unwrap();
+    assert_eq!(env.token_balance(alice.token_account).await, 392);
+    env.remove_liquidity(&bob, outright, 100).await.unwrap();
+    assert_eq!(env.token_balance(bob.token_account).await, 398);
+}
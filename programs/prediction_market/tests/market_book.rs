@@ -0,0 +1,290 @@
+//! Market books: one zero-copy account holding every outcome's stake and
+//! price, kept current by each bet instead of a pass over `Outcome` accounts.
+
+mod common;
+
+use common::*;
+use prediction_market::errors::PredictionMarketError;
+use prediction_market::instruction;
+use prediction_market::state::{Market, MarketBook, Outcome, BPS_DENOMINATOR, MAX_BOOK_OUTCOMES, MAX_OUTCOMES};
+use solana_sdk::pubkey::Pubkey;
+
+async fn market_book(env: &mut TestEnv, market: Pubkey) -> MarketBook {
+    let data = env.account_data(market_book_address(market)).await;
+    bytemuck::pod_read_unaligned(&data[8..MarketBook::SIZE])
+}
+
+#[tokio::test]
+async fn bets_reprice_every_outcome() {
+    let mut env = TestEnv::new().await;
+    let market = env.create_market_with_outcomes(3).await;
+    env.create_market_book(market).await.unwrap();
+    assert!(env.account::<Market>(market).await.has_book);
+
+    let alice = env.create_user(600).await;
+    let bob = env.create_user(400).await;
+    env.place_bet(&alice, market, 0, 600).await.unwrap();
+    env.place_bet(&bob, market, 1, 400).await.unwrap();
+
+    // 2% fee on a pool of 1000 leaves 980 to share
+    let book = market_book(&mut env, market).await;
+    assert_eq!(book.version, MarketBook::VERSION);
+    assert_eq!(book.market, market);
+    assert_eq!(&book.outcome_ids[..book.outcome_count as usize], &[0, 1, 2]);
+    assert_eq!(&book.stakes[..3], &[600, 400, 0]);
+    assert_eq!(&book.prices[..3], &[16_333, 24_500, 0]);
+}
+
+#[tokio::test]
+async fn book_settles_and_cancels() {
+    let mut env = TestEnv::new().await;
+    let market = env.create_market_with_outcomes(2).await;
+    env.create_market_book(market).await.unwrap();
+    let alice = env.create_user(600).await;
+    let bob = env.create_user(400).await;
+    env.place_bet(&alice, market, 0, 600).await.unwrap();
+    env.place_bet(&bob, market, 1, 400).await.unwrap();
+
+    env.end_and_resolve(market, 1).await;
+    let book = market_book(&mut env, market).await;
+    assert_eq!(&book.prices[..2], &[0, 24_500]);
+
+    let cancelled = env.create_market_with_outcomes(3).await;
+    env.create_market_book(cancelled).await.unwrap();
+    let carol = env.create_user(100).await;
+    env.place_bet(&carol, cancelled, 2, 100).await.unwrap();
+    env.cancel_market(cancelled).await.unwrap();
+    let book = market_book(&mut env, cancelled).await;
+    assert_eq!(&book.prices[..3], &[0, 0, BPS_DENOMINATOR]);
+}
+
+#[tokio::test]
+async fn outcomes_added_later_join_the_book() {
+    let mut env = TestEnv::new().await;
+    let market = env.create_market().await;
+    env.create_market_book(market).await.unwrap();
+    for outcome_id in [4, 2] {
+        env.add_outcome(market, outcome_id).await.unwrap();
+    }
+
+    let alice = env.create_user(100).await;
+    env.place_bet(&alice, market, 2, 100).await.unwrap();
+    let book = market_book(&mut env, market).await;
+    assert_eq!(&book.outcome_ids[..book.outcome_count as usize], &[4, 2]);
+    assert_eq!(&book.stakes[..2], &[0, 100]);
+}
+
+#[tokio::test]
+async fn book_must_be_created_before_betting() {
+    let mut env = TestEnv::new().await;
+    let market = env.create_market_with_outcomes(2).await;
+    let alice = env.create_user(100).await;
+    env.place_bet(&alice, market, 0, 100).await.unwrap();
+
+    let result = env.create_market_book(market).await;
+    assert_program_error(result, PredictionMarketError::MarketHasBets);
+}
+
+#[tokio::test]
+async fn refresh_odds_writes_each_outcome() {
+    let mut env = TestEnv::new().await;
+    let market = env.create_market_with_outcomes(2).await;
+    let alice = env.create_user(600).await;
+    let bob = env.create_user(400).await;
+    env.place_bet(&alice, market, 0, 600).await.unwrap();
+    env.place_bet(&bob, market, 1, 400).await.unwrap();
+
+    let ix = env.refresh_odds_instruction(market, &[0, 1]);
+    env.process(&[ix], &[]).await.unwrap();
+    let outcome: Outcome = env.account(outcome_address(market, 0)).await;
+    assert_eq!(outcome.odds, 16_333);
+    let outcome: Outcome = env.account(outcome_address(market, 1)).await;
+    assert_eq!(outcome.odds, 24_500);
+
+    // Every outcome has to be passed, in order
+    let ix = env.refresh_odds_instruction(market, &[1, 0]);
+    let result = env.process(&[ix], &[]).await;
+    assert_program_error(result, PredictionMarketError::OutcomeNotFound);
+}
+
+/// A market with a book and `outcomes` outcomes, added after the book.
+async fn book_market(env: &mut TestEnv, outcomes: u8) -> Pubkey {
+    let market = env.create_market().await;
+    env.create_market_book(market).await.unwrap();
+    for outcome_id in 0..outcomes {
+        env.add_outcome(market, outcome_id).await.unwrap();
+    }
+    market
+}
+
+#[tokio::test]
+async fn large_markets_settle_in_batches() {
+    let mut env = TestEnv::new().await;
+    let market = book_market(&mut env, 40).await;
+    let alice = env.create_user(300).await;
+    let bob = env.create_user(100).await;
+    let carol = env.create_user(600).await;
+    env.place_bet(&alice, market, 0, 300).await.unwrap();
+    env.place_bet(&bob, market, 1, 100).await.unwrap();
+    env.place_bet(&carol, market, 39, 600).await.unwrap();
+
+    // Resolving sweeps the first ten losing escrows and leaves the rest
+    let end_time = env.account::<Market>(market).await.end_time;
+    env.warp_to(end_time).await;
+    env.resolve_market_sweeping(market, 0, 10).await.unwrap();
+    assert_eq!(env.account::<Market>(market).await.unswept_escrows, 29);
+    assert_eq!(env.token_balance(env.escrow_token_account(market, 0)).await, 400);
+    assert_eq!(env.token_balance(env.treasury_token_account).await, 0);
+    let result = env.claim_payout(&alice, market, 0).await;
+    assert_program_error(result, PredictionMarketError::SettlementPending);
+
+    // Escrows are swept in order from where the last batch stopped
+    let mut ix = env.sweep_escrows_instruction(market, 2).await;
+    let metas = ix.accounts.len();
+    ix.accounts.swap(metas - 4, metas - 2);
+    ix.accounts.swap(metas - 3, metas - 1);
+    let admin = env.admin.insecure_clone();
+    let result = env.process(&[ix], &[&admin]).await;
+    assert_program_error(result, PredictionMarketError::InvalidSettlementAccounts);
+
+    for batch in [10, 10, 9] {
+        env.sweep_escrows(market, batch).await.unwrap();
+    }
+    assert_eq!(env.account::<Market>(market).await.unswept_escrows, 0);
+    assert_eq!(env.token_balance(env.treasury_token_account).await, 20);
+    let book = market_book(&mut env, market).await;
+    assert_eq!(&book.prices[..2], &[32_666, 0]);
+
+    env.claim_payout(&alice, market, 0).await.unwrap();
+    assert_eq!(env.token_balance(alice.token_account).await, 980);
+    assert_eq!(env.token_balance(env.escrow_token_account(market, 0)).await, 0);
+
+    let result = env.sweep_escrows(market, 1).await;
+    assert_program_error(result, PredictionMarketError::InvalidSettlementAccounts);
+}
+
+#[tokio::test]
+async fn books_hold_more_outcomes_than_markets_without() {
+    let mut env = TestEnv::new().await;
+    let market = book_market(&mut env, MAX_BOOK_OUTCOMES as u8).await;
+    let stored: Market = env.account(market).await;
+    assert_eq!(stored.outcomes.len(), MAX_BOOK_OUTCOMES);
+    assert_eq!(env.account_data(market).await.len(), Market::space_for(MAX_BOOK_OUTCOMES));
+
+    let result = env.add_outcome(market, MAX_BOOK_OUTCOMES as u8).await;
+    assert_program_error(result, PredictionMarketError::TooManyOutcomes);
+
+    // Without a book every losing escrow has to be swept at resolution
+    let market = env.create_market_with_outcomes(3).await;
+    let alice = env.create_user(100).await;
+    env.place_bet(&alice, market, 0, 100).await.unwrap();
+    let end_time = env.account::<Market>(market).await.end_time;
+    env.warp_to(end_time).await;
+    let result = env.resolve_market_sweeping(market, 0, 1).await;
+    assert_program_error(result, PredictionMarketError::InvalidSettlementAccounts);
+}
+
+/// Transaction size and account data loaded to place one bet and leave
+/// every outcome priced, with `outcomes` outcomes: a bet followed by
+/// `refresh_odds` without a book, and a bet alone with one.
+async fn pricing_footprint(outcomes: u8, with_book: bool) -> (usize, usize) {
+    let mut env = TestEnv::new().await;
+    let market = env.create_market().await;
+    if with_book {
+        env.create_market_book(market).await.unwrap();
+    }
+    for outcome_id in 0..outcomes {
+        env.add_outcome(market, outcome_id).await.unwrap();
+    }
+
+    let alice = env.create_user(100).await;
+    let mut accounts = env.place_bet_accounts(&alice, market, 0);
+    accounts.market_book = env.market_book(market).await;
+    let mut ixs = vec![program_instruction(accounts, instruction::PlaceBet { amount: 100 })];
+    if !with_book {
+        let ids: Vec<u8> = (0..outcomes).collect();
+        ixs.push(env.refresh_odds_instruction(market, &ids));
+    }
+    let footprint = env.transaction_footprint(&ixs, 1).await;
+    env.process(&ixs, &[&alice.keypair]).await.unwrap();
+    footprint
+}
+
+#[tokio::test]
+async fn book_is_cheaper_to_price_large_markets() {
+    println!("outcomes | per-outcome tx bytes | loaded bytes | book tx bytes | loaded bytes");
+    for outcomes in [2, 8, MAX_OUTCOMES as u8] {
+        let (per_outcome_size, per_outcome_loaded) = pricing_footprint(outcomes, false).await;
+        let (book_size, book_loaded) = pricing_footprint(outcomes, true).await;
+        println!(
+            "{outcomes:>8} | {per_outcome_size:>20} | {per_outcome_loaded:>12} | {book_size:>13} | {book_loaded:>12}"
+        );
+
+        // The book costs the same however many outcomes the market has
+        if outcomes as usize == MAX_OUTCOMES {
+            assert!(book_size < per_outcome_size);
+            assert!(book_loaded < per_outcome_loaded);
+        }
+    }
+}
+
+
+/// Compute units to place a bet and leave every outcome priced, as in
+/// `pricing_footprint`, and to settle the market: in one transaction
+/// sweeping every losing escrow without a book, and with one in as many
+/// `sweep_escrows` batches of eight as it takes.
+async fn compute_units(env: &mut TestEnv, outcomes: u8, with_book: bool) -> (u64, u64) {
+    let market = if with_book {
+        book_market(env, outcomes).await
+    } else {
+        env.create_market_with_outcomes(outcomes).await
+    };
+
+    let alice = env.create_user(100).await;
+    let mut accounts = env.place_bet_accounts(&alice, market, 0);
+    accounts.market_book = env.market_book(market).await;
+    let mut ixs = vec![program_instruction(accounts, instruction::PlaceBet { amount: 100 })];
+    if !with_book {
+        let ids: Vec<u8> = (0..outcomes).collect();
+        ixs.push(env.refresh_odds_instruction(market, &ids));
+    }
+    let pricing = env.compute_units(&ixs, &[&alice.keypair]).await;
+    env.process(&ixs, &[&alice.keypair]).await.unwrap();
+
+    let end_time = env.account::<Market>(market).await.end_time;
+    env.warp_to(end_time).await;
+    let admin = env.admin.insecure_clone();
+    let sweep = if with_book { 8 } else { outcomes as usize - 1 };
+    let ix = env.resolve_market_instruction(market, 0, sweep.min(outcomes as usize - 1)).await;
+    let mut settlement = env.compute_units(std::slice::from_ref(&ix), &[&admin]).await;
+    env.process(&[ix], &[&admin]).await.unwrap();
+    while env.account::<Market>(market).await.unswept_escrows > 0 {
+        let ix = env.sweep_escrows_instruction(market, sweep).await;
+        settlement += env.compute_units(std::slice::from_ref(&ix), &[&admin]).await;
+        env.process(&[ix], &[&admin]).await.unwrap();
+    }
+    (pricing, settlement)
+}
+
+/// Needs the program built for SBF, since natively run programs are not
+/// metered, so it only runs when asked for with `--ignored`. See the README
+/// for the commands.
+#[tokio::test]
+#[ignore = "needs prediction_market.so from `cargo build-sbf`"]
+async fn compute_units_versus_outcome_accounts() {
+    let mut env = TestEnv::new_sbf().await;
+
+    println!("outcomes | per-outcome pricing CU | settlement CU | book pricing CU | settlement CU");
+    for outcomes in [2, 8, MAX_OUTCOMES as u8, 32] {
+        let per_outcome = if outcomes as usize <= MAX_OUTCOMES {
+            Some(compute_units(&mut env, outcomes, false).await)
+        } else {
+            None
+        };
+        let (book_pricing, book_settlement) = compute_units(&mut env, outcomes, true).await;
+        let (pricing, settlement) = per_outcome.map_or(("-".to_string(), "-".to_string()), |(pricing, settlement)| {
+            (pricing.to_string(), settlement.to_string())
+        });
+        println!("{outcomes:>8} | {pricing:>22} | {settlement:>13} | {book_pricing:>15} | {book_settlement:>13}");
+    }
+}
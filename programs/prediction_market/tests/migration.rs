@@ -0,0 +1,515 @@
+//! Migrating accounts created by the first deployed version of the program.
+//! The accounts are written by that version itself, kept verbatim as the
+//! `prediction_market_baseline` fixture and run in a bank of its own, then
+//! copied over for the `migrate_*` instructions to rewrite in the current
+//! layout, after which they work as before.
+
+mod common;
+
+use anchor_lang::error::ErrorCode;
+use common::*;
+use prediction_market::errors::PredictionMarketError;
+use prediction_market::state::legacy::LegacyPlatformConfig;
+use prediction_market::state::{Market, Outcome, PlatformConfig, MAX_OUTCOMES};
+use prediction_market::{accounts, instruction};
+use prediction_market_baseline::{accounts as baseline_accounts, instruction as baseline_instruction};
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::account::Account;
+use solana_sdk::account_info::AccountInfo;
+use solana_sdk::entrypoint::ProgramResult;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_system_interface::program as system_program;
+use solana_sdk::transaction::Transaction;
+use anchor_spl::associated_token::spl_associated_token_account;
+use anchor_spl::token::spl_token;
+
+fn process_baseline_instruction(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    prediction_market_baseline::entry(program_id, accounts, data)
+}
+
+/// A bank running the first version of the program at the same address,
+/// seeded with copies of accounts from a `TestEnv`.
+struct Baseline {
+    ctx: ProgramTestContext,
+    admin: Keypair,
+    mint: Pubkey,
+}
+
+impl Baseline {
+    /// Starts the first version with `env`'s admin and collateral mint, and
+    /// copies of `accounts`.
+    async fn start(env: &mut TestEnv, accounts: &[Pubkey]) -> Self {
+        let mut program_test = ProgramTest::new(
+            "prediction_market_baseline",
+            prediction_market::ID,
+            processor!(process_baseline_instruction),
+        );
+        program_test.prefer_bpf(false);
+        for address in [env.admin.pubkey(), env.mint].iter().chain(accounts) {
+            program_test.add_account(*address, env.raw_account(*address).await);
+        }
+
+        Self {
+            ctx: program_test.start_with_context().await,
+            admin: env.admin.insecure_clone(),
+            mint: env.mint,
+        }
+    }
+
+    async fn send(
+        &mut self,
+        accounts: impl anchor_lang::ToAccountMetas,
+        args: impl anchor_lang::InstructionData,
+        signer: &Keypair,
+    ) -> Result<(), BanksClientError> {
+        let payer = self.ctx.payer.insecure_clone();
+        let blockhash = self.ctx.get_new_latest_blockhash().await.unwrap();
+        let tx = Transaction::new_signed_with_payer(
+            &[program_instruction(accounts, args)],
+            Some(&payer.pubkey()),
+            &[&payer, signer],
+            blockhash,
+        );
+        self.ctx.banks_client.process_transaction(tx).await
+    }
+
+    async fn now(&mut self) -> i64 {
+        self.ctx.banks_client.get_sysvar::<solana_sdk::clock::Clock>().await.unwrap().unix_timestamp
+    }
+
+    async fn initialize_platform(&mut self) {
+        let admin = self.admin.insecure_clone();
+        self.send(
+            baseline_accounts::InitializePlatform {
+                admin: admin.pubkey(),
+                treasury: Pubkey::new_unique(),
+                platform_config: platform_config(),
+                system_program: system_program::ID,
+            },
+            baseline_instruction::InitializePlatform {},
+            &admin,
+        )
+        .await
+        .unwrap();
+    }
+
+    /// Creates market `id` with `outcomes` outcomes and a 3% fee
+    async fn create_market(&mut self, id: u64, outcomes: u8) -> Pubkey {
+        let name = format!("Baseline Market {}", id);
+        self.create_market_named(id, &name, "Created by the first version", outcomes).await
+    }
+
+    async fn create_market_named(&mut self, id: u64, name: &str, description: &str, outcomes: u8) -> Pubkey {
+        let admin = self.admin.insecure_clone();
+        let now = self.now().await;
+        let market = market_address(id);
+        self.send(
+            baseline_accounts::CreateMarket {
+                creator: admin.pubkey(),
+                platform_config: platform_config(),
+                market,
+                system_program: system_program::ID,
+            },
+            baseline_instruction::CreateMarket {
+                name: name.to_string(),
+                description: description.to_string(),
+                start_time: now + MARKET_START_OFFSET,
+                end_time: now + MARKET_END_OFFSET,
+                custom_fee_percentage: Some(3),
+            },
+            &admin,
+        )
+        .await
+        .unwrap();
+
+        for outcome_id in 0..outcomes {
+            self.send(
+                baseline_accounts::AddOutcome {
+                    market,
+                    authority: admin.pubkey(),
+                    outcome: outcome_address(market, outcome_id),
+                    escrow_authority: escrow_address(market, outcome_id),
+                    mint: self.mint,
+                    escrow_token_account: escrow_token_address(market, outcome_id, &self.mint),
+                    token_program: spl_token::ID,
+                    associated_token_program: spl_associated_token_account::ID,
+                    system_program: system_program::ID,
+                },
+                baseline_instruction::AddOutcome { name: format!("Team {} wins", outcome_id), outcome_id },
+                &admin,
+            )
+            .await
+            .unwrap();
+        }
+        market
+    }
+
+    /// A bet as the first version's `place_bet` expected it, with `escrow`
+    /// standing in for the outcome's escrow token account
+    async fn place_bet(&mut self, user: &User, market: Pubkey, outcome_id: u8, escrow: Pubkey) -> Result<(), BanksClientError> {
+        self.send(
+            baseline_accounts::PlaceBet {
+                user: user.pubkey(),
+                market,
+                outcome: outcome_address(market, outcome_id),
+                user_position: user_position_address(user.pubkey(), market, outcome_id),
+                mint: self.mint,
+                user_token_account: user.token_account,
+                escrow_token_account: escrow,
+                token_program: spl_token::ID,
+                system_program: system_program::ID,
+            },
+            baseline_instruction::PlaceBet { amount: 100 },
+            &user.keypair,
+        )
+        .await
+    }
+
+    async fn resolve_market(&mut self, market: Pubkey, winning_outcome_id: u8) {
+        let admin = self.admin.insecure_clone();
+        let mut clock = self.ctx.banks_client.get_sysvar::<solana_sdk::clock::Clock>().await.unwrap();
+        clock.unix_timestamp += MARKET_END_OFFSET;
+        self.ctx.set_sysvar(&clock);
+        self.send(
+            baseline_accounts::ResolveMarket { market, oracle: admin.pubkey(), platform_config: platform_config() },
+            baseline_instruction::ResolveMarket { winning_outcome_id },
+            &admin,
+        )
+        .await
+        .unwrap();
+    }
+
+    /// Copies the accounts at `addresses`, exactly as this version wrote
+    /// them, into `env`
+    async fn copy_to(&mut self, env: &mut TestEnv, addresses: &[Pubkey]) {
+        for address in addresses {
+            let account: Account = self.ctx.banks_client.get_account(*address).await.unwrap().unwrap();
+            env.put_account(*address, account);
+        }
+    }
+}
+
+/// Platform config, market, outcomes and escrows of a market the first
+/// version created
+fn market_accounts(env: &TestEnv, market: Pubkey, outcomes: u8) -> Vec<Pubkey> {
+    let mut addresses = vec![platform_config(), market];
+    for outcome_id in 0..outcomes {
+        addresses.push(outcome_address(market, outcome_id));
+        addresses.push(escrow_token_address(market, outcome_id, &env.mint));
+    }
+    addresses
+}
+
+async fn migrate_platform_config(env: &mut TestEnv) -> Result<(), BanksClientError> {
+    let payer = env.admin.pubkey();
+    env.send_as_admin(
+        accounts::MigratePlatformConfig { platform_config: platform_config(), payer, system_program: system_program::ID },
+        instruction::MigratePlatformConfig {},
+    )
+    .await
+}
+
+async fn migrate_market(env: &mut TestEnv, market: Pubkey, mint: Pubkey) -> Result<(), BanksClientError> {
+    env.send_as_admin(
+        accounts::MigrateMarket {
+            market,
+            platform_config: platform_config(),
+            collateral_config: collateral_config(&mint),
+            admin: env.admin.pubkey(),
+            system_program: system_program::ID,
+        },
+        instruction::MigrateMarket {},
+    )
+    .await
+}
+
+async fn migrate_outcome(env: &mut TestEnv, market: Pubkey, outcome_id: u8) -> Result<(), BanksClientError> {
+    env.send_as_admin(
+        accounts::MigrateOutcome {
+            outcome: outcome_address(market, outcome_id),
+            market,
+            payer: env.admin.pubkey(),
+            system_program: system_program::ID,
+        },
+        instruction::MigrateOutcome {},
+    )
+    .await
+}
+
+#[tokio::test]
+async fn the_first_version_could_not_take_bets() {
+    let mut env = TestEnv::new().await;
+    let alice = env.create_user(1_000).await;
+    let mut baseline = Baseline::start(&mut env, &[alice.pubkey(), alice.token_account]).await;
+    baseline.initialize_platform().await;
+    let market = baseline.create_market(0, 2).await;
+
+    // `place_bet` wanted the escrow at the escrow authority's own address,
+    // where no token account was ever opened, and not the one `add_outcome`
+    // created, so no outcome was staked on and no position written
+    let escrow = escrow_token_address(market, 0, &env.mint);
+    let result = baseline.place_bet(&alice, market, 0, escrow).await;
+    assert_anchor_error(result, ErrorCode::ConstraintSeeds);
+    let result = baseline.place_bet(&alice, market, 0, escrow_address(market, 0)).await;
+    assert_anchor_error(result, ErrorCode::AccountNotInitialized);
+}
+
+#[tokio::test]
+async fn baseline_accounts_migrate_and_settle() {
+    let mut env = TestEnv::new().await;
+    let mut baseline = Baseline::start(&mut env, &[]).await;
+    baseline.initialize_platform().await;
+    let market = baseline.create_market(0, 2).await;
+    let mint = env.mint;
+    let addresses = market_accounts(&env, market, 2);
+    baseline.copy_to(&mut env, &addresses).await;
+
+    // The first version's sizes, not the current ones
+    assert_eq!(env.account_data_len(platform_config()).await, LegacyPlatformConfig::SIZE);
+    let market_size = env.account_data_len(market).await;
+    assert_eq!(market_size, 442 + "Baseline Market 0".len() + "Created by the first version".len());
+
+    migrate_platform_config(&mut env).await.unwrap();
+    migrate_market(&mut env, market, mint).await.unwrap();
+    migrate_outcome(&mut env, market, 0).await.unwrap();
+    migrate_outcome(&mut env, market, 1).await.unwrap();
+
+    let config = env.platform_config().await;
+    assert_eq!(config.version, PlatformConfig::VERSION);
+    assert_eq!(config.admin, env.admin.pubkey());
+    assert_eq!(config.default_fee_bps, 200);
+    assert_eq!(config.markets_count, 1);
+    assert_eq!(env.account_data_len(platform_config()).await, PlatformConfig::SIZE);
+    let migrated: Market = env.account(market).await;
+    assert_eq!(migrated.version, Market::VERSION);
+    assert_eq!(migrated.name, "Baseline Market 0");
+    assert_eq!(migrated.fee_bps, 300);
+    assert_eq!(migrated.collateral_mint, env.mint);
+    assert_eq!(migrated.outcomes, vec![0, 1]);
+    assert_eq!(env.account_data_len(market).await, Market::SIZE);
+    let migrated: Outcome = env.account(outcome_address(market, 1)).await;
+    assert_eq!(migrated.version, Outcome::VERSION);
+    assert_eq!(migrated.name, "Team 1 wins");
+    assert_eq!(migrated.escrow_pubkey, env.escrow_token_account(market, 1));
+    assert_eq!(migrated.odds, 10_000);
+    assert_eq!(env.account_data_len(outcome_address(market, 1)).await, Outcome::SIZE);
+
+    // The migrated market takes bets and settles at its converted 3% fee
+    let alice = env.create_user(600).await;
+    let bob = env.create_user(400).await;
+    env.place_bet(&alice, market, 0, 600).await.unwrap();
+    env.place_bet(&bob, market, 1, 400).await.unwrap();
+    env.end_and_resolve(market, 0).await;
+    assert_eq!(env.token_balance(env.treasury_token_account).await, 30);
+    env.claim_payout(&alice, market, 0).await.unwrap();
+    assert_eq!(env.token_balance(alice.token_account).await, 970);
+
+    // And the platform carries on numbering markets after it
+    assert_eq!(env.create_market().await, market_address(1));
+}
+
+#[tokio::test]
+async fn baseline_resolved_markets_stay_resolved() {
+    let mut env = TestEnv::new().await;
+    let mut baseline = Baseline::start(&mut env, &[]).await;
+    baseline.initialize_platform().await;
+    let market = baseline.create_market(0, 2).await;
+    baseline.resolve_market(market, 1).await;
+    let mint = env.mint;
+    let addresses = market_accounts(&env, market, 2);
+    baseline.copy_to(&mut env, &addresses).await;
+
+    migrate_platform_config(&mut env).await.unwrap();
+    migrate_market(&mut env, market, mint).await.unwrap();
+    migrate_outcome(&mut env, market, 0).await.unwrap();
+    migrate_outcome(&mut env, market, 1).await.unwrap();
+
+    let migrated: Market = env.account(market).await;
+    assert_eq!(migrated.winner, Some(1));
+    assert!(migrated.resolved);
+    assert_eq!(migrated.total_pool, 0);
+    let result = env.resolve_market(market, 0).await;
+    assert_program_error(result, PredictionMarketError::MarketAlreadyResolved);
+}
+
+#[tokio::test]
+async fn outcomes_must_match_the_named_collateral() {
+    let mut env = TestEnv::new().await;
+    let mut baseline = Baseline::start(&mut env, &[]).await;
+    baseline.initialize_platform().await;
+    let market = baseline.create_market(0, 1).await;
+    let addresses = market_accounts(&env, market, 1);
+    baseline.copy_to(&mut env, &addresses).await;
+    migrate_platform_config(&mut env).await.unwrap();
+
+    // Only the admin picks a market's collateral
+    let mallory = env.create_user(0).await;
+    let result = env
+        .send(
+            accounts::MigrateMarket {
+                market,
+                platform_config: platform_config(),
+                collateral_config: collateral_config(&env.mint),
+                admin: mallory.pubkey(),
+                system_program: system_program::ID,
+            },
+            instruction::MigrateMarket {},
+            &[&mallory.keypair],
+        )
+        .await;
+    assert_program_error(result, PredictionMarketError::UnauthorizedAdmin);
+
+    // An outcome whose escrow holds another mint is refused
+    let other_mint = env.create_other_mint().await;
+    env.add_collateral_mint(other_mint).await.unwrap();
+    migrate_market(&mut env, market, other_mint).await.unwrap();
+    let result = migrate_outcome(&mut env, market, 0).await;
+    assert_program_error(result, PredictionMarketError::InvalidMint);
+}
+
+#[tokio::test]
+async fn migrated_markets_pay_out_in_full() {
+    let mut env = TestEnv::new().await;
+    let mut baseline = Baseline::start(&mut env, &[]).await;
+    baseline.initialize_platform().await;
+    let resolved = baseline.create_market(0, 2).await;
+    let cancelled = baseline.create_market(1, 2).await;
+    let mut addresses = market_accounts(&env, resolved, 2);
+    addresses.extend(market_accounts(&env, cancelled, 2));
+    baseline.copy_to(&mut env, &addresses).await;
+
+    let mint = env.mint;
+    migrate_platform_config(&mut env).await.unwrap();
+    for market in [resolved, cancelled] {
+        migrate_market(&mut env, market, mint).await.unwrap();
+        migrate_outcome(&mut env, market, 0).await.unwrap();
+        migrate_outcome(&mut env, market, 1).await.unwrap();
+    }
+
+    // Every winner claims their share of the 970 left after the 3% fee, the
+    // last one whatever rounding left behind
+    let alice = env.create_user(300).await;
+    let bob = env.create_user(100).await;
+    let carol = env.create_user(600).await;
+    env.place_bet(&alice, resolved, 0, 300).await.unwrap();
+    env.place_bet(&bob, resolved, 0, 100).await.unwrap();
+    env.place_bet(&carol, resolved, 1, 600).await.unwrap();
+    let dave = env.create_user(200).await;
+    let erin = env.create_user(50).await;
+    env.place_bet(&dave, cancelled, 0, 200).await.unwrap();
+    env.place_bet(&erin, cancelled, 1, 50).await.unwrap();
+    env.end_and_resolve(resolved, 0).await;
+    env.claim_payout(&alice, resolved, 0).await.unwrap();
+    env.claim_payout(&bob, resolved, 0).await.unwrap();
+    assert_eq!(env.token_balance(alice.token_account).await, 727);
+    assert_eq!(env.token_balance(bob.token_account).await, 243);
+    let outcome: Outcome = env.account(outcome_address(resolved, 0)).await;
+    assert_eq!(outcome.claimants_remaining, 0);
+    assert_eq!(outcome.total_claimed, 970);
+    assert_eq!(env.token_balance(env.escrow_token_account(resolved, 0)).await, 0);
+    assert_eq!(env.token_balance(env.escrow_token_account(resolved, 1)).await, 0);
+
+    // And every stake on a cancelled one comes back
+    env.cancel_market(cancelled).await.unwrap();
+    env.claim_refund(&dave, cancelled, 0).await.unwrap();
+    env.claim_refund(&erin, cancelled, 1).await.unwrap();
+    assert_eq!(env.token_balance(dave.token_account).await, 200);
+    assert_eq!(env.token_balance(erin.token_account).await, 50);
+    for outcome_id in 0..2 {
+        let outcome: Outcome = env.account(outcome_address(cancelled, outcome_id)).await;
+        assert_eq!(outcome.claimants_remaining, 0);
+        assert_eq!(env.token_balance(env.escrow_token_account(cancelled, outcome_id)).await, 0);
+    }
+}
+
+#[tokio::test]
+async fn first_version_markets_the_size_of_a_current_one_migrate() {
+    let mut env = TestEnv::new().await;
+    let mut baseline = Baseline::start(&mut env, &[]).await;
+    baseline.initialize_platform().await;
+    baseline.create_market(0, 0).await;
+
+    // Sized like a current market, with a 1 where the version byte would be
+    let name = "N".repeat(69);
+    let description = "D".repeat(Market::SIZE - 442 - name.len());
+    let market = baseline.create_market_named(1, &name, &description, 1).await;
+    let mint = env.mint;
+    let addresses = market_accounts(&env, market, 1);
+    baseline.copy_to(&mut env, &addresses).await;
+    assert_eq!(env.account_data_len(market).await, Market::SIZE);
+    assert_eq!(env.account_data(market).await[8], Market::VERSION);
+
+    migrate_platform_config(&mut env).await.unwrap();
+    migrate_market(&mut env, market, mint).await.unwrap();
+    let migrated: Market = env.account(market).await;
+    assert_eq!(migrated.id, 1);
+    assert_eq!(migrated.description, description);
+}
+
+#[tokio::test]
+async fn staked_outcomes_are_not_guessed_at() {
+    let mut env = TestEnv::new().await;
+    let mut baseline = Baseline::start(&mut env, &[]).await;
+    baseline.initialize_platform().await;
+    let market = baseline.create_market(0, 1).await;
+    let mint = env.mint;
+    let addresses = market_accounts(&env, market, 1);
+    baseline.copy_to(&mut env, &addresses).await;
+    migrate_platform_config(&mut env).await.unwrap();
+    migrate_market(&mut env, market, mint).await.unwrap();
+
+    // Claims can't be derived for an outcome with stake on it, as it would
+    // have had if the first version had taken bets
+    let outcome = outcome_address(market, 0);
+    let mut data = env.account_data(outcome).await;
+    let total_staked = 8 + 1 + 32 + 4 + "Team 0 wins".len() + 32;
+    data[total_staked..total_staked + 8].copy_from_slice(&100u64.to_le_bytes());
+    env.set_account_data(outcome, &data).await;
+    let result = migrate_outcome(&mut env, market, 0).await;
+    assert_program_error(result, PredictionMarketError::InvalidMigration);
+}
+
+#[tokio::test]
+async fn markets_sized_for_fewer_outcomes_grow() {
+    let mut env = TestEnv::new().await;
+    let market = env.create_market_with_outcomes(2).await;
+
+    // Markets were first versioned with room for 10 outcome ids
+    let data = env.account_data(market).await;
+    let old_size = Market::SIZE - (MAX_OUTCOMES - 10);
+    env.set_account_data(market, &data[..old_size]).await;
+
+    env.add_outcome(market, 2).await.unwrap();
+    assert_eq!(env.account_data_len(market).await, Market::SIZE);
+    assert_eq!(env.account::<Market>(market).await.outcomes, vec![0, 1, 2]);
+}
+
+#[tokio::test]
+async fn current_accounts_are_left_alone() {
+    let mut env = TestEnv::new().await;
+    let market = env.create_market_with_outcomes(1).await;
+    let mint = env.mint;
+
+    let result = migrate_platform_config(&mut env).await;
+    assert_program_error(result, PredictionMarketError::InvalidMigration);
+    let result = migrate_market(&mut env, market, mint).await;
+    assert_program_error(result, PredictionMarketError::InvalidMigration);
+    let result = migrate_outcome(&mut env, market, 0).await;
+    assert_program_error(result, PredictionMarketError::InvalidMigration);
+
+    // Nor can one account type be migrated as another
+    let result = env
+        .send_as_admin(
+            accounts::MigrateOutcome {
+                outcome: market,
+                market,
+                payer: env.admin.pubkey(),
+                system_program: system_program::ID,
+            },
+            instruction::MigrateOutcome {},
+        )
+        .await;
+    assert_program_error(result, PredictionMarketError::InvalidMigration);
+}
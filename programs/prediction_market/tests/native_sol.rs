@@ -0,0 +1,98 @@
+//! Native SOL markets: bets come straight from the wallet and are wrapped
+//! into wSOL escrows, and payouts and refunds are unwrapped back into it.
+
+mod common;
+
+use anchor_spl::token::spl_token;
+use common::*;
+use prediction_market::state::{CollateralMode, Market, Outcome, UserPosition};
+
+#[tokio::test]
+async fn bets_are_wrapped_and_payouts_unwrapped() {
+    let mut env = TestEnv::new().await;
+    env.use_native_sol().await;
+    let market = env.create_market_with_outcomes(2).await;
+
+    let stored: Market = env.account(market).await;
+    assert_eq!(stored.collateral_mode, CollateralMode::NativeSol);
+    assert_eq!(stored.collateral_mint, spl_token::native_mint::ID);
+
+    let alice = env.create_user(1_000_000_000).await;
+    let bob = env.create_user(1_000_000_000).await;
+    env.place_bet(&alice, market, 0, 300_000_000).await.unwrap();
+    env.place_bet(&bob, market, 1, 100_000_000).await.unwrap();
+
+    // Only the stake and the position's, stake's and profile's rent leave the wallet
+    let rent = env.lamports(user_position_address(alice.pubkey(), market, 0)).await
+        + env.lamports(market_stake_address(alice.pubkey(), market)).await
+        + env.lamports(user_profile_address(alice.pubkey())).await;
+    let after_bet = USER_LAMPORTS + 700_000_000 - rent;
+    assert_eq!(env.lamports(alice.pubkey()).await, after_bet);
+    assert_eq!(env.token_balance(env.escrow_token_account(market, 0)).await, 300_000_000);
+
+    env.end_and_resolve(market, 0).await;
+    assert_eq!(env.token_balance(env.treasury_token_account).await, 8_000_000);
+
+    env.claim_payout(&alice, market, 0).await.unwrap();
+    assert_eq!(env.lamports(alice.pubkey()).await, after_bet + 392_000_000);
+    assert_eq!(env.token_balance(env.escrow_token_account(market, 0)).await, 0);
+    assert!(!env.account_exists(unwrap_address(alice.pubkey())).await);
+    assert_eq!(env.account::<Outcome>(outcome_address(market, 0)).await.total_claimed, 392_000_000);
+}
+
+#[tokio::test]
+async fn lamports_sent_to_an_escrow_are_not_credited_to_the_next_bet() {
+    let mut env = TestEnv::new().await;
+    env.use_native_sol().await;
+    let market = env.create_market_with_outcomes(2).await;
+
+    // Wrapping syncs these along with the bet's lamports
+    let escrow = env.escrow_token_account(market, 0);
+    env.airdrop(&escrow, 50_000_000).await;
+
+    let alice = env.create_user(1_000_000_000).await;
+    env.place_bet(&alice, market, 0, 100_000_000).await.unwrap();
+    assert_eq!(env.token_balance(escrow).await, 150_000_000);
+
+    let position: UserPosition = env.account(user_position_address(alice.pubkey(), market, 0)).await;
+    assert_eq!(position.amount, 100_000_000);
+    assert_eq!(env.account::<Outcome>(outcome_address(market, 0)).await.total_staked, 100_000_000);
+    assert_eq!(env.account::<Market>(market).await.total_pool, 100_000_000);
+}
+
+#[tokio::test]
+async fn refunds_are_unwrapped() {
+    let mut env = TestEnv::new().await;
+    env.use_native_sol().await;
+    let market = env.create_market_with_outcomes(2).await;
+
+    let alice = env.create_user(1_000_000_000).await;
+    env.place_bet(&alice, market, 1, 400_000_000).await.unwrap();
+    env.cancel_market(market).await.unwrap();
+    env.claim_refund(&alice, market, 1).await.unwrap();
+
+    let rent = env.lamports(user_position_address(alice.pubkey(), market, 1)).await
+        + env.lamports(market_stake_address(alice.pubkey(), market)).await
+        + env.lamports(user_profile_address(alice.pubkey())).await;
+    assert_eq!(env.lamports(alice.pubkey()).await, USER_LAMPORTS + 1_000_000_000 - rent);
+    assert_eq!(env.token_balance(env.escrow_token_account(market, 1)).await, 0);
+}
+
+#[tokio::test]
+async fn prefunded_unwrap_account_does_not_block_claims() {
+    let mut env = TestEnv::new().await;
+    env.use_native_sol().await;
+    let market = env.create_market_with_outcomes(2).await;
+
+    let alice = env.create_user(1_000_000_000).await;
+    env.place_bet(&alice, market, 0, 500_000_000).await.unwrap();
+    env.end_and_resolve(market, 0).await;
+
+    // Anyone can send lamports to the temporary account's address
+    env.airdrop(&unwrap_address(alice.pubkey()), 1_000_000).await;
+    let before = env.lamports(alice.pubkey()).await;
+
+    env.claim_payout(&alice, market, 0).await.unwrap();
+    assert_eq!(env.lamports(alice.pubkey()).await, before + 490_000_000 + 1_000_000);
+    assert!(!env.account_exists(unwrap_address(alice.pubkey())).await);
+}
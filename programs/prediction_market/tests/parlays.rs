@@ -0,0 +1,166 @@
+//! Parlays: one stake on several outcomes all winning, at odds fixed when
+//! placed and paid from the house vault by a settlement crank.
+
+mod common;
+
+use common::*;
+use prediction_market::errors::PredictionMarketError;
+use prediction_market::state::{House, LegLimits, LegResult, Parlay, ParlayLimits, ParlayStatus};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signer;
+
+/// Two matches priced at 3.92 on outcome 0 of the first and 1.96 on
+/// outcome 0 of the second, after a 2% fee.
+async fn priced_matches(env: &mut TestEnv) -> (Pubkey, Pubkey) {
+    let first = env.create_market_with_outcomes(2).await;
+    let second = env.create_market_with_outcomes(2).await;
+    let bettor = env.create_user(800).await;
+    env.place_bet(&bettor, first, 0, 100).await.unwrap();
+    env.place_bet(&bettor, first, 1, 300).await.unwrap();
+    env.place_bet(&bettor, second, 0, 200).await.unwrap();
+    env.place_bet(&bettor, second, 1, 200).await.unwrap();
+    (first, second)
+}
+
+#[tokio::test]
+async fn winning_parlay_is_paid_by_the_house() {
+    let mut env = TestEnv::new().await;
+    env.create_house(PARLAY_LIMITS, 10_000).await.unwrap();
+    let (first, second) = priced_matches(&mut env).await;
+
+    let alice = env.create_user(100).await;
+    env.place_parlay(&alice, 7, 100, &[(first, 0), (second, 0)]).await.unwrap();
+    let parlay: Parlay = env.account(parlay_address(alice.pubkey(), 7)).await;
+    assert_eq!(parlay.legs.iter().map(|leg| leg.odds).collect::<Vec<_>>(), vec![39_200, 19_600]);
+    assert_eq!(parlay.payout, 768);
+    assert_eq!(env.account::<House>(house_address(&env.mint)).await.liability, 768);
+    assert_eq!(env.token_balance(env.house_vault()).await, 10_100);
+
+    // Later bets move the markets but not the parlay's odds
+    let bob = env.create_user(1_000).await;
+    env.place_bet(&bob, first, 0, 1_000).await.unwrap();
+
+    env.end_and_resolve(first, 0).await;
+    let result = env.settle_parlay(&alice, 7).await;
+    assert_program_error(result, PredictionMarketError::ParlayNotSettled);
+
+    env.resolve_market(second, 0).await.unwrap();
+    env.settle_parlay(&alice, 7).await.unwrap();
+    let parlay: Parlay = env.account(parlay_address(alice.pubkey(), 7)).await;
+    assert_eq!(parlay.status, ParlayStatus::Won);
+    assert_eq!(parlay.paid, 768);
+    assert_eq!(env.token_balance(alice.token_account).await, 768);
+    assert_eq!(env.account::<House>(house_address(&env.mint)).await.liability, 0);
+    assert_eq!(env.token_balance(env.house_vault()).await, 9_332);
+}
+
+#[tokio::test]
+async fn losing_leg_settles_at_once() {
+    let mut env = TestEnv::new().await;
+    env.create_house(PARLAY_LIMITS, 10_000).await.unwrap();
+    let (first, second) = priced_matches(&mut env).await;
+    let alice = env.create_user(100).await;
+    env.place_parlay(&alice, 0, 100, &[(first, 0), (second, 0)]).await.unwrap();
+
+    // The second match is still open, but the parlay is already lost
+    env.end_and_resolve(first, 1).await;
+    env.settle_parlay(&alice, 0).await.unwrap();
+    let parlay: Parlay = env.account(parlay_address(alice.pubkey(), 0)).await;
+    assert_eq!(parlay.status, ParlayStatus::Lost);
+    assert_eq!(parlay.legs[0].result, LegResult::Lost);
+    assert_eq!(parlay.legs[1].result, LegResult::Pending);
+    assert_eq!(env.account::<House>(house_address(&env.mint)).await.liability, 0);
+    assert_eq!(env.token_balance(env.house_vault()).await, 10_100);
+
+    let result = env.settle_parlay(&alice, 0).await;
+    assert_program_error(result, PredictionMarketError::ParlayAlreadySettled);
+}
+
+#[tokio::test]
+async fn cancelled_markets_void_their_legs() {
+    let mut env = TestEnv::new().await;
+    env.create_house(PARLAY_LIMITS, 10_000).await.unwrap();
+    let (first, second) = priced_matches(&mut env).await;
+    let alice = env.create_user(200).await;
+    env.place_parlay(&alice, 0, 100, &[(first, 0), (second, 0)]).await.unwrap();
+    env.place_parlay(&alice, 1, 100, &[(first, 0), (second, 0)]).await.unwrap();
+
+    // A void leg counts at even odds
+    env.cancel_market(second).await.unwrap();
+    env.end_and_resolve(first, 0).await;
+    env.settle_parlay(&alice, 0).await.unwrap();
+    let parlay: Parlay = env.account(parlay_address(alice.pubkey(), 0)).await;
+    assert_eq!((parlay.status, parlay.paid), (ParlayStatus::Won, 392));
+
+    // With every leg void the stake comes back
+    let (third, fourth) = priced_matches(&mut env).await;
+    let carol = env.create_user(100).await;
+    env.place_parlay(&carol, 0, 100, &[(third, 0), (fourth, 1)]).await.unwrap();
+    env.cancel_market(third).await.unwrap();
+    env.cancel_market(fourth).await.unwrap();
+    env.settle_parlay(&carol, 0).await.unwrap();
+    let parlay: Parlay = env.account(parlay_address(carol.pubkey(), 0)).await;
+    assert_eq!((parlay.status, parlay.paid), (ParlayStatus::Void, 100));
+    assert_eq!(env.token_balance(carol.token_account).await, 100);
+}
+
+#[tokio::test]
+async fn house_limits_and_liquidity_bound_its_risk() {
+    let mut env = TestEnv::new().await;
+    env.create_house(ParlayLimits { max_legs: 2, max_stake: 50, max_payout: u64::MAX, max_liability: 0 }, 1_000)
+        .await
+        .unwrap();
+    let (first, second) = priced_matches(&mut env).await;
+    let third = env.create_market_with_outcomes(2).await;
+    let bettor = env.create_user(200).await;
+    env.place_bet(&bettor, third, 0, 100).await.unwrap();
+    env.place_bet(&bettor, third, 1, 100).await.unwrap();
+    let alice = env.create_user(1_000).await;
+
+    let result = env.place_parlay(&alice, 0, 100, &[(first, 0), (second, 0)]).await;
+    assert_program_error(result, PredictionMarketError::ParlayLimitExceeded);
+    let result = env.place_parlay(&alice, 0, 50, &[(first, 0), (second, 0), (third, 0)]).await;
+    assert_program_error(result, PredictionMarketError::InvalidParlayLegCount);
+    let result = env.place_parlay(&alice, 0, 50, &[(first, 0)]).await;
+    assert_program_error(result, PredictionMarketError::InvalidParlayLegCount);
+
+    // The vault must cover every open parlay winning at once
+    env.set_parlay_limits(ParlayLimits { max_legs: 3, ..PARLAY_LIMITS }).await.unwrap();
+    env.place_parlay(&alice, 0, 50, &[(first, 0), (second, 0)]).await.unwrap();
+    env.place_parlay(&alice, 1, 50, &[(first, 0), (second, 0)]).await.unwrap();
+    let result = env.place_parlay(&alice, 2, 50, &[(first, 0), (second, 0), (third, 0)]).await;
+    assert_program_error(result, PredictionMarketError::InsufficientLiquidity);
+
+    // Only what is not reserved for the two open parlays can be withdrawn
+    let admin = env.admin.pubkey();
+    let admin_token_account = env.create_token_account(&admin).await;
+    let result = env.withdraw_house_liquidity(admin_token_account, 333).await;
+    assert_program_error(result, PredictionMarketError::InsufficientLiquidity);
+    env.withdraw_house_liquidity(admin_token_account, 332).await.unwrap();
+    assert_eq!(env.token_balance(admin_token_account).await, 332);
+}
+
+
+#[tokio::test]
+async fn thin_markets_cannot_set_leg_odds() {
+    let mut env = TestEnv::new().await;
+    env.create_house(PARLAY_LIMITS, 10_000).await.unwrap();
+    let (first, second) = priced_matches(&mut env).await;
+
+    // A dust bet on an empty outcome would otherwise be worth thousands
+    let thin = env.create_market_with_outcomes(2).await;
+    let bettor = env.create_user(1_001).await;
+    env.place_bet(&bettor, thin, 0, 1_000).await.unwrap();
+    env.place_bet(&bettor, thin, 1, 1).await.unwrap();
+    env.set_house_limits(PARLAY_LIMITS, LegLimits { min_pool: 1_500, max_odds: 30_000 }).await.unwrap();
+    let alice = env.create_user(200).await;
+    let result = env.place_parlay(&alice, 0, 100, &[(first, 0), (thin, 1)]).await;
+    assert_program_error(result, PredictionMarketError::InvalidParlayLeg);
+
+    // Deep enough markets are taken at no more than the cap
+    env.set_house_limits(PARLAY_LIMITS, LegLimits { min_pool: 400, max_odds: 30_000 }).await.unwrap();
+    env.place_parlay(&alice, 0, 100, &[(first, 0), (second, 0)]).await.unwrap();
+    let parlay: Parlay = env.account(parlay_address(alice.pubkey(), 0)).await;
+    assert_eq!(parlay.legs.iter().map(|leg| leg.odds).collect::<Vec<_>>(), vec![30_000, 19_600]);
+    assert_eq!(parlay.payout, 588);
+}
@@ -0,0 +1,146 @@
+//! Player props: over/under markets on one player's statistic, generated
+//! from a line and resolved from the value the oracle reports, and top
+//! player markets with an outcome per player, resolved to the leader.
+
+mod common;
+
+use common::*;
+use prediction_market::errors::PredictionMarketError;
+use prediction_market::state::{Market, MarketStatus, Outcome, Player, Prop, PropKind, StatKind, Team};
+use solana_sdk::pubkey::Pubkey;
+
+/// "TenZ over/under `line` kills", with 100 on over and 300 on under.
+async fn kills_prop(env: &mut TestEnv, line: u64) -> (Pubkey, [User; 2]) {
+    let team = env.create_team("Sentinels").await.unwrap();
+    let player = env.create_player(team, "TenZ").await.unwrap();
+    let market = env.create_market().await;
+    env.create_prop_market(market, player, StatKind::Kills, line).await.unwrap();
+
+    let users = [env.create_user(100).await, env.create_user(300).await];
+    env.place_bet(&users[0], market, Prop::OVER, 100).await.unwrap();
+    env.place_bet(&users[1], market, Prop::UNDER, 300).await.unwrap();
+    (market, users)
+}
+
+#[tokio::test]
+async fn registries_and_generated_outcomes() {
+    let mut env = TestEnv::new().await;
+    let team = env.create_team("Sentinels").await.unwrap();
+    let player = env.create_player(team, "TenZ").await.unwrap();
+    assert_eq!(env.account::<Team>(team).await.name, "Sentinels");
+    let stored: Player = env.account(player).await;
+    assert_eq!((stored.handle.as_str(), stored.team), ("TenZ", team));
+    let platform_config = env.platform_config().await;
+    assert_eq!((platform_config.teams_count, platform_config.players_count), (1, 1));
+
+    let market = env.create_market().await;
+    env.create_prop_market(market, player, StatKind::Acs, 2455).await.unwrap();
+    let stored: Market = env.account(market).await;
+    assert!(stored.has_prop);
+    assert_eq!(stored.outcomes, vec![Prop::OVER, Prop::UNDER]);
+    let over: Outcome = env.account(outcome_address(market, Prop::OVER)).await;
+    let under: Outcome = env.account(outcome_address(market, Prop::UNDER)).await;
+    assert_eq!(over.name, "Over 245.5 ACS");
+    assert_eq!(under.name, "Under 245.5 ACS");
+
+    // Props only ever have their two generated outcomes
+    let result = env.add_outcome(market, 2).await;
+    assert_program_error(result, PredictionMarketError::InvalidProp);
+}
+
+#[tokio::test]
+async fn stat_above_the_line_pays_over() {
+    let mut env = TestEnv::new().await;
+    let (market, users) = kills_prop(&mut env, 205).await;
+
+    // Props settle from the stat, not a chosen outcome
+    let end_time = env.account::<Market>(market).await.end_time;
+    env.warp_to(end_time).await;
+    let result = env.resolve_market(market, Prop::OVER).await;
+    assert_program_error(result, PredictionMarketError::InvalidProp);
+
+    env.resolve_prop_market(market, 21).await.unwrap();
+    let stored: Market = env.account(market).await;
+    assert_eq!((stored.status, stored.winner), (MarketStatus::Resolved, Some(Prop::OVER)));
+    assert_eq!(env.account::<Prop>(prop_address(market)).await.stat_value, Some(21));
+
+    // 2% fee on a pool of 400
+    env.claim_payout(&users[0], market, Prop::OVER).await.unwrap();
+    assert_eq!(env.token_balance(users[0].token_account).await, 392);
+}
+
+#[tokio::test]
+async fn stat_below_the_line_pays_under() {
+    let mut env = TestEnv::new().await;
+    let (market, users) = kills_prop(&mut env, 205).await;
+    let end_time = env.account::<Market>(market).await.end_time;
+    env.warp_to(end_time).await;
+
+    env.resolve_prop_market(market, 20).await.unwrap();
+    assert_eq!(env.account::<Market>(market).await.winner, Some(Prop::UNDER));
+    env.claim_payout(&users[1], market, Prop::UNDER).await.unwrap();
+    assert_eq!(env.token_balance(users[1].token_account).await, 392);
+}
+
+#[tokio::test]
+async fn stat_on_the_line_refunds_every_position() {
+    let mut env = TestEnv::new().await;
+    let (market, users) = kills_prop(&mut env, 200).await;
+
+    let result = env.resolve_prop_market(market, 20).await;
+    assert_program_error(result, PredictionMarketError::MarketNotEnded);
+
+    let end_time = env.account::<Market>(market).await.end_time;
+    env.warp_to(end_time).await;
+    env.resolve_prop_market(market, 20).await.unwrap();
+    assert_eq!(env.account::<Market>(market).await.status, MarketStatus::Cancelled);
+
+    env.claim_refund(&users[0], market, Prop::OVER).await.unwrap();
+    env.claim_refund(&users[1], market, Prop::UNDER).await.unwrap();
+    assert_eq!(env.token_balance(users[0].token_account).await, 100);
+    assert_eq!(env.token_balance(users[1].token_account).await, 300);
+}
+
+#[tokio::test]
+async fn top_player_prop_pays_the_leader() {
+    let mut env = TestEnv::new().await;
+    let team = env.create_team("Sentinels").await.unwrap();
+    let tenz = env.create_player(team, "TenZ").await.unwrap();
+    let zekken = env.create_player(team, "zekken").await.unwrap();
+    let market = env.create_market().await;
+    env.create_top_player_market(market, StatKind::Acs, &[tenz, zekken]).await.unwrap();
+
+    assert_eq!(env.account::<Prop>(prop_address(market)).await.kind, PropKind::TopPlayer);
+    assert_eq!(env.account::<Market>(market).await.outcomes, vec![0, 1]);
+    let first: Outcome = env.account(outcome_address(market, 0)).await;
+    let second: Outcome = env.account(outcome_address(market, 1)).await;
+    assert_eq!((first.name.as_str(), first.player), ("TenZ top ACS", Some(0)));
+    assert_eq!((second.name.as_str(), second.player), ("zekken top ACS", Some(1)));
+
+    // Outcomes only come from players, and players only join top player props
+    let result = env.add_outcome(market, 2).await;
+    assert_program_error(result, PredictionMarketError::InvalidProp);
+    let line = env.create_market().await;
+    env.create_prop_market(line, tenz, StatKind::Kills, 205).await.unwrap();
+    let result = env.add_top_player(line, 2, zekken).await;
+    assert_program_error(result, PredictionMarketError::InvalidProp);
+
+    let users = [env.create_user(100).await, env.create_user(300).await];
+    env.place_bet(&users[0], market, 0, 100).await.unwrap();
+    env.place_bet(&users[1], market, 1, 300).await.unwrap();
+    let end_time = env.account::<Market>(market).await.end_time;
+    env.warp_to(end_time).await;
+
+    // The oracle names the player, who must be the winning outcome's
+    let result = env.resolve_prop_market(market, 287).await;
+    assert_program_error(result, PredictionMarketError::InvalidProp);
+    let result = env.resolve_top_player_market(market, 1, 0, 287).await;
+    assert_program_error(result, PredictionMarketError::OutcomeNotFound);
+    env.resolve_top_player_market(market, 0, 0, 287).await.unwrap();
+    assert_eq!(env.account::<Market>(market).await.winner, Some(0));
+    assert_eq!(env.account::<Prop>(prop_address(market)).await.stat_value, Some(287));
+
+    // 2% fee on a pool of 400
+    env.claim_payout(&users[0], market, 0).await.unwrap();
+    assert_eq!(env.token_balance(users[0].token_account).await, 392);
+}
@@ -0,0 +1,210 @@
+//! Team ratings: Elo ratings moved by the head-to-head results of markets set
+//! to rate teams, and used to open new markets at fair odds instead of even.
+
+mod common;
+
+use common::*;
+use prediction_market::errors::PredictionMarketError;
+use prediction_market::{accounts, instruction};
+use prediction_market::state::{Market, MarketBook, Outcome, Team};
+use solana_sdk::pubkey::Pubkey;
+
+/// A market between `home` and `away`, backed by outcomes `0` and `1`.
+async fn head_to_head(env: &mut TestEnv, home: Pubkey, away: Pubkey) -> Pubkey {
+    let market = env.create_market().await;
+    env.add_outcome_for(market, 0, Some(home)).await.unwrap();
+    env.add_outcome_for(market, 1, Some(away)).await.unwrap();
+    market
+}
+
+#[test]
+fn expected_score_follows_the_elo_curve() {
+    assert_eq!(Team::expected_score_bps(1500, 1500), 5000);
+    assert_eq!(Team::expected_score_bps(1900, 1500), 9091);
+    assert_eq!(Team::expected_score_bps(1500, 1900), 909);
+
+    // Between table entries the score is interpolated
+    assert_eq!(Team::expected_score_bps(1512, 1500), 5172);
+    assert_eq!(Team::expected_score_bps(1500, 1512), 4828);
+
+    // Differences beyond 800 count as 800
+    assert_eq!(Team::expected_score_bps(2800, 1000), 9901);
+    assert_eq!(Team::expected_score_bps(0, u32::MAX), 99);
+}
+
+#[test]
+fn upsets_move_ratings_further() {
+    // Even teams trade half of K
+    assert_eq!(Team::rating_change(1500, 1500), 16);
+
+    // K * (1 - 0.9091) and K * (1 - 0.0909)
+    assert_eq!(Team::rating_change(1900, 1500), 3);
+    assert_eq!(Team::rating_change(1500, 1900), 29);
+
+    assert_eq!(Team::rating_change(2800, 1000), 0);
+    assert_eq!(Team::rating_change(1000, 2800), 32);
+}
+
+#[test]
+fn seeded_odds_are_fair() {
+    assert_eq!(Team::seeded_odds(1500, 1500), 20000);
+    assert_eq!(Team::seeded_odds(1900, 1500), 10999);
+    assert_eq!(Team::seeded_odds(1500, 1900), 110011);
+}
+
+#[tokio::test]
+async fn results_move_ratings() {
+    let mut env = TestEnv::new().await;
+    let sentinels = env.create_team("Sentinels").await.unwrap();
+    let cloud9 = env.create_team("Cloud9").await.unwrap();
+    assert_eq!(env.account::<Team>(sentinels).await.rating, Team::INITIAL_RATING);
+
+    let market = head_to_head(&mut env, sentinels, cloud9).await;
+    env.set_rates_teams(market, true).await.unwrap();
+    let users = [env.create_user(100).await, env.create_user(100).await];
+    env.place_bet(&users[0], market, 0, 100).await.unwrap();
+    env.place_bet(&users[1], market, 1, 100).await.unwrap();
+    env.end_and_resolve(market, 0).await;
+    assert_eq!(env.account::<Team>(sentinels).await.rating, 1516);
+    assert_eq!(env.account::<Team>(cloud9).await.rating, 1484);
+
+    // Leaving the teams out fails rather than skipping the update
+    let market = head_to_head(&mut env, sentinels, cloud9).await;
+    env.set_rates_teams(market, true).await.unwrap();
+    let user = env.create_user(100).await;
+    env.place_bet(&user, market, 1, 100).await.unwrap();
+    let end_time = env.account::<Market>(market).await.end_time;
+    env.warp_to(end_time).await;
+    let mut accounts = env.resolve_market_accounts(market, 1);
+    accounts.losing_outcome = Some(outcome_address(market, 0));
+    let mut ix = program_instruction(accounts, instruction::ResolveMarket { winning_outcome_id: 1 });
+    ix.accounts.extend(env.settlement_accounts(market, 1).await);
+    let admin = env.admin.insecure_clone();
+    let result = env.process(&[ix], &[&admin]).await;
+    assert_program_error(result, PredictionMarketError::InvalidTeam);
+
+    // The lower-rated team winning takes more
+    env.resolve_market(market, 1).await.unwrap();
+    assert_eq!(env.account::<Team>(sentinels).await.rating, 1499);
+    assert_eq!(env.account::<Team>(cloud9).await.rating, 1501);
+}
+
+#[tokio::test]
+async fn only_markets_set_to_rate_teams_move_ratings() {
+    let mut env = TestEnv::new().await;
+    let sentinels = env.create_team("Sentinels").await.unwrap();
+    let cloud9 = env.create_team("Cloud9").await.unwrap();
+
+    // Resolves without the teams, leaving their ratings alone
+    let market = head_to_head(&mut env, sentinels, cloud9).await;
+    let user = env.create_user(100).await;
+    env.place_bet(&user, market, 0, 100).await.unwrap();
+    let accounts = env.resolve_market_accounts(market, 0);
+    assert_eq!((accounts.losing_outcome, accounts.winning_team, accounts.losing_team), (None, None, None));
+    env.end_and_resolve(market, 0).await;
+    assert_eq!(env.account::<Team>(sentinels).await.rating, Team::INITIAL_RATING);
+    assert_eq!(env.account::<Team>(cloud9).await.rating, Team::INITIAL_RATING);
+
+    // A market rating teams needs both outcomes to back one
+    let market = env.create_market().await;
+    env.add_outcome_for(market, 0, Some(sentinels)).await.unwrap();
+    env.add_outcome(market, 1).await.unwrap();
+    env.set_rates_teams(market, true).await.unwrap();
+    let user = env.create_user(100).await;
+    env.place_bet(&user, market, 0, 100).await.unwrap();
+    let end_time = env.account::<Market>(market).await.end_time;
+    env.warp_to(end_time).await;
+    let result = env.resolve_market(market, 0).await;
+    assert_program_error(result, PredictionMarketError::InvalidTeam);
+
+    // Only the admin sets it, and only while the market is open
+    let accounts = accounts::SetRatesTeams { market, platform_config: platform_config(), admin: user.pubkey() };
+    let result = env.send(accounts, instruction::SetRatesTeams { rates_teams: false }, &[&user.keypair]).await;
+    assert_program_error(result, PredictionMarketError::UnauthorizedAdmin);
+    env.set_rates_teams(market, false).await.unwrap();
+    env.resolve_market(market, 0).await.unwrap();
+    let result = env.set_rates_teams(market, true).await;
+    assert_program_error(result, PredictionMarketError::MarketNotActive);
+}
+
+#[tokio::test]
+async fn new_markets_open_at_rated_odds() {
+    let mut env = TestEnv::new().await;
+    let sentinels = env.create_team("Sentinels").await.unwrap();
+    let cloud9 = env.create_team("Cloud9").await.unwrap();
+    let mut stored: Team = env.account(sentinels).await;
+    stored.rating = 1900;
+    env.set_account(sentinels, &stored).await;
+
+    // The second team outcome prices both, in the outcomes and the book
+    let market = env.create_market().await;
+    env.create_market_book(market).await.unwrap();
+    env.add_outcome_for(market, 0, Some(sentinels)).await.unwrap();
+    assert_eq!(env.account::<Outcome>(outcome_address(market, 0)).await.odds, 10000);
+    env.add_outcome_for(market, 1, Some(cloud9)).await.unwrap();
+
+    let home: Outcome = env.account(outcome_address(market, 0)).await;
+    let away: Outcome = env.account(outcome_address(market, 1)).await;
+    assert_eq!((home.odds, away.odds), (10999, 110011));
+    let data = env.account_data(market_book_address(market)).await;
+    let market_book: MarketBook = bytemuck::pod_read_unaligned(&data[8..MarketBook::SIZE]);
+    assert_eq!(market_book.prices[..2], [10999, 110011]);
+
+    // Ratings that move before the first bet can be priced in again
+    stored.rating = 1500;
+    env.set_account(sentinels, &stored).await;
+    env.seed_odds(market, sentinels, cloud9).await.unwrap();
+    assert_eq!(env.account::<Outcome>(outcome_address(market, 0)).await.odds, 20000);
+}
+
+#[tokio::test]
+async fn refreshing_keeps_seeded_odds() {
+    let mut env = TestEnv::new().await;
+    let sentinels = env.create_team("Sentinels").await.unwrap();
+    let cloud9 = env.create_team("Cloud9").await.unwrap();
+    let mut stored: Team = env.account(sentinels).await;
+    stored.rating = 1900;
+    env.set_account(sentinels, &stored).await;
+    let market = head_to_head(&mut env, sentinels, cloud9).await;
+
+    let ix = env.refresh_odds_instruction(market, &[0, 1]);
+    env.process(&[ix], &[]).await.unwrap();
+    let home: Outcome = env.account(outcome_address(market, 0)).await;
+    let away: Outcome = env.account(outcome_address(market, 1)).await;
+    assert_eq!((home.odds, away.odds), (10999, 110011));
+
+    // Only staked outcomes are priced by their stake
+    let user = env.create_user(100).await;
+    env.place_bet(&user, market, 0, 100).await.unwrap();
+    let ix = env.refresh_odds_instruction(market, &[0, 1]);
+    env.process(&[ix], &[]).await.unwrap();
+    let home: Outcome = env.account(outcome_address(market, 0)).await;
+    let away: Outcome = env.account(outcome_address(market, 1)).await;
+    assert_eq!((home.odds, away.odds), (9800, 110011));
+}
+
+#[tokio::test]
+async fn head_to_heads_need_the_opponent_to_seed() {
+    let mut env = TestEnv::new().await;
+    let sentinels = env.create_team("Sentinels").await.unwrap();
+    let cloud9 = env.create_team("Cloud9").await.unwrap();
+    let market = env.create_market().await;
+    env.add_outcome_for(market, 0, Some(sentinels)).await.unwrap();
+
+    let accounts = |env: &TestEnv, opponent_outcome| {
+        let mut accounts = env.add_outcome_accounts(market, 1);
+        accounts.team = Some(cloud9);
+        accounts.team_outcome = Some(team_outcome_address(outcome_address(market, 1)));
+        accounts.opponent_outcome = opponent_outcome;
+        accounts
+    };
+    let args = instruction::AddOutcome { name: "Cloud9 wins".to_string(), outcome_id: 1 };
+    let result = env.send_as_admin(accounts(&env, None), args).await;
+    assert_program_error(result, PredictionMarketError::InvalidTeam);
+
+    // Nor can the opponent's team be left out
+    let accounts = accounts(&env, Some(outcome_address(market, 0)));
+    let args = instruction::AddOutcome { name: "Cloud9 wins".to_string(), outcome_id: 1 };
+    let result = env.send_as_admin(accounts, args).await;
+    assert_program_error(result, PredictionMarketError::InvalidTeam);
+}
\ No newline at end of file
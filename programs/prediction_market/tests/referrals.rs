@@ -0,0 +1,100 @@
+//! Referral fee sharing: bets can name a referrer, who earns a share of the
+//! market fee proportional to the volume they referred.
+
+mod common;
+
+use common::*;
+use prediction_market::errors::PredictionMarketError;
+use prediction_market::state::{Market, PlatformConfig};
+
+#[tokio::test]
+async fn referrers_share_the_fee_on_their_volume() {
+    let mut env = TestEnv::new().await;
+    env.set_referral_share(2_500).await.unwrap();
+    let market = env.create_market_with_outcomes(2).await;
+
+    let carol = env.create_user(0).await;
+    let dave = env.create_user(0).await;
+    let alice = env.create_user(600).await;
+    let bob = env.create_user(400).await;
+    let erin = env.create_user(1_000).await;
+    env.place_referred_bet(&alice, market, 0, 600, carol.pubkey()).await.unwrap();
+    env.place_referred_bet(&bob, market, 1, 400, dave.pubkey()).await.unwrap();
+    env.place_bet(&erin, market, 0, 1_000).await.unwrap();
+
+    let stored: Market = env.account(market).await;
+    assert_eq!(stored.referral_share_bps, 2_500);
+    assert_eq!(stored.referred_volume, 1_000);
+    assert_eq!(stored.referrers_remaining, 2);
+
+    // Fee is 40; referred volume is half the pool, so referrers get a
+    // quarter of half of it
+    env.end_and_resolve(market, 0).await;
+    let stored: Market = env.account(market).await;
+    assert_eq!(stored.referral_fees, 5);
+    assert_eq!(env.token_balance(env.treasury_token_account).await, 35);
+
+    env.claim_referral_rewards(&carol, market).await.unwrap();
+    assert_eq!(env.token_balance(carol.token_account).await, 3);
+    assert!(!env.account_exists(referrer_address(market, carol.pubkey())).await);
+
+    // Winners are paid exactly as without referrals
+    env.claim_payout(&alice, market, 0).await.unwrap();
+    env.claim_payout(&erin, market, 0).await.unwrap();
+    assert_eq!(env.token_balance(alice.token_account).await, 735);
+    assert_eq!(env.token_balance(erin.token_account).await, 1_225);
+
+    // The last referrer takes the remainder and empties the escrow
+    env.claim_referral_rewards(&dave, market).await.unwrap();
+    assert_eq!(env.token_balance(dave.token_account).await, 2);
+    assert_eq!(env.token_balance(env.escrow_token_account(market, 0)).await, 0);
+}
+
+#[tokio::test]
+async fn repeat_referrals_accumulate() {
+    let mut env = TestEnv::new().await;
+    env.set_referral_share(10_000).await.unwrap();
+    let market = env.create_market_with_outcomes(2).await;
+
+    let carol = env.create_user(0).await;
+    let alice = env.create_user(1_000).await;
+    let bob = env.create_user(1_000).await;
+    env.place_referred_bet(&alice, market, 0, 500, carol.pubkey()).await.unwrap();
+    env.place_referred_bet(&bob, market, 1, 500, carol.pubkey()).await.unwrap();
+    env.place_referred_bet(&alice, market, 0, 500, carol.pubkey()).await.unwrap();
+
+    let stored: Market = env.account(market).await;
+    assert_eq!(stored.referrers_remaining, 1);
+
+    // Every bet was referred, so the whole fee is the referrer's
+    env.end_and_resolve(market, 0).await;
+    env.claim_referral_rewards(&carol, market).await.unwrap();
+    assert_eq!(env.token_balance(carol.token_account).await, 30);
+    assert_eq!(env.token_balance(env.treasury_token_account).await, 0);
+}
+
+#[tokio::test]
+async fn share_is_fixed_when_the_market_is_created() {
+    let mut env = TestEnv::new().await;
+    env.set_referral_share(1_000).await.unwrap();
+    let market = env.create_market().await;
+    env.set_referral_share(5_000).await.unwrap();
+
+    let config: PlatformConfig = env.platform_config().await;
+    assert_eq!(config.referral_share_bps, 5_000);
+    assert_eq!(env.account::<Market>(market).await.referral_share_bps, 1_000);
+}
+
+#[tokio::test]
+async fn referrers_need_a_resolved_market() {
+    let mut env = TestEnv::new().await;
+    env.set_referral_share(2_500).await.unwrap();
+    let market = env.create_market_with_outcomes(2).await;
+
+    let carol = env.create_user(0).await;
+    let alice = env.create_user(1_000).await;
+    env.place_referred_bet(&alice, market, 0, 1_000, carol.pubkey()).await.unwrap();
+
+    let result = env.claim_referral_rewards(&carol, market).await;
+    assert_program_error(result, PredictionMarketError::MarketNotResolved);
+}
@@ -0,0 +1,112 @@
+//! Team and tournament registries: markets created in a tournament and
+//! outcomes backing a team, so every market for either can be found on
+//! chain.
+
+mod common;
+
+use anchor_lang::Discriminator;
+use common::*;
+use prediction_market::state::{
+    Market, Outcome, Region, Team, TeamOutcome, Tournament, TournamentMarket, TournamentTier,
+};
+use solana_rpc_client_types::filter::{Memcmp, RpcFilterType};
+use solana_sdk::pubkey::Pubkey;
+
+#[tokio::test]
+async fn outcomes_back_registered_teams() {
+    let mut env = TestEnv::new().await;
+    let sentinels = env.create_team_with("Sentinels", "SEN", "https://example.com/sen.png").await.unwrap();
+    let cloud9 = env.create_team("Cloud9").await.unwrap();
+
+    let stored: Team = env.account(sentinels).await;
+    assert_eq!((stored.id, stored.name.as_str(), stored.tag.as_str()), (0, "Sentinels", "SEN"));
+    assert_eq!(stored.region, Region::Americas);
+    assert_eq!(stored.logo_uri, "https://example.com/sen.png");
+    assert_eq!(env.account::<Team>(cloud9).await.tag, "CLO");
+
+    let market = env.create_market().await;
+    env.add_outcome_for(market, 0, Some(sentinels)).await.unwrap();
+    env.add_outcome_for(market, 1, Some(cloud9)).await.unwrap();
+    env.add_outcome(market, 2).await.unwrap();
+
+    let mut teams = Vec::new();
+    for outcome_id in 0..3 {
+        teams.push(env.account::<Outcome>(outcome_address(market, outcome_id)).await.team);
+    }
+    assert_eq!(teams, vec![Some(0), Some(1), None]);
+}
+
+#[tokio::test]
+async fn markets_join_their_tournament_at_creation() {
+    let mut env = TestEnv::new().await;
+    let outright = env.create_market().await;
+    let tournament = env.create_tournament(outright).await.unwrap();
+
+    let stored: Tournament = env.account(tournament).await;
+    assert_eq!(stored.name, "Valorant Champions 2025");
+    assert_eq!((stored.tier, stored.region), (TournamentTier::International, Region::International));
+
+    let semi_final = env.create_tournament_match(tournament).await.unwrap();
+    let grand_final = env.create_tournament_match(tournament).await.unwrap();
+    assert_eq!(env.account::<Market>(semi_final).await.tournament, Some(stored.id));
+
+    // A match linked at creation is passed again to make it the grand final
+    env.add_tournament_match(tournament, grand_final, true).await.unwrap();
+    let stored: Tournament = env.account(tournament).await;
+    assert_eq!(stored.matches, vec![semi_final, grand_final]);
+    assert_eq!(stored.grand_final, Some(grand_final));
+}
+
+/// The filters a client passes to `getProgramAccounts` to find every index
+/// of type `T` holding `id` at `offset`
+fn index_filters<T: Discriminator>(size: usize, offset: usize, id: u64) -> Vec<RpcFilterType> {
+    vec![
+        RpcFilterType::DataSize(size as u64),
+        RpcFilterType::Memcmp(Memcmp::new_raw_bytes(0, T::DISCRIMINATOR.to_vec())),
+        RpcFilterType::Memcmp(Memcmp::new_raw_bytes(offset, id.to_le_bytes().to_vec())),
+    ]
+}
+
+#[tokio::test]
+async fn indexes_find_markets_by_team_and_tournament() {
+    let mut env = TestEnv::new().await;
+    let sentinels = env.create_team("Sentinels").await.unwrap();
+    let cloud9 = env.create_team("Cloud9").await.unwrap();
+    let outright = env.create_market().await;
+    let tournament = env.create_tournament(outright).await.unwrap();
+
+    // A match created in the tournament, one linked later and one outside it
+    let first = env.create_tournament_match(tournament).await.unwrap();
+    env.add_outcome_for(first, 0, Some(sentinels)).await.unwrap();
+    env.add_outcome_for(first, 1, Some(cloud9)).await.unwrap();
+    let second = env.create_market().await;
+    env.add_outcome_for(second, 0, Some(cloud9)).await.unwrap();
+    env.add_outcome(second, 1).await.unwrap();
+    env.add_tournament_match(tournament, second, false).await.unwrap();
+    let third = env.create_market().await;
+    env.add_outcome_for(third, 0, Some(sentinels)).await.unwrap();
+
+    let markets = [outright, first, second, third];
+    let mut candidates: Vec<Pubkey> = vec![sentinels, cloud9, tournament];
+    for market in markets {
+        candidates.extend([market, tournament_market_address(market)]);
+        for outcome_id in 0..2 {
+            let outcome = outcome_address(market, outcome_id);
+            candidates.extend([outcome, team_outcome_address(outcome)]);
+        }
+    }
+
+    let filters = index_filters::<TeamOutcome>(TeamOutcome::SIZE, TeamOutcome::TEAM_OFFSET, 1);
+    let mut found = Vec::new();
+    for index in env.program_accounts(&candidates, &filters).await {
+        found.push(env.account::<TeamOutcome>(index).await.market);
+    }
+    assert_eq!(found, vec![first, second]);
+
+    let filters = index_filters::<TournamentMarket>(TournamentMarket::SIZE, TournamentMarket::TOURNAMENT_OFFSET, 0);
+    let mut found = Vec::new();
+    for index in env.program_accounts(&candidates, &filters).await {
+        found.push(env.account::<TournamentMarket>(index).await.market);
+    }
+    assert_eq!(found, vec![outright, first, second]);
+}
\ No newline at end of file
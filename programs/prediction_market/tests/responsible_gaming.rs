@@ -0,0 +1,122 @@
+//! Responsible gaming: self-exclusion and deposit limits on the user's
+//! profile apply to bets in every market, and loosening them waits out a
+//! cooldown.
+
+mod common;
+
+use common::*;
+use prediction_market::errors::PredictionMarketError;
+use prediction_market::state::UserProfile;
+use solana_sdk::pubkey::Pubkey;
+
+const DAY: i64 = 24 * 60 * 60;
+const WEEK: i64 = 7 * DAY;
+
+/// Creates a market with two outcomes that stays open for `days` days.
+async fn long_market(env: &mut TestEnv, days: i64) -> Pubkey {
+    let now = env.now().await;
+    let market = env
+        .create_market_with_times(now + MARKET_START_OFFSET, now + days * DAY)
+        .await
+        .unwrap();
+    for outcome_id in 0..2 {
+        env.add_outcome(market, outcome_id).await.unwrap();
+    }
+    market
+}
+
+/// Warps to the start of next week, Monday 00:00 UTC, so a test's days fall
+/// in a known week.
+async fn warp_to_next_monday(env: &mut TestEnv) -> i64 {
+    let now = env.now().await;
+    let monday = ((now + 3 * DAY).div_euclid(WEEK) + 1) * WEEK - 3 * DAY;
+    env.warp_to(monday).await;
+    monday
+}
+
+#[tokio::test]
+async fn self_exclusion_blocks_bets_in_every_market() {
+    let mut env = TestEnv::new().await;
+    let first = long_market(&mut env, 30).await;
+    let second = long_market(&mut env, 30).await;
+    let user = env.create_user(1_000).await;
+
+    let now = env.now().await;
+    env.set_self_exclusion(&user, now + 2 * DAY).await.unwrap();
+    for market in [first, second] {
+        let result = env.place_bet(&user, market, 0, 100).await;
+        assert_program_error(result, PredictionMarketError::SelfExcluded);
+    }
+
+    env.warp_to(now + 2 * DAY).await;
+    env.place_bet(&user, first, 0, 100).await.unwrap();
+    env.place_bet(&user, second, 1, 100).await.unwrap();
+}
+
+#[tokio::test]
+async fn exclusion_extends_at_once_but_shortens_after_cooldown() {
+    let mut env = TestEnv::new().await;
+    let user = env.create_user(1_000).await;
+
+    let now = env.now().await;
+    env.set_self_exclusion(&user, now + 30 * DAY).await.unwrap();
+    env.set_self_exclusion(&user, now + 60 * DAY).await.unwrap();
+
+    let result = env.set_self_exclusion(&user, now + 10 * DAY).await;
+    assert_program_error(result, PredictionMarketError::ProfileCooldownActive);
+
+    env.warp_to(now + UserProfile::COOLDOWN).await;
+    env.set_self_exclusion(&user, 0).await.unwrap();
+
+    let market = long_market(&mut env, 1).await;
+    env.place_bet(&user, market, 0, 100).await.unwrap();
+}
+
+#[tokio::test]
+async fn deposit_limits_aggregate_across_markets() {
+    let mut env = TestEnv::new().await;
+    let monday = warp_to_next_monday(&mut env).await;
+    let first = long_market(&mut env, 30).await;
+    let second = long_market(&mut env, 30).await;
+    let user = env.create_user(1_000).await;
+    env.set_deposit_limits(&user, 100, 150).await.unwrap();
+
+    env.place_bet(&user, first, 0, 60).await.unwrap();
+    env.place_bet(&user, second, 1, 40).await.unwrap();
+    let result = env.place_bet(&user, first, 1, 1).await;
+    assert_program_error(result, PredictionMarketError::DailyDepositLimitExceeded);
+
+    // A new day resets the daily total but not the weekly one
+    env.warp_to(monday + DAY).await;
+    env.place_bet(&user, second, 0, 50).await.unwrap();
+    let result = env.place_bet(&user, first, 0, 1).await;
+    assert_program_error(result, PredictionMarketError::WeeklyDepositLimitExceeded);
+
+    env.warp_to(monday + WEEK).await;
+    env.place_bet(&user, first, 0, 100).await.unwrap();
+
+    let profile: UserProfile = env.account(user_profile_address(user.pubkey())).await;
+    assert_eq!(profile.deposited_today, 100);
+    assert_eq!(profile.deposited_this_week, 100);
+}
+
+#[tokio::test]
+async fn limits_tighten_at_once_but_loosen_after_cooldown() {
+    let mut env = TestEnv::new().await;
+    let user = env.create_user(1_000).await;
+
+    env.set_deposit_limits(&user, 100, 500).await.unwrap();
+    env.set_deposit_limits(&user, 50, 500).await.unwrap();
+
+    let result = env.set_deposit_limits(&user, 200, 500).await;
+    assert_program_error(result, PredictionMarketError::ProfileCooldownActive);
+    let result = env.set_deposit_limits(&user, 50, 0).await;
+    assert_program_error(result, PredictionMarketError::ProfileCooldownActive);
+
+    let now = env.now().await;
+    env.warp_to(now + UserProfile::COOLDOWN).await;
+    env.set_deposit_limits(&user, 0, 0).await.unwrap();
+
+    let market = long_market(&mut env, 1).await;
+    env.place_bet(&user, market, 0, 1_000).await.unwrap();
+}
@@ -0,0 +1,89 @@
+//! Payout rounding: proportional payouts round down and the last claimer on
+//! the winning outcome receives the exact remainder, so no dust is left in
+//! the escrow.
+
+mod common;
+
+use common::*;
+use prediction_market::state::{Market, Outcome};
+
+/// Stakes chosen so that nearly every proportional payout truncates
+const WINNING_STAKES: [u64; 9] = [1, 2, 3, 5, 7, 11, 13, 17, 19];
+const LOSING_STAKE: u64 = 29;
+
+async fn settle_small_positions(claim_order: &[usize]) {
+    let mut env = TestEnv::new().await;
+    let market_address = env.create_market_with_outcomes(2).await;
+
+    let mut winners = Vec::new();
+    for stake in WINNING_STAKES {
+        let user = env.create_user(stake).await;
+        env.place_bet(&user, market_address, 0, stake).await.unwrap();
+        winners.push(user);
+    }
+    let loser = env.create_user(LOSING_STAKE).await;
+    env.place_bet(&loser, market_address, 1, LOSING_STAKE).await.unwrap();
+    env.end_and_resolve(market_address, 0).await;
+
+    let market: Market = env.account(market_address).await;
+    let winning_stake: u64 = WINNING_STAKES.iter().sum();
+    let distributable = market.distributable_pool(winning_stake).unwrap();
+
+    let mut paid = 0;
+    for (claimed, &index) in claim_order.iter().enumerate() {
+        let user = &winners[index];
+        env.claim_payout(user, market_address, 0).await.unwrap();
+        let received = env.token_balance(user.token_account).await;
+
+        if claimed + 1 < claim_order.len() {
+            let proportional = market.payout(WINNING_STAKES[index], winning_stake).unwrap();
+            assert_eq!(received, proportional);
+        } else {
+            assert_eq!(received, distributable - paid);
+        }
+        paid += received;
+    }
+
+    assert_eq!(paid, distributable);
+    let winning_escrow = escrow_token_address(market_address, 0, &env.mint);
+    assert_eq!(env.token_balance(winning_escrow).await, 0);
+
+    let outcome: Outcome = env.account(outcome_address(market_address, 0)).await;
+    assert_eq!(outcome.total_claimed, distributable);
+    assert_eq!(outcome.claimants_remaining, 0);
+}
+
+#[tokio::test]
+async fn last_claimer_takes_remainder() {
+    settle_small_positions(&[0, 1, 2, 3, 4, 5, 6, 7, 8]).await;
+}
+
+#[tokio::test]
+async fn remainder_goes_to_whoever_claims_last() {
+    settle_small_positions(&[8, 6, 4, 2, 0, 1, 3, 5, 7]).await;
+}
+
+#[tokio::test]
+async fn refunds_empty_escrow() {
+    let mut env = TestEnv::new().await;
+    let market_address = env.create_market_with_outcomes(2).await;
+
+    let mut users = Vec::new();
+    for stake in WINNING_STAKES {
+        let user = env.create_user(stake).await;
+        env.place_bet(&user, market_address, 0, stake).await.unwrap();
+        users.push((user, stake));
+    }
+    env.cancel_market(market_address).await.unwrap();
+
+    for (user, stake) in &users {
+        env.claim_refund(user, market_address, 0).await.unwrap();
+        assert_eq!(env.token_balance(user.token_account).await, *stake);
+    }
+
+    let escrow = escrow_token_address(market_address, 0, &env.mint);
+    assert_eq!(env.token_balance(escrow).await, 0);
+    let outcome: Outcome = env.account(outcome_address(market_address, 0)).await;
+    assert_eq!(outcome.total_claimed, WINNING_STAKES.iter().sum::<u64>());
+    assert_eq!(outcome.claimants_remaining, 0);
+}
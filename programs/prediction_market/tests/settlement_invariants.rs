@@ -0,0 +1,374 @@
+//! Property-based checks that settlement conserves tokens.
+//!
+//! `random_lifecycles_conserve_tokens` drives the program with random
+//! sequences of market operations, predicting every instruction's result from
+//! a shadow model of the markets, then settles everything and checks that
+//! escrows end empty and treasury and users account for every token
+//! deposited.
+//!
+//! Each lifecycle case runs a full program test, so the case count defaults
+//! to `DEFAULT_LIFECYCLE_CASES`; set `PROPTEST_CASES` to run more, e.g. in a
+//! nightly job.
+
+mod common;
+
+use std::collections::{HashMap, HashSet};
+
+use common::*;
+use prediction_market::state::{FeeModel, Market, PlatformConfig};
+use proptest::prelude::*;
+use solana_program_test::BanksClientError;
+use solana_sdk::instruction::InstructionError;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::transaction::TransactionError;
+
+const USERS: usize = 3;
+const MAX_OUTCOMES: u8 = 4;
+const STARTING_BALANCE: u64 = 1_000_000;
+const FEE_BPS: u64 = 200;
+const DEFAULT_LIFECYCLE_CASES: u32 = 64;
+
+fn lifecycle_cases() -> u32 {
+    std::env::var("PROPTEST_CASES")
+        .ok()
+        .and_then(|cases| cases.parse().ok())
+        .unwrap_or(DEFAULT_LIFECYCLE_CASES)
+}
+
+#[derive(Debug, Clone)]
+enum Op {
+    CreateMarket,
+    AddOutcome { market: usize },
+    PlaceBet { user: usize, market: usize, outcome: u8, amount: u64 },
+    Resolve { market: usize, outcome: u8 },
+    Cancel { market: usize },
+    Claim { user: usize, market: usize, outcome: u8 },
+}
+
+fn op_strategy() -> impl Strategy<Value = Op> {
+    prop_oneof![
+        1 => Just(Op::CreateMarket),
+        1 => any::<usize>().prop_map(|market| Op::AddOutcome { market }),
+        6 => (0..USERS, any::<usize>(), 0..MAX_OUTCOMES + 1, 0u64..50_000).prop_map(
+            |(user, market, outcome, amount)| Op::PlaceBet { user, market, outcome, amount }
+        ),
+        1 => (any::<usize>(), 0..MAX_OUTCOMES).prop_map(|(market, outcome)| Op::Resolve { market, outcome }),
+        1 => any::<usize>().prop_map(|market| Op::Cancel { market }),
+        2 => (0..USERS, any::<usize>(), 0..MAX_OUTCOMES).prop_map(
+            |(user, market, outcome)| Op::Claim { user, market, outcome }
+        ),
+    ]
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Status {
+    Active,
+    Resolved(u8),
+    Cancelled,
+}
+
+/// What the program should hold for one market.
+struct ModelMarket {
+    address: Pubkey,
+    end_time: i64,
+    outcomes: u8,
+    status: Status,
+    stakes: HashMap<(usize, u8), u64>,
+    claimed: HashSet<(usize, u8)>,
+    paid_out: u64,
+}
+
+impl ModelMarket {
+    fn total_pool(&self) -> u64 {
+        self.stakes.values().sum()
+    }
+
+    fn outcome_stake(&self, outcome: u8) -> u64 {
+        self.stakes
+            .iter()
+            .filter(|((_, o), _)| *o == outcome)
+            .map(|(_, amount)| amount)
+            .sum()
+    }
+
+    fn fee(&self) -> u64 {
+        self.total_pool() * FEE_BPS / 10_000
+    }
+
+    /// Independent restatement of the payout rule
+    fn expected_claim(&self, user: usize, outcome: u8) -> Option<u64> {
+        let amount = *self.stakes.get(&(user, outcome))?;
+        if self.claimed.contains(&(user, outcome)) {
+            return None;
+        }
+        match self.status {
+            Status::Active => None,
+            Status::Cancelled => Some(amount),
+            Status::Resolved(winner) if winner == outcome => {
+                let distributable = self.total_pool() - self.fee();
+                let unclaimed = self
+                    .stakes
+                    .keys()
+                    .filter(|position| position.1 == winner && !self.claimed.contains(position))
+                    .count();
+                if unclaimed == 1 {
+                    // The last winner sweeps whatever rounding left behind
+                    return Some(distributable - self.paid_out);
+                }
+                Some((amount as u128 * distributable as u128 / self.outcome_stake(winner) as u128) as u64)
+            }
+            Status::Resolved(_) => None,
+        }
+    }
+}
+
+struct Model {
+    now: i64,
+    markets: Vec<ModelMarket>,
+    balances: [u64; USERS],
+    fees: u64,
+}
+
+/// Rejections must be clean program errors, never panics or aborted programs.
+fn assert_rejected(result: Result<(), BanksClientError>, op: &Op) {
+    match result {
+        Ok(()) => panic!("{:?} should have been rejected", op),
+        Err(err) => match err.unwrap() {
+            TransactionError::InstructionError(_, InstructionError::Custom(_)) => {}
+            other => panic!("{:?} failed uncleanly: {:?}", op, other),
+        },
+    }
+}
+
+fn assert_outcome(result: Result<(), BanksClientError>, expect_ok: bool, op: &Op) {
+    if expect_ok {
+        if let Err(err) = result {
+            panic!("{:?} should have succeeded: {:?}", op, err);
+        }
+    } else {
+        assert_rejected(result, op);
+    }
+}
+
+async fn run(ops: Vec<Op>) {
+    let mut env = TestEnv::new().await;
+    let mut users = Vec::with_capacity(USERS);
+    for _ in 0..USERS {
+        users.push(env.create_user(STARTING_BALANCE).await);
+    }
+
+    let mut model = Model {
+        now: env.now().await,
+        markets: Vec::new(),
+        balances: [STARTING_BALANCE; USERS],
+        fees: 0,
+    };
+
+    for op in &ops {
+        apply(&mut env, &users, &mut model, op).await;
+    }
+
+    // Settle whatever is still open: resolve where someone backed outcome 0,
+    // otherwise cancel, then let everyone claim what they are owed
+    for index in 0..model.markets.len() {
+        if model.markets[index].status != Status::Active {
+            continue;
+        }
+        let op = if model.markets[index].outcome_stake(0) > 0 {
+            Op::Resolve { market: index, outcome: 0 }
+        } else {
+            Op::Cancel { market: index }
+        };
+        apply(&mut env, &users, &mut model, &op).await;
+    }
+    for index in 0..model.markets.len() {
+        let positions: Vec<_> = model.markets[index].stakes.keys().copied().collect();
+        for (user, outcome) in positions {
+            let op = Op::Claim { user, market: index, outcome };
+            apply(&mut env, &users, &mut model, &op).await;
+        }
+    }
+
+    // Users hold exactly what the model paid them
+    let mut user_total = 0;
+    for (user, expected) in users.iter().zip(model.balances) {
+        let balance = env.token_balance(user.token_account).await;
+        assert_eq!(balance, expected);
+        user_total += balance;
+    }
+
+    // The treasury holds exactly the fees of resolved markets
+    let treasury = env.treasury_token_account;
+    let treasury_balance = env.token_balance(treasury).await;
+    assert_eq!(treasury_balance, model.fees);
+
+    // Every escrow is emptied, rounding dust included
+    for market in &model.markets {
+        for outcome in 0..market.outcomes {
+            let balance = env
+                .token_balance(escrow_token_address(market.address, outcome, &env.mint))
+                .await;
+            assert_eq!(balance, 0, "escrow for outcome {} not emptied", outcome);
+        }
+    }
+
+    assert_eq!(
+        user_total + treasury_balance,
+        STARTING_BALANCE * USERS as u64,
+        "tokens were created or lost"
+    );
+}
+
+async fn apply(env: &mut TestEnv, users: &[User], model: &mut Model, op: &Op) {
+    let market_index = |market: usize| (!model.markets.is_empty()).then(|| market % model.markets.len());
+
+    match *op {
+        Op::CreateMarket => {
+            let address = env.create_market().await;
+            let end_time = env.account::<Market>(address).await.end_time;
+            model.markets.push(ModelMarket {
+                address,
+                end_time,
+                outcomes: 0,
+                status: Status::Active,
+                stakes: HashMap::new(),
+                claimed: HashSet::new(),
+                paid_out: 0,
+            });
+        }
+        Op::AddOutcome { market } => {
+            let Some(index) = market_index(market) else { return };
+            let market = &mut model.markets[index];
+            if market.outcomes == MAX_OUTCOMES {
+                return;
+            }
+            let result = env.add_outcome(market.address, market.outcomes).await;
+            let expect_ok = market.status == Status::Active && market.total_pool() == 0;
+            assert_outcome(result, expect_ok, op);
+            if expect_ok {
+                market.outcomes += 1;
+            }
+        }
+        Op::PlaceBet { user, market, outcome, amount } => {
+            let Some(index) = market_index(market) else { return };
+            let market = &mut model.markets[index];
+            let result = env.place_bet(&users[user], market.address, outcome, amount).await;
+            let expect_ok = market.status == Status::Active
+                && model.now < market.end_time
+                && outcome < market.outcomes
+                && amount > 0
+                && amount <= model.balances[user];
+            assert_outcome(result, expect_ok, op);
+            if expect_ok {
+                *market.stakes.entry((user, outcome)).or_default() += amount;
+                model.balances[user] -= amount;
+            }
+        }
+        Op::Resolve { market, outcome } => {
+            let Some(index) = market_index(market) else { return };
+            let market = &mut model.markets[index];
+            if outcome >= market.outcomes {
+                return;
+            }
+            if model.now < market.end_time {
+                model.now = market.end_time;
+                env.warp_to(model.now).await;
+            }
+            let result = env.resolve_market(market.address, outcome).await;
+            let expect_ok = market.status == Status::Active && market.outcome_stake(outcome) > 0;
+            assert_outcome(result, expect_ok, op);
+            if expect_ok {
+                market.status = Status::Resolved(outcome);
+                model.fees += market.fee();
+            }
+        }
+        Op::Cancel { market } => {
+            let Some(index) = market_index(market) else { return };
+            let market = &mut model.markets[index];
+            let result = env.cancel_market(market.address).await;
+            let expect_ok = market.status == Status::Active;
+            assert_outcome(result, expect_ok, op);
+            if expect_ok {
+                market.status = Status::Cancelled;
+            }
+        }
+        Op::Claim { user, market, outcome } => {
+            let Some(index) = market_index(market) else { return };
+            let market = &mut model.markets[index];
+            if !market.stakes.contains_key(&(user, outcome)) {
+                return;
+            }
+            let result = if market.status == Status::Cancelled {
+                env.claim_refund(&users[user], market.address, outcome).await
+            } else {
+                env.claim_payout(&users[user], market.address, outcome).await
+            };
+            let expected = market.expected_claim(user, outcome);
+            assert_outcome(result, expected.is_some(), op);
+            if let Some(amount) = expected {
+                market.claimed.insert((user, outcome));
+                market.paid_out += amount;
+                model.balances[user] += amount;
+            }
+        }
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig { cases: lifecycle_cases(), ..ProptestConfig::default() })]
+
+    #[test]
+    fn random_lifecycles_conserve_tokens(ops in prop::collection::vec(op_strategy(), 1..40)) {
+        // Every sequence starts with a market with two outcomes to act on
+        let mut sequence = vec![
+            Op::CreateMarket,
+            Op::AddOutcome { market: 0 },
+            Op::AddOutcome { market: 0 },
+        ];
+        sequence.extend(ops);
+
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap()
+            .block_on(run(sequence));
+    }
+}
+
+proptest! {
+    /// Winners can never be paid more than the pool minus the fee, however
+    /// the winning stake is split.
+    #[test]
+    fn payouts_never_exceed_distributable_pool(
+        losing_stake in 0u64..=u64::MAX / 4,
+        winning_stakes in prop::collection::vec(1u64..=u64::MAX / 1024, 1..20),
+        fee_bps in 0u16..=PlatformConfig::MAX_FEE_BPS,
+        fee_model in prop_oneof![
+            Just(FeeModel::TotalPool),
+            Just(FeeModel::NetWinnings),
+            Just(FeeModel::AtBet),
+        ],
+    ) {
+        let winning_total: u64 = winning_stakes.iter().sum();
+        let market = Market {
+            total_pool: winning_total + losing_stake,
+            fee_bps,
+            fee_model,
+            ..Market::default()
+        };
+
+        let fee = market.fee_amount(winning_total).unwrap();
+        let distributable = market.distributable_pool(winning_total).unwrap();
+        prop_assert_eq!(fee + distributable, market.total_pool);
+
+        let mut paid: u128 = 0;
+        for stake in &winning_stakes {
+            let payout = market.payout(*stake, winning_total).unwrap();
+            prop_assert!(payout <= distributable);
+            paid += payout as u128;
+        }
+        prop_assert!(paid <= distributable as u128);
+        // Truncation loses strictly less than one unit per winner
+        prop_assert!(distributable as u128 - paid < winning_stakes.len() as u128);
+    }
+}
@@ -0,0 +1,98 @@
+//! Token-2022 collateral: markets work through the token interface, credit
+//! only what actually reaches the escrow for transfer-fee mints, and refuse
+//! mints whose extensions would make escrows unsafe.
+
+mod common;
+
+use common::*;
+use prediction_market::errors::PredictionMarketError;
+use prediction_market::state::{Market, Outcome, UserPosition};
+
+/// 1% transfer fee, rounded up by the token program
+const TRANSFER_FEE: MintExtension = MintExtension::TransferFee {
+    basis_points: 100,
+    maximum_fee: u64::MAX,
+};
+
+async fn use_token_2022_collateral(env: &mut TestEnv, extensions: &[MintExtension]) {
+    let mint = env.create_token_2022_mint(extensions).await;
+    env.add_collateral_mint(mint).await.unwrap();
+    env.use_collateral(mint).await;
+}
+
+#[tokio::test]
+async fn token_2022_market_lifecycle() {
+    let mut env = TestEnv::new().await;
+    use_token_2022_collateral(&mut env, &[]).await;
+    let market = env.create_market_with_outcomes(2).await;
+
+    let alice = env.create_user(1_000).await;
+    let bob = env.create_user(1_000).await;
+    env.place_bet(&alice, market, 0, 300).await.unwrap();
+    env.place_bet(&bob, market, 1, 100).await.unwrap();
+    env.end_and_resolve(market, 0).await;
+
+    assert_eq!(env.token_balance(env.treasury_token_account).await, 8);
+    env.claim_payout(&alice, market, 0).await.unwrap();
+    assert_eq!(env.token_balance(alice.token_account).await, 700 + 392);
+    assert_eq!(env.token_balance(env.escrow_token_account(market, 0)).await, 0);
+}
+
+#[tokio::test]
+async fn transfer_fee_credits_received_amount() {
+    let mut env = TestEnv::new().await;
+    use_token_2022_collateral(&mut env, &[TRANSFER_FEE]).await;
+    let market = env.create_market_with_outcomes(2).await;
+
+    let alice = env.create_user(10_000).await;
+    let bob = env.create_user(10_000).await;
+    env.place_bet(&alice, market, 0, 1_000).await.unwrap();
+    env.place_bet(&bob, market, 1, 1_000).await.unwrap();
+
+    // Each bet loses 10 to the transfer fee on its way into the escrow
+    let position: UserPosition = env
+        .account(user_position_address(alice.pubkey(), market, 0))
+        .await;
+    assert_eq!(position.amount, 990);
+    assert_eq!(env.account::<Outcome>(outcome_address(market, 0)).await.total_staked, 990);
+    assert_eq!(env.account::<Market>(market).await.total_pool, 1_980);
+    assert_eq!(env.token_balance(env.escrow_token_account(market, 0)).await, 990);
+
+    // Sweeping bob's 990 costs another 10, so the pool settles at 1,970
+    env.end_and_resolve(market, 0).await;
+    assert_eq!(env.account::<Market>(market).await.total_pool, 1_970);
+
+    // The 39 fee loses 1 on its way to the treasury
+    assert_eq!(env.token_balance(env.treasury_token_account).await, 38);
+    assert_eq!(env.token_balance(env.escrow_token_account(market, 0)).await, 1_931);
+
+    // Alice is the last claimer and receives the whole remainder, less 20
+    env.claim_payout(&alice, market, 0).await.unwrap();
+    assert_eq!(env.token_balance(alice.token_account).await, 9_000 + 1_911);
+    assert_eq!(env.token_balance(env.escrow_token_account(market, 0)).await, 0);
+}
+
+#[tokio::test]
+async fn transfer_fee_refunds_what_was_credited() {
+    let mut env = TestEnv::new().await;
+    use_token_2022_collateral(&mut env, &[TRANSFER_FEE]).await;
+    let market = env.create_market_with_outcomes(2).await;
+
+    let alice = env.create_user(10_000).await;
+    env.place_bet(&alice, market, 0, 1_000).await.unwrap();
+    env.cancel_market(market).await.unwrap();
+
+    // The 990 credited comes back, less 10 on the way out
+    env.claim_refund(&alice, market, 0).await.unwrap();
+    assert_eq!(env.token_balance(alice.token_account).await, 9_000 + 980);
+    assert_eq!(env.token_balance(env.escrow_token_account(market, 0)).await, 0);
+}
+
+#[tokio::test]
+async fn non_transferable_mints_are_rejected() {
+    let mut env = TestEnv::new().await;
+    let mint = env.create_token_2022_mint(&[MintExtension::NonTransferable]).await;
+
+    let result = env.add_collateral_mint(mint).await;
+    assert_program_error(result, PredictionMarketError::UnsupportedMintExtension);
+}
@@ -0,0 +1,128 @@
+//! Tournament outrights: a market on the tournament winner that stays open
+//! across its matches, loses outcomes as teams are knocked out and is
+//! resolved once the grand final is.
+
+mod common;
+
+use common::*;
+use prediction_market::errors::PredictionMarketError;
+use prediction_market::state::{Market, MarketBook, Outcome, Tournament};
+use solana_sdk::pubkey::Pubkey;
+
+/// An outright with an outcome backing each of `teams`, open for ten times
+/// as long as a match.
+async fn create_outright(env: &mut TestEnv, teams: &[Pubkey]) -> Pubkey {
+    let now = env.now().await;
+    let market = env
+        .create_market_with_times(now + MARKET_START_OFFSET, now + 10 * MARKET_END_OFFSET)
+        .await
+        .unwrap();
+    for (outcome_id, team) in teams.iter().enumerate() {
+        env.add_outcome_for(market, outcome_id as u8, Some(*team)).await.unwrap();
+    }
+    market
+}
+
+#[tokio::test]
+async fn outright_follows_the_bracket() {
+    let mut env = TestEnv::new().await;
+    let teams = env.create_teams(4).await;
+    let outright = create_outright(&mut env, &teams).await;
+    let tournament = env.create_tournament(outright).await.unwrap();
+    let semi_final = env.create_market_with_teams(&[teams[1], teams[3]]).await;
+    let grand_final = env.create_market_with_teams(&[teams[0], teams[1]]).await;
+    env.add_tournament_match(tournament, semi_final, false).await.unwrap();
+    env.add_tournament_match(tournament, grand_final, true).await.unwrap();
+
+    let stored: Tournament = env.account(tournament).await;
+    assert_eq!(stored.outright_market, outright);
+    assert_eq!(stored.matches, vec![semi_final, grand_final]);
+    assert_eq!(stored.grand_final, Some(grand_final));
+    assert_eq!(env.account::<Market>(grand_final).await.tournament, Some(stored.id));
+
+    let users = [
+        env.create_user(400).await,
+        env.create_user(300).await,
+        env.create_user(200).await,
+        env.create_user(100).await,
+    ];
+    for (outcome_id, user) in users.iter().enumerate() {
+        let amount = 400 - 100 * outcome_id as u64;
+        env.place_bet(user, outright, outcome_id as u8, amount).await.unwrap();
+    }
+
+    // Team 3 drops out in the semi-final and its odds go to zero at once
+    env.eliminate_outcome(tournament, 3).await.unwrap();
+    let outcome: Outcome = env.account(outcome_address(outright, 3)).await;
+    assert!(outcome.eliminated);
+    assert_eq!(outcome.odds, 0);
+    assert_eq!(env.account::<Tournament>(tournament).await.eliminated_count, 1);
+
+    let late = env.create_user(100).await;
+    let result = env.place_bet(&late, outright, 3, 100).await;
+    assert_program_error(result, PredictionMarketError::OutcomeEliminated);
+    env.place_bet(&late, outright, 0, 100).await.unwrap();
+
+    // The outright waits for the grand final, then resolves before its own end
+    let result = env.resolve_market(outright, 0).await;
+    assert_program_error(result, PredictionMarketError::GrandFinalNotResolved);
+
+    let finalist = env.create_user(100).await;
+    env.place_bet(&finalist, grand_final, 0, 100).await.unwrap();
+    env.end_and_resolve(grand_final, 0).await;
+    assert!(env.now().await < env.account::<Market>(outright).await.end_time);
+
+    // Only to the team that won it, whichever outcome is still standing
+    let result = env.resolve_market(outright, 1).await;
+    assert_program_error(result, PredictionMarketError::NotGrandFinalWinner);
+    env.resolve_market(outright, 0).await.unwrap();
+
+    // 2% fee on a pool of 1100, shared by the 500 staked on the winner
+    env.claim_payout(&users[0], outright, 0).await.unwrap();
+    assert_eq!(env.token_balance(users[0].token_account).await, 862);
+}
+
+#[tokio::test]
+async fn eliminated_outcome_cannot_win() {
+    let mut env = TestEnv::new().await;
+    let teams = env.create_teams(2).await;
+    let outright = create_outright(&mut env, &teams).await;
+    let tournament = env.create_tournament(outright).await.unwrap();
+    let grand_final = env.create_market_with_teams(&teams).await;
+    env.add_tournament_match(tournament, grand_final, true).await.unwrap();
+
+    let alice = env.create_user(200).await;
+    let bob = env.create_user(100).await;
+    env.place_bet(&alice, outright, 0, 100).await.unwrap();
+    env.place_bet(&bob, outright, 1, 100).await.unwrap();
+    env.eliminate_outcome(tournament, 1).await.unwrap();
+
+    env.place_bet(&alice, grand_final, 0, 100).await.unwrap();
+    env.end_and_resolve(grand_final, 0).await;
+    let result = env.resolve_market(outright, 1).await;
+    assert_program_error(result, PredictionMarketError::OutcomeEliminated);
+    env.resolve_market(outright, 0).await.unwrap();
+}
+
+#[tokio::test]
+async fn book_keeps_eliminated_outcomes_at_zero() {
+    let mut env = TestEnv::new().await;
+    let teams = env.create_teams(3).await;
+    let outright = create_outright(&mut env, &teams).await;
+    env.create_market_book(outright).await.unwrap();
+    let tournament = env.create_tournament(outright).await.unwrap();
+
+    let alice = env.create_user(200).await;
+    env.place_bet(&alice, outright, 0, 100).await.unwrap();
+    env.place_bet(&alice, outright, 2, 100).await.unwrap();
+    env.eliminate_outcome(tournament, 2).await.unwrap();
+
+    let bob = env.create_user(100).await;
+    env.place_bet(&bob, outright, 1, 100).await.unwrap();
+    let data = env.account_data(market_book_address(outright)).await;
+    let book: MarketBook = bytemuck::pod_read_unaligned(&data[8..MarketBook::SIZE]);
+    assert_eq!(&book.stakes[..3], &[100, 100, 100]);
+    assert_eq!(book.prices[2], 0);
+    assert_eq!(book.eliminated[2], 1);
+    assert!(book.prices[0] > 0 && book.prices[1] > 0);
+}
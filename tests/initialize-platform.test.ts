@@ -13,8 +13,6 @@ describe("Initialize Platform", () => {
   
   // Admin keypair for testing
   const admin = anchor.web3.Keypair.generate();
-  // Treasury account for collecting fees
-  const treasury = anchor.web3.Keypair.generate();
   
   // Generate the platform config PDA
   const [platformConfigPDA] = PublicKey.findProgramAddressSync(
@@ -36,7 +34,6 @@ describe("Initialize Platform", () => {
       // Prepare the accounts for the instruction
       const accounts = {
         admin: admin.publicKey,
-        treasury: treasury.publicKey,
         platformConfig: platformConfigPDA, // Using camelCase as required by Anchor
         systemProgram: SystemProgram.programId,
       };
@@ -59,11 +56,17 @@ describe("Initialize Platform", () => {
       // Verify the platform configuration was set correctly using the actual field names
       assert.ok(platformConfig.admin.toBase58() === admin.publicKey.toBase58(), "Admin key should match");
       assert.ok(platformConfig.oracleAuthority.toBase58() === admin.publicKey.toBase58(), "Oracle authority should initially be the admin");
-      assert.ok(platformConfig.treasury.toBase58() === treasury.publicKey.toBase58(), "Treasury key should match");
-      assert.equal(platformConfig.defaultFeePercentage, 2, "Default fee percentage should be 2%");
+      assert.equal(platformConfig.version, 1, "Platform config should be at the current layout version");
+      assert.equal(platformConfig.defaultFeeBps, 200, "Default fee should be 200 bps (2%)");
       assert.equal(platformConfig.marketsCount.toNumber(), 0, "Markets count should start at 0");
       assert.equal(platformConfig.totalVolume.toNumber(), 0, "Total volume should start at 0");
       assert.equal(platformConfig.paused, false, "Platform should not start paused");
+      assert.equal(platformConfig.referralShareBps, 0, "Referral share should start at 0");
+      assert.equal(platformConfig.maxCreatorFeeBps, 0, "Creator fees should start disallowed");
+      assert.equal(platformConfig.lpFeeShareBps, 0, "LP fee share should start at 0");
+
+      // Fees are collected per collateral mint, so there is no platform treasury
+      assert.notProperty(platformConfig, "treasury", "Treasuries live on each collateral mint's config");
       
       console.log("Platform configuration initialized and verified successfully");
     } catch (error) {
@@ -0,0 +1,252 @@
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use anchor_client::anchor_lang::{AccountDeserialize, Discriminator};
+use anchor_client::solana_account_decoder::UiAccountEncoding;
+use anchor_client::solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use anchor_client::solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use anchor_client::solana_sdk::commitment_config::CommitmentConfig;
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anchor_client::solana_sdk::signature::{Keypair, Signature, Signer};
+use anchor_client::anchor_lang::system_program;
+use anchor_client::{Client, Cluster, Program};
+use anchor_spl::associated_token::{self, get_associated_token_address_with_program_id};
+use anyhow::{Context, Result};
+use prediction_market::state::{
+    CollateralConfig, CollateralMode, FeeModel, Market, MarketBook, Outcome, PlatformConfig,
+};
+
+use crate::plan::{Action, ExistingMarket, ExistingMarkets, PlatformDefaults};
+use crate::schedule::DesiredMarket;
+
+/// Accounts that decoded, with their addresses, and the addresses of those
+/// that didn't.
+type DecodedAccounts<T> = (Vec<(Pubkey, T)>, Vec<Pubkey>);
+
+pub struct Chain {
+    program: Program<Rc<Keypair>>,
+    payer: Rc<Keypair>,
+}
+
+impl Chain {
+    pub fn new(cluster: Cluster, payer: Rc<Keypair>) -> Result<Self> {
+        let client = Client::new_with_options(cluster, payer.clone(), CommitmentConfig::confirmed());
+        let program = client.program(prediction_market::ID)?;
+        Ok(Self { program, payer })
+    }
+
+    fn platform_config_address() -> Pubkey {
+        Pubkey::find_program_address(
+            &[PlatformConfig::SEED_PREFIX.as_bytes()],
+            &prediction_market::ID,
+        )
+        .0
+    }
+
+    fn platform_config(&self) -> Result<PlatformConfig> {
+        self.program
+            .account(Self::platform_config_address())
+            .context("fetching platform config; is the platform initialized?")
+    }
+
+    pub fn platform_defaults(&self) -> Result<PlatformDefaults> {
+        let config = self.platform_config()?;
+        Ok(PlatformDefaults {
+            fee_bps: config.default_fee_bps,
+            oracle: config.oracle_authority,
+        })
+    }
+
+    /// Every market on chain with the outcomes it has. Accounts that don't
+    /// decode, e.g. ones still waiting on a `migrate_*` call, are listed
+    /// rather than failing the whole diff.
+    pub fn existing_markets(&self) -> Result<ExistingMarkets> {
+        let (outcomes, undecodable_outcomes) = self.decoded_accounts::<Outcome>()?;
+        let mut outcome_ids: HashMap<Pubkey, Vec<u8>> = HashMap::new();
+        for (_, outcome) in outcomes {
+            outcome_ids.entry(outcome.market).or_default().push(outcome.id);
+        }
+
+        let (markets, mut undecodable) = self.decoded_accounts::<Market>()?;
+        undecodable.extend(undecodable_outcomes);
+        Ok(ExistingMarkets {
+            markets: markets
+                .into_iter()
+                .map(|(address, market)| ExistingMarket {
+                    address,
+                    outcome_ids: outcome_ids.remove(&address).unwrap_or_default(),
+                    market,
+                })
+                .collect(),
+            undecodable,
+        })
+    }
+
+    /// Every program account of type `T`, split into those that decode and
+    /// the addresses of those that don't.
+    fn decoded_accounts<T: AccountDeserialize + Discriminator>(
+        &self,
+    ) -> Result<DecodedAccounts<T>> {
+        let config = RpcProgramAccountsConfig {
+            filters: Some(vec![RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+                0,
+                T::DISCRIMINATOR,
+            ))]),
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                ..RpcAccountInfoConfig::default()
+            },
+            ..RpcProgramAccountsConfig::default()
+        };
+
+        let mut decoded = Vec::new();
+        let mut undecodable = Vec::new();
+        for (address, account) in self
+            .program
+            .rpc()
+            .get_program_accounts_with_config(&prediction_market::ID, config)
+            .context("fetching program accounts")?
+        {
+            match T::try_deserialize(&mut account.data.as_slice()) {
+                Ok(value) => decoded.push((address, value)),
+                Err(_) => undecodable.push(address),
+            }
+        }
+        Ok((decoded, undecodable))
+    }
+
+    /// SPL Token or Token-2022, whichever owns the collateral mint.
+    fn token_program(&self, mint: Pubkey) -> Result<Pubkey> {
+        let account = self
+            .program
+            .rpc()
+            .get_account(&mint)
+            .with_context(|| format!("fetching collateral mint {}", mint))?;
+        Ok(account.owner)
+    }
+
+    /// Sends the transactions for one planned action, in order.
+    pub fn execute(&self, action: &Action, mint: Pubkey) -> Result<Vec<Signature>> {
+        match action {
+            Action::CreateMarket(desired) => self.create_market(desired, mint),
+            Action::AddOutcome {
+                market,
+                outcome_id,
+                name,
+                ..
+            } => Ok(vec![self.add_outcome(*market, *outcome_id, name, mint)?]),
+        }
+    }
+
+    fn create_market(&self, desired: &DesiredMarket, mint: Pubkey) -> Result<Vec<Signature>> {
+        // The market PDA is seeded by the platform's running market count
+        let platform_config = Self::platform_config_address();
+        let markets_count = self.platform_config()?.markets_count;
+        let (market, _) = Pubkey::find_program_address(
+            &[b"market", markets_count.to_le_bytes().as_ref()],
+            &prediction_market::ID,
+        );
+        let (collateral_config, _) = Pubkey::find_program_address(
+            &[CollateralConfig::SEED_PREFIX.as_bytes(), mint.as_ref()],
+            &prediction_market::ID,
+        );
+
+        let signature = self
+            .program
+            .request()
+            .accounts(prediction_market::accounts::CreateMarket {
+                creator: self.payer.pubkey(),
+                platform_config,
+                market,
+                mint,
+                collateral_config,
+                tournament: None,
+                tournament_market: None,
+                system_program: system_program::ID,
+            })
+            .args(prediction_market::instruction::CreateMarket {
+                name: desired.name.clone(),
+                description: desired.description.clone(),
+                start_time: desired.start_time,
+                end_time: desired.end_time,
+                custom_fee_bps: desired.fee_bps,
+                custom_oracle: desired.oracle,
+                collateral_mode: CollateralMode::Token,
+            })
+            .send()
+            .with_context(|| format!("creating market `{}`", desired.name))?;
+
+        let mut signatures = vec![signature];
+        if desired.fee_model != FeeModel::TotalPool {
+            signatures.push(
+                self.program
+                    .request()
+                    .accounts(prediction_market::accounts::SetFeeModel {
+                        market,
+                        platform_config,
+                        admin: self.payer.pubkey(),
+                    })
+                    .args(prediction_market::instruction::SetFeeModel {
+                        fee_model: desired.fee_model,
+                    })
+                    .send()
+                    .with_context(|| format!("setting the fee model of `{}`", desired.name))?,
+            );
+        }
+        for (outcome_id, name) in &desired.outcomes {
+            signatures.push(self.add_outcome(market, *outcome_id, name, mint)?);
+        }
+        Ok(signatures)
+    }
+
+    fn add_outcome(&self, market: Pubkey, outcome_id: u8, name: &str, mint: Pubkey) -> Result<Signature> {
+        let id_bytes = outcome_id.to_le_bytes();
+        let (outcome, _) = Pubkey::find_program_address(
+            &[b"outcome", market.as_ref(), &id_bytes],
+            &prediction_market::ID,
+        );
+        let (escrow_authority, _) = Pubkey::find_program_address(
+            &[b"escrow", market.as_ref(), &id_bytes],
+            &prediction_market::ID,
+        );
+        let token_program = self.token_program(mint)?;
+        // Markets given a book list every outcome in it
+        let market_book = self.program.account::<Market>(market)?.has_book.then(|| {
+            Pubkey::find_program_address(
+                &[MarketBook::SEED_PREFIX.as_bytes(), market.as_ref()],
+                &prediction_market::ID,
+            )
+            .0
+        });
+
+        self.program
+            .request()
+            .accounts(prediction_market::accounts::AddOutcome {
+                market,
+                platform_config: Self::platform_config_address(),
+                authority: self.payer.pubkey(),
+                outcome,
+                escrow_authority,
+                mint,
+                escrow_token_account: get_associated_token_address_with_program_id(
+                    &escrow_authority,
+                    &mint,
+                    &token_program,
+                ),
+                market_book,
+                team: None,
+                team_outcome: None,
+                opponent_outcome: None,
+                opponent_team: None,
+                token_program,
+                associated_token_program: associated_token::ID,
+                system_program: system_program::ID,
+            })
+            .args(prediction_market::instruction::AddOutcome {
+                name: name.to_string(),
+                outcome_id,
+            })
+            .send()
+            .with_context(|| format!("adding outcome `{}` to {}", name, market))
+    }
+}
@@ -0,0 +1,98 @@
+//! Converges on-chain prediction markets with a tournament schedule file.
+//!
+//! `plan` prints the `create_market`/`add_outcome` transactions the schedule
+//! requires, `apply` sends them. Running `apply` twice on the same file is a
+//! no-op the second time.
+
+mod chain;
+mod plan;
+mod schedule;
+
+use std::path::PathBuf;
+use std::rc::Rc;
+
+use anchor_client::solana_sdk::signature::read_keypair_file;
+use anchor_client::Cluster;
+use anyhow::{anyhow, Result};
+use clap::{Parser, Subcommand};
+
+use crate::chain::Chain;
+use crate::schedule::Schedule;
+
+#[derive(Parser)]
+#[command(name = "market-schedule", about = "Bulk-create markets from a schedule file")]
+struct Cli {
+    /// Cluster name (localnet, devnet, mainnet) or RPC URL
+    #[arg(long, default_value = "localnet")]
+    cluster: Cluster,
+
+    /// Admin keypair used to pay for and sign market creation
+    #[arg(long, default_value = "~/.config/solana/id.json")]
+    keypair: String,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Show the transactions needed to converge, without sending them
+    Plan { schedule: PathBuf },
+    /// Send the transactions needed to converge
+    Apply { schedule: PathBuf },
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    let keypair_path = shellexpand_home(&cli.keypair);
+    let payer = read_keypair_file(&keypair_path)
+        .map_err(|err| anyhow!("reading keypair {}: {}", keypair_path, err))?;
+    let chain = Chain::new(cli.cluster, Rc::new(payer))?;
+
+    let (path, apply) = match cli.command {
+        Command::Plan { schedule } => (schedule, false),
+        Command::Apply { schedule } => (schedule, true),
+    };
+
+    let schedule = Schedule::load(&path)?;
+    let desired = schedule.desired_markets()?;
+    let mint = schedule.mint()?;
+
+    let plan = plan::plan(
+        &desired,
+        &chain.existing_markets()?,
+        chain.platform_defaults()?,
+        chrono::Utc::now().timestamp(),
+    )?;
+
+    for warning in &plan.warnings {
+        eprintln!("warning: {}", warning);
+    }
+    if plan.is_empty() {
+        println!("Up to date: {} markets in schedule", desired.len());
+        return Ok(());
+    }
+
+    for action in &plan.actions {
+        println!("{}", action);
+        if apply {
+            for signature in chain.execute(action, mint)? {
+                println!("  {}", signature);
+            }
+        }
+    }
+
+    if !apply {
+        println!("{} actions pending; run `apply` to send them", plan.actions.len());
+    }
+
+    Ok(())
+}
+
+fn shellexpand_home(path: &str) -> String {
+    match (path.strip_prefix("~/"), std::env::var("HOME")) {
+        (Some(rest), Ok(home)) => format!("{}/{}", home, rest),
+        _ => path.to_string(),
+    }
+}
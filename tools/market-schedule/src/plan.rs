@@ -0,0 +1,346 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anyhow::{bail, Result};
+use prediction_market::state::{Market, MarketStatus};
+
+use crate::schedule::DesiredMarket;
+
+/// On-chain state the planner diffs against: a market and the ids of the
+/// `Outcome` accounts that actually exist for it.
+#[derive(Clone)]
+pub struct ExistingMarket {
+    pub address: Pubkey,
+    pub market: Market,
+    pub outcome_ids: Vec<u8>,
+}
+
+/// All markets read from chain, and the program accounts that could not be
+/// decoded. Those may be markets whose names the planner cannot see.
+#[derive(Clone, Default)]
+pub struct ExistingMarkets {
+    pub markets: Vec<ExistingMarket>,
+    pub undecodable: Vec<Pubkey>,
+}
+
+/// Platform-wide values a market inherits when the schedule doesn't override them.
+#[derive(Debug, Clone, Copy)]
+pub struct PlatformDefaults {
+    pub fee_bps: u16,
+    pub oracle: Pubkey,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Action {
+    /// Create the market, then add all of its outcomes
+    CreateMarket(DesiredMarket),
+    /// Add a missing outcome to an existing market
+    AddOutcome {
+        market: Pubkey,
+        market_name: String,
+        outcome_id: u8,
+        name: String,
+    },
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Action::CreateMarket(desired) => write!(
+                f,
+                "create market `{}` with {} outcomes",
+                desired.name,
+                desired.outcomes.len()
+            ),
+            Action::AddOutcome {
+                market_name,
+                outcome_id,
+                name,
+                ..
+            } => write!(f, "add outcome {} `{}` to `{}`", outcome_id, name, market_name),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Plan {
+    pub actions: Vec<Action>,
+    /// Differences that no instruction can converge (e.g. a changed start time)
+    pub warnings: Vec<String>,
+}
+
+impl Plan {
+    pub fn is_empty(&self) -> bool {
+        self.actions.is_empty()
+    }
+}
+
+/// Computes the actions needed to make chain state match the schedule.
+///
+/// Markets are matched by `Market.name`, so re-running against an unchanged
+/// schedule yields no actions. A scheduled name held by more than one market
+/// on chain is an error, since there is no telling which one to converge.
+/// Existing markets are never modified; fields that drifted from the schedule
+/// are reported as warnings instead.
+pub fn plan(
+    desired: &[DesiredMarket],
+    existing: &ExistingMarkets,
+    defaults: PlatformDefaults,
+    now: i64,
+) -> Result<Plan> {
+    let mut by_name: HashMap<&str, Vec<&ExistingMarket>> = HashMap::new();
+    for existing in &existing.markets {
+        by_name.entry(existing.market.name.as_str()).or_default().push(existing);
+    }
+
+    let mut plan = Plan::default();
+    for address in &existing.undecodable {
+        plan.warnings.push(format!(
+            "account {} could not be decoded and is not matched against the schedule; migrate it first",
+            address
+        ));
+    }
+
+    for want in desired {
+        let matches = by_name.get(want.name.as_str()).map(Vec::as_slice).unwrap_or_default();
+        if matches.len() > 1 {
+            let addresses: Vec<String> = matches.iter().map(|have| have.address.to_string()).collect();
+            bail!(
+                "`{}` matches {} markets on chain ({}); rename or cancel the extras",
+                want.name,
+                matches.len(),
+                addresses.join(", ")
+            );
+        }
+        let Some(have) = matches.first() else {
+            if want.start_time <= now {
+                plan.warnings.push(format!(
+                    "`{}` starts in the past and can no longer be created",
+                    want.name
+                ));
+            } else {
+                plan.actions.push(Action::CreateMarket(want.clone()));
+            }
+            continue;
+        };
+
+        check_drift(want, have, defaults, &mut plan.warnings);
+
+        let missing: Vec<_> = want
+            .outcomes
+            .iter()
+            .filter(|(id, _)| !have.outcome_ids.contains(id))
+            .collect();
+        if missing.is_empty() {
+            continue;
+        }
+
+        if have.market.status != MarketStatus::Active {
+            plan.warnings.push(format!(
+                "`{}` is missing {} outcomes but is no longer active",
+                want.name,
+                missing.len()
+            ));
+            continue;
+        }
+
+        if have.market.total_pool > 0 || have.market.has_liquidity {
+            plan.warnings.push(format!(
+                "`{}` is missing {} outcomes but already has bets",
+                want.name,
+                missing.len()
+            ));
+            continue;
+        }
+
+        for (outcome_id, name) in missing {
+            plan.actions.push(Action::AddOutcome {
+                market: have.address,
+                market_name: want.name.clone(),
+                outcome_id: *outcome_id,
+                name: name.clone(),
+            });
+        }
+    }
+
+    Ok(plan)
+}
+
+fn check_drift(
+    want: &DesiredMarket,
+    have: &ExistingMarket,
+    defaults: PlatformDefaults,
+    warnings: &mut Vec<String>,
+) {
+    let market = &have.market;
+    let mut drift = |field: &str, want: String, have: String| {
+        if want != have {
+            warnings.push(format!(
+                "`{}` {} is {} on chain but {} in the schedule",
+                market.name, field, have, want
+            ));
+        }
+    };
+
+    drift("description", want.description.clone(), market.description.clone());
+    drift("start_time", want.start_time.to_string(), market.start_time.to_string());
+    drift("end_time", want.end_time.to_string(), market.end_time.to_string());
+    drift(
+        "fee_bps",
+        want.fee_bps.unwrap_or(defaults.fee_bps).to_string(),
+        market.fee_bps.to_string(),
+    );
+    drift(
+        "fee_model",
+        format!("{:?}", want.fee_model),
+        format!("{:?}", market.fee_model),
+    );
+    drift(
+        "oracle",
+        want.oracle.unwrap_or(defaults.oracle).to_string(),
+        market.oracle.to_string(),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prediction_market::state::FeeModel;
+
+    const NOW: i64 = 1_700_000_000;
+
+    fn defaults() -> PlatformDefaults {
+        PlatformDefaults {
+            fee_bps: 200,
+            oracle: Pubkey::new_unique(),
+        }
+    }
+
+    fn desired(name: &str) -> DesiredMarket {
+        DesiredMarket {
+            name: name.to_string(),
+            description: "VCT Grand Final".to_string(),
+            start_time: NOW + 3600,
+            end_time: NOW + 7200,
+            fee_bps: None,
+            fee_model: FeeModel::TotalPool,
+            oracle: None,
+            outcomes: vec![(0, "TSM wins".to_string()), (1, "Cloud9 wins".to_string())],
+        }
+    }
+
+    fn chain(markets: &[ExistingMarket]) -> ExistingMarkets {
+        ExistingMarkets {
+            markets: markets.to_vec(),
+            undecodable: Vec::new(),
+        }
+    }
+
+    fn on_chain(want: &DesiredMarket, defaults: PlatformDefaults, outcome_ids: &[u8]) -> ExistingMarket {
+        ExistingMarket {
+            address: Pubkey::new_unique(),
+            market: Market {
+                name: want.name.clone(),
+                description: want.description.clone(),
+                start_time: want.start_time,
+                end_time: want.end_time,
+                fee_bps: defaults.fee_bps,
+                oracle: defaults.oracle,
+                outcomes: outcome_ids.to_vec(),
+                ..Market::default()
+            },
+            outcome_ids: outcome_ids.to_vec(),
+        }
+    }
+
+    #[test]
+    fn creates_missing_markets() {
+        let want = desired("TSM vs Cloud9");
+        let plan = plan(std::slice::from_ref(&want), &chain(&[]), defaults(), NOW).unwrap();
+        assert_eq!(plan.actions, vec![Action::CreateMarket(want)]);
+        assert!(plan.warnings.is_empty());
+    }
+
+    #[test]
+    fn converged_state_is_a_no_op() {
+        let defaults = defaults();
+        let want = desired("TSM vs Cloud9");
+        let have = on_chain(&want, defaults, &[0, 1]);
+        let plan = plan(&[want], &chain(&[have]), defaults, NOW).unwrap();
+        assert!(plan.is_empty());
+        assert!(plan.warnings.is_empty());
+    }
+
+    #[test]
+    fn adds_only_missing_outcomes() {
+        let defaults = defaults();
+        let want = desired("TSM vs Cloud9");
+        let have = on_chain(&want, defaults, &[0]);
+        let plan = plan(&[want], &chain(std::slice::from_ref(&have)), defaults, NOW).unwrap();
+        assert_eq!(
+            plan.actions,
+            vec![Action::AddOutcome {
+                market: have.address,
+                market_name: "TSM vs Cloud9".to_string(),
+                outcome_id: 1,
+                name: "Cloud9 wins".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn leaves_outcomes_of_markets_with_bets_alone() {
+        let defaults = defaults();
+        let want = desired("TSM vs Cloud9");
+        let mut have = on_chain(&want, defaults, &[0]);
+        have.market.total_pool = 100;
+        let plan = plan(&[want], &chain(&[have]), defaults, NOW).unwrap();
+        assert!(plan.is_empty());
+        assert_eq!(plan.warnings, vec!["`TSM vs Cloud9` is missing 1 outcomes but already has bets"]);
+    }
+
+    #[test]
+    fn reports_drift_without_acting() {
+        let defaults = defaults();
+        let mut want = desired("TSM vs Cloud9");
+        let have = on_chain(&want, defaults, &[0, 1]);
+        want.start_time += 600;
+        want.fee_bps = Some(500);
+        want.fee_model = FeeModel::AtBet;
+        let plan = plan(&[want], &chain(&[have]), defaults, NOW).unwrap();
+        assert!(plan.is_empty());
+        assert_eq!(plan.warnings.len(), 3);
+    }
+
+    #[test]
+    fn skips_markets_that_already_started() {
+        let mut want = desired("TSM vs Cloud9");
+        want.start_time = NOW - 1;
+        let plan = plan(&[want], &chain(&[]), defaults(), NOW).unwrap();
+        assert!(plan.is_empty());
+        assert_eq!(plan.warnings.len(), 1);
+    }
+
+    #[test]
+    fn rejects_names_held_by_several_markets() {
+        let defaults = defaults();
+        let want = desired("TSM vs Cloud9");
+        let first = on_chain(&want, defaults, &[0, 1]);
+        let second = on_chain(&want, defaults, &[0]);
+        let result = plan(&[want], &chain(&[first, second]), defaults, NOW);
+        assert!(result.unwrap_err().to_string().contains("matches 2 markets"));
+    }
+
+    #[test]
+    fn reports_undecodable_accounts() {
+        let want = desired("TSM vs Cloud9");
+        let existing = ExistingMarkets {
+            markets: Vec::new(),
+            undecodable: vec![Pubkey::new_unique()],
+        };
+        let plan = plan(std::slice::from_ref(&want), &existing, defaults(), NOW).unwrap();
+        assert_eq!(plan.actions, vec![Action::CreateMarket(want)]);
+        assert_eq!(plan.warnings.len(), 1);
+    }
+}
@@ -0,0 +1,350 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::DateTime;
+use prediction_market::state::{
+    FeeModel, MAX_MARKET_DESCRIPTION_LEN, MAX_MARKET_NAME_LEN, MAX_OUTCOMES, MAX_OUTCOME_NAME_LEN,
+};
+use serde::{Deserialize, Deserializer};
+
+/// A tournament schedule as written by operators, in TOML or JSON.
+///
+/// Top-level `tournament`, `oracle`, `fee_bps` and `fee_model` act as
+/// defaults for every entry in `markets`; each entry may override them.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Schedule {
+    /// Collateral mint for every market; it must be on the platform allowlist
+    pub mint: String,
+    pub tournament: Option<String>,
+    pub oracle: Option<String>,
+    pub fee_bps: Option<u16>,
+    #[serde(default, deserialize_with = "optional_fee_model")]
+    pub fee_model: Option<FeeModel>,
+    #[serde(default)]
+    pub markets: Vec<MatchEntry>,
+}
+
+/// One match on the schedule; becomes one `Market` with one `Outcome` per team.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MatchEntry {
+    pub tournament: Option<String>,
+    #[serde(rename = "match")]
+    pub match_name: String,
+    pub teams: Vec<String>,
+    #[serde(deserialize_with = "timestamp")]
+    pub start_time: i64,
+    /// When betting closes; defaults to `start_time` plus the format's duration
+    #[serde(default, deserialize_with = "optional_timestamp")]
+    pub end_time: Option<i64>,
+    #[serde(default)]
+    pub format: SeriesFormat,
+    pub fee_bps: Option<u16>,
+    /// `total_pool`, `net_winnings` or `at_bet`; defaults to `total_pool`
+    #[serde(default, deserialize_with = "optional_fee_model")]
+    pub fee_model: Option<FeeModel>,
+    pub oracle: Option<String>,
+    /// Overrides the generated `Market.name`
+    pub name: Option<String>,
+    /// Overrides the generated `Market.description`
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SeriesFormat {
+    Bo1,
+    #[default]
+    Bo3,
+    Bo5,
+}
+
+impl SeriesFormat {
+    /// Generous upper bound on how long a series of this format runs
+    pub fn expected_duration_secs(self) -> i64 {
+        match self {
+            SeriesFormat::Bo1 => 2 * 3600,
+            SeriesFormat::Bo3 => 4 * 3600,
+            SeriesFormat::Bo5 => 6 * 3600,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SeriesFormat::Bo1 => "best of 1",
+            SeriesFormat::Bo3 => "best of 3",
+            SeriesFormat::Bo5 => "best of 5",
+        }
+    }
+}
+
+/// A schedule entry resolved into exactly what should exist on chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DesiredMarket {
+    pub name: String,
+    pub description: String,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub fee_bps: Option<u16>,
+    pub fee_model: FeeModel,
+    pub oracle: Option<Pubkey>,
+    /// `(outcome_id, outcome name)` in team order
+    pub outcomes: Vec<(u8, String)>,
+}
+
+impl Schedule {
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("reading schedule {}", path.display()))?;
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => serde_json::from_str(&contents)
+                .with_context(|| format!("parsing {} as JSON", path.display())),
+            Some("toml") => toml::from_str(&contents)
+                .with_context(|| format!("parsing {} as TOML", path.display())),
+            _ => bail!("schedule must be a .toml or .json file: {}", path.display()),
+        }
+    }
+
+    pub fn mint(&self) -> Result<Pubkey> {
+        parse_pubkey(&self.mint).context("schedule `mint`")
+    }
+
+    /// Applies defaults and validates every entry.
+    pub fn desired_markets(&self) -> Result<Vec<DesiredMarket>> {
+        let mut names = HashSet::new();
+        let mut markets = Vec::with_capacity(self.markets.len());
+
+        for entry in &self.markets {
+            let market = self
+                .resolve(entry)
+                .with_context(|| format!("schedule entry `{}`", entry.match_name))?;
+            if !names.insert(market.name.clone()) {
+                bail!("duplicate market name `{}` in schedule", market.name);
+            }
+            markets.push(market);
+        }
+
+        Ok(markets)
+    }
+
+    fn resolve(&self, entry: &MatchEntry) -> Result<DesiredMarket> {
+        let tournament = entry
+            .tournament
+            .as_ref()
+            .or(self.tournament.as_ref())
+            .ok_or_else(|| anyhow!("no tournament set on the entry or the schedule"))?;
+
+        if entry.teams.len() < 2 {
+            bail!("a match needs at least two teams");
+        }
+        if entry.teams.len() > MAX_OUTCOMES {
+            bail!("a market can have at most {} teams", MAX_OUTCOMES);
+        }
+
+        let end_time = entry
+            .end_time
+            .unwrap_or(entry.start_time + entry.format.expected_duration_secs());
+        if end_time <= entry.start_time {
+            bail!("end_time must be after start_time");
+        }
+
+        let oracle = entry
+            .oracle
+            .as_ref()
+            .or(self.oracle.as_ref())
+            .map(|key| parse_pubkey(key).context("`oracle`"))
+            .transpose()?;
+
+        let name = entry.name.clone().unwrap_or_else(|| {
+            format!(
+                "{} - {} {}",
+                entry.teams.join(" vs "),
+                tournament,
+                entry.match_name
+            )
+        });
+        let description = entry.description.clone().unwrap_or_else(|| {
+            format!(
+                "{} {}: {}, {}",
+                tournament,
+                entry.match_name,
+                entry.teams.join(" vs "),
+                entry.format.label()
+            )
+        });
+
+        let outcomes = entry
+            .teams
+            .iter()
+            .enumerate()
+            .map(|(id, team)| (id as u8, format!("{} wins", team)))
+            .collect::<Vec<_>>();
+
+        if name.len() > MAX_MARKET_NAME_LEN {
+            bail!("market name `{}` is longer than {} bytes", name, MAX_MARKET_NAME_LEN);
+        }
+        if description.len() > MAX_MARKET_DESCRIPTION_LEN {
+            bail!("description is longer than {} bytes", MAX_MARKET_DESCRIPTION_LEN);
+        }
+        if let Some((_, outcome)) = outcomes.iter().find(|(_, outcome)| outcome.len() > MAX_OUTCOME_NAME_LEN) {
+            bail!("outcome name `{}` is longer than {} bytes", outcome, MAX_OUTCOME_NAME_LEN);
+        }
+
+        Ok(DesiredMarket {
+            name,
+            description,
+            start_time: entry.start_time,
+            end_time,
+            fee_bps: entry.fee_bps.or(self.fee_bps),
+            fee_model: entry.fee_model.or(self.fee_model).unwrap_or_default(),
+            oracle,
+            outcomes,
+        })
+    }
+}
+
+fn parse_pubkey(key: &str) -> Result<Pubkey> {
+    Pubkey::from_str(key).map_err(|err| anyhow!("invalid pubkey `{}`: {}", key, err))
+}
+
+/// Accepts either a unix timestamp or an RFC 3339 string
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawTimestamp {
+    Unix(i64),
+    Rfc3339(String),
+}
+
+impl RawTimestamp {
+    fn into_unix<E: serde::de::Error>(self) -> std::result::Result<i64, E> {
+        match self {
+            RawTimestamp::Unix(ts) => Ok(ts),
+            RawTimestamp::Rfc3339(s) => DateTime::parse_from_rfc3339(&s)
+                .map(|dt| dt.timestamp())
+                .map_err(|err| E::custom(format!("invalid RFC 3339 timestamp `{}`: {}", s, err))),
+        }
+    }
+}
+
+fn timestamp<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<i64, D::Error> {
+    RawTimestamp::deserialize(deserializer)?.into_unix()
+}
+
+fn optional_fee_model<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<Option<FeeModel>, D::Error> {
+    Option::<String>::deserialize(deserializer)?
+        .map(|name| match name.as_str() {
+            "total_pool" => Ok(FeeModel::TotalPool),
+            "net_winnings" => Ok(FeeModel::NetWinnings),
+            "at_bet" => Ok(FeeModel::AtBet),
+            _ => Err(serde::de::Error::custom(format!(
+                "unknown fee model `{}`, expected total_pool, net_winnings or at_bet",
+                name
+            ))),
+        })
+        .transpose()
+}
+
+fn optional_timestamp<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<Option<i64>, D::Error> {
+    Option::<RawTimestamp>::deserialize(deserializer)?
+        .map(RawTimestamp::into_unix)
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toml_and_json_schedules_agree() {
+        let toml_schedule = Schedule::load(Path::new(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/schedules/example.toml"
+        )))
+        .unwrap();
+        let json_schedule: Schedule = serde_json::from_str(
+            r#"{
+                "mint": "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+                "tournament": "Valorant Champions 2025",
+                "markets": [
+                    {
+                        "match": "Upper Final",
+                        "teams": ["Fnatic", "Paper Rex"],
+                        "start_time": 1759590000,
+                        "format": "bo3"
+                    },
+                    {
+                        "match": "Grand Final",
+                        "teams": ["TSM", "Cloud9"],
+                        "start_time": "2025-10-05T18:00:00Z",
+                        "format": "bo5",
+                        "fee_bps": 300,
+                        "fee_model": "net_winnings"
+                    }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let markets = toml_schedule.desired_markets().unwrap();
+        assert_eq!(markets, json_schedule.desired_markets().unwrap());
+
+        let grand_final = &markets[1];
+        assert_eq!(grand_final.name, "TSM vs Cloud9 - Valorant Champions 2025 Grand Final");
+        assert_eq!(grand_final.end_time - grand_final.start_time, 6 * 3600);
+        assert_eq!(grand_final.fee_bps, Some(300));
+        assert_eq!(grand_final.fee_model, FeeModel::NetWinnings);
+        assert_eq!(markets[0].fee_model, FeeModel::TotalPool);
+        assert_eq!(
+            grand_final.outcomes,
+            vec![(0, "TSM wins".to_string()), (1, "Cloud9 wins".to_string())]
+        );
+    }
+
+    #[test]
+    fn rejects_duplicate_markets() {
+        let mut schedule: Schedule = toml::from_str(
+            r#"
+            mint = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v"
+            tournament = "Masters Toronto"
+
+            [[markets]]
+            match = "Final"
+            teams = ["G2", "Sentinels"]
+            start_time = 1750000000
+            "#,
+        )
+        .unwrap();
+        schedule.markets.push(schedule.markets[0].clone());
+
+        assert!(schedule.desired_markets().is_err());
+    }
+
+    #[test]
+    fn rejects_names_the_program_cannot_store() {
+        let mut schedule: Schedule = toml::from_str(
+            r#"
+            mint = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v"
+            tournament = "Masters Toronto"
+
+            [[markets]]
+            match = "Final"
+            teams = ["G2", "Sentinels"]
+            start_time = 1750000000
+            "#,
+        )
+        .unwrap();
+        assert!(schedule.desired_markets().is_ok());
+
+        schedule.markets[0].name = Some("x".repeat(MAX_MARKET_NAME_LEN + 1));
+        assert!(schedule.desired_markets().is_err());
+    }
+}